@@ -1,3 +1,38 @@
 fn main() {
-    println!("cargo:rustc-link-lib=framework=QuartzCore");
+    let target_os = std::env::var("CARGO_CFG_TARGET_OS").unwrap_or_default();
+    let target_arch = std::env::var("CARGO_CFG_TARGET_ARCH").unwrap_or_default();
+    let target_abi = std::env::var("CARGO_CFG_TARGET_ABI").unwrap_or_default();
+    match target_os.as_str() {
+        "macos" | "ios" | "tvos" | "visionos" => {
+            println!("cargo:rustc-link-lib=framework=QuartzCore")
+        }
+        _ => (),
+    }
+    // Targets with a real display link. Everything else needs the `fallback` feature.
+    println!("cargo:rustc-check-cfg=cfg(native_backend)");
+    let native = match target_os.as_str() {
+        "android" | "ios" | "linux" | "macos" | "tvos" | "visionos" | "windows" => true,
+        "unknown" => target_arch == "wasm32",
+        _ => false,
+    };
+    if native {
+        println!("cargo:rustc-cfg=native_backend");
+    }
+    // Mac Catalyst targets are `target_os = "ios"`, but run on macOS.
+    println!("cargo:rustc-check-cfg=cfg(catalyst)");
+    if target_os == "ios" && target_abi == "macabi" {
+        println!("cargo:rustc-cfg=catalyst");
+    }
+    // Simulator targets either have a `sim` ABI, or are the x86_64 targets predating it.
+    println!("cargo:rustc-check-cfg=cfg(simulator)");
+    let apple_mobile = matches!(target_os.as_str(), "ios" | "tvos" | "visionos");
+    if apple_mobile && (target_abi == "sim" || (target_arch == "x86_64" && target_abi != "macabi"))
+    {
+        println!("cargo:rustc-cfg=simulator");
+    }
+    // `std::time::Instant::now` panics on `wasm32-unknown-unknown`.
+    println!("cargo:rustc-check-cfg=cfg(no_instant)");
+    if target_arch == "wasm32" && target_os == "unknown" {
+        println!("cargo:rustc-cfg=no_instant");
+    }
 }
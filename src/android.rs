@@ -0,0 +1,203 @@
+#![cfg(target_os = "android")]
+
+use crate::{
+    callback::Park,
+    frame::{DiscontinuityCallback, Epoch, RateCallback},
+    worker::Worker,
+    Backend, Display, FrameTime, FrameTimestamp, PauseError, ResumeError, SetDisplayError,
+};
+use ndk_sys::{
+    AChoreographer, AChoreographer_getInstance, AChoreographer_postFrameCallback64,
+    ALooper_pollOnce, ALooper_prepare,
+};
+use std::{ffi::c_void, ptr, task::Waker};
+use time_point::TimePoint;
+
+/// An Android display id. The Choreographer always follows the default display, `0`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DisplayId(pub i32);
+
+impl DisplayId {
+    /// The id reported when the display driving a link is unknown, e.g. for the timer fallback.
+    pub const UNKNOWN: DisplayId = DisplayId(-1);
+}
+
+/// A `CLOCK_MONOTONIC` time, in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PlatformTimestamp(pub i64);
+
+impl PlatformTimestamp {
+    pub(crate) fn now() -> Self {
+        let mut ts = libc::timespec {
+            tv_sec:  0,
+            tv_nsec: 0,
+        };
+        unsafe {
+            libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts);
+        }
+        PlatformTimestamp(ts.tv_sec as i64 * 1_000_000_000 + ts.tv_nsec as i64)
+    }
+
+    pub(crate) fn seconds_since(&self, earlier: Self) -> f64 {
+        (self.0 - earlier.0) as f64 / 1e9
+    }
+
+    pub(crate) fn to_time_point(self, epoch: &Epoch) -> TimePoint {
+        TimePoint::from_std_instant(epoch.to_instant(self))
+    }
+}
+
+/// The Choreographer of the worker thread, created on the first wait.
+struct Choreographer {
+    choreographer: *mut AChoreographer,
+    /// The frame time of the last frame callback, if it hasn't been consumed yet.
+    frame_time:    Option<i64>,
+    last:          Option<i64>,
+}
+
+// The Choreographer is only ever touched from the worker thread.
+unsafe impl Send for Choreographer {}
+
+unsafe extern "C" fn frame_callback(frame_time_nanos: i64, data: *mut c_void) {
+    let choreographer = &mut *(data as *mut Choreographer);
+    choreographer.frame_time = Some(frame_time_nanos);
+}
+
+impl Choreographer {
+    fn new() -> Self {
+        Choreographer {
+            choreographer: ptr::null_mut(),
+            frame_time:    None,
+            last:          None,
+        }
+    }
+
+    /// Blocks until the Choreographer's next frame callback.
+    fn wait(&mut self) -> Option<(PlatformTimestamp, DisplayId)> {
+        unsafe {
+            if self.choreographer.is_null() {
+                ALooper_prepare(0);
+                self.choreographer = AChoreographer_getInstance();
+                if self.choreographer.is_null() {
+                    return None;
+                }
+            }
+            AChoreographer_postFrameCallback64(
+                self.choreographer,
+                Some(frame_callback),
+                self as *mut Self as *mut c_void,
+            );
+            while self.frame_time.is_none() {
+                ALooper_pollOnce(-1, ptr::null_mut(), ptr::null_mut(), ptr::null_mut());
+            }
+        }
+        let frame_time = self.frame_time.take()?;
+        // The frame time is the vsync that started this frame, so estimate when it'll be shown
+        // from the previous interval.
+        let next = match self.last.replace(frame_time) {
+            Some(last) => 2 * frame_time - last,
+            None => frame_time,
+        };
+        Some((PlatformTimestamp(next), DisplayId(0)))
+    }
+}
+
+/// Listing displays needs the Java `DisplayManager`, which the NDK doesn't expose.
+pub(crate) fn displays() -> Vec<Display> {
+    Vec::new()
+}
+
+/// A `DisplayLink` driven by `AChoreographer` frame callbacks on a dedicated looper thread.
+#[derive(Debug)]
+pub struct DisplayLink {
+    worker: Worker,
+}
+
+impl DisplayLink {
+    /// Creates a new Android `DisplayLink` instance.
+    ///
+    /// Android _does_ require the callback to be `Send`, as it's called from a dedicated thread.
+    pub fn new<T, F>(callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        let mut choreographer = Box::new(Choreographer::new());
+        let worker = Worker::spawn_link(move || choreographer.wait(), callback)?;
+        Some(DisplayLink { worker })
+    }
+
+    pub fn on_display<T, F>(_display_id: DisplayId, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::new(callback)
+    }
+
+    /// `Backend::Choreographer` is the only backend on Android.
+    pub fn with_backend<T, F>(backend: Backend, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        match backend {
+            Backend::Choreographer => Self::new(callback),
+            _ => None,
+        }
+    }
+
+    pub fn set_display(&mut self, _display_id: DisplayId) -> Result<(), SetDisplayError> {
+        Err(SetDisplayError::Unsupported)
+    }
+
+    pub fn display(&self) -> DisplayId {
+        self.worker.display()
+    }
+
+    pub fn on_refresh_rate_changed(&mut self, callback: RateCallback) {
+        self.worker.on_refresh_rate_changed(callback)
+    }
+
+    pub fn on_clock_discontinuity(&mut self, callback: DiscontinuityCallback) {
+        self.worker.on_clock_discontinuity(callback)
+    }
+
+    pub fn register_waker(&mut self, waker: Waker) {
+        self.worker.register_waker(waker)
+    }
+
+    pub fn backend(&self) -> Backend {
+        Backend::Choreographer
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
+    pub(crate) fn park(&self) -> Park {
+        self.worker.park()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.worker.is_stopped()
+    }
+
+    pub fn pause(&mut self) -> Result<(), PauseError> {
+        if self.is_paused() {
+            Err(PauseError::AlreadyPaused)
+        } else {
+            self.worker.set_paused(true);
+            Ok(())
+        }
+    }
+
+    pub fn resume(&mut self) -> Result<(), ResumeError> {
+        if !self.is_paused() {
+            Err(ResumeError::AlreadyRunning)
+        } else {
+            self.worker.set_paused(false);
+            Ok(())
+        }
+    }
+}
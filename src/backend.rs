@@ -0,0 +1,46 @@
+/// The mechanism driving a `DisplayLink`.
+///
+/// Every variant exists on every platform, so code can match on it without `cfg`s, but only some
+/// are available on each platform.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Backend {
+    /// `CVDisplayLink`, on macOS.
+    CoreVideo,
+    /// `CADisplayLink`, on iOS, tvOS, visionOS, and Mac Catalyst, and on macOS 14 and newer when
+    /// asked for with [`DisplayLink::with_backend`](crate::DisplayLink::with_backend).
+    CoreAnimation,
+    /// `IDXGIOutput::WaitForVBlank`, on Windows.
+    Dxgi,
+    /// The DWM compositor, on Windows.
+    Dwm,
+    /// `wl_surface.frame` callbacks, on Linux.
+    Wayland,
+    /// The X11 Present extension, on Linux.
+    X11,
+    /// `DRM_IOCTL_WAIT_VBLANK`, on Linux.
+    Drm,
+    /// `AChoreographer`, on Android.
+    Choreographer,
+    /// `requestAnimationFrame`, on the web.
+    AnimationFrame,
+    /// A timer ticking at a fixed rate, with the `fallback` feature.
+    Timer,
+}
+
+impl Backend {
+    /// Returns the name of the underlying API, e.g. `"CVDisplayLink"`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Backend::CoreVideo => "CVDisplayLink",
+            Backend::CoreAnimation => "CADisplayLink",
+            Backend::Dxgi => "DXGI",
+            Backend::Dwm => "DWM",
+            Backend::Wayland => "Wayland",
+            Backend::X11 => "X11 Present",
+            Backend::Drm => "DRM",
+            Backend::Choreographer => "AChoreographer",
+            Backend::AnimationFrame => "requestAnimationFrame",
+            Backend::Timer => "timer",
+        }
+    }
+}
@@ -0,0 +1,78 @@
+use crate::{mailbox, DisplayLink, FrameTime};
+use std::sync::{Arc, Condvar, Mutex};
+
+#[derive(Debug, Default)]
+struct State {
+    /// Counts refreshes, so waiters can tell the next one from the one they arrived after.
+    generation: u64,
+    frame:      Option<FrameTime>,
+    /// Set once the `DisplayLink` driving the barrier is dropped.
+    closed:     bool,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    state:   Mutex<State>,
+    condvar: Condvar,
+}
+
+/// Held by the `DisplayLink` callback, releasing every waiter when the link drops it.
+struct Releaser(Arc<Shared>);
+
+impl Releaser {
+    fn release(&self, frame: FrameTime) {
+        let mut state = self.0.state.lock().unwrap();
+        state.generation = state.generation.wrapping_add(1);
+        state.frame = Some(frame);
+        self.0.condvar.notify_all();
+    }
+}
+
+impl Drop for Releaser {
+    fn drop(&mut self) {
+        self.0.state.lock().unwrap().closed = true;
+        self.0.condvar.notify_all();
+    }
+}
+
+/// A handle any number of threads can wait on, all of which are released together on the next
+/// display refresh, e.g. for job-system renderers that fan out per-frame work.
+///
+/// Clone the barrier to share it between threads.
+#[derive(Clone, Debug)]
+pub struct VsyncBarrier {
+    shared: Arc<Shared>,
+}
+
+impl VsyncBarrier {
+    /// Creates a running `DisplayLink` driving a new barrier.
+    ///
+    /// Waiters are released for good once the `DisplayLink` is dropped. Returns `None` if a
+    /// `DisplayLink` could not be created, like [`DisplayLink::new`]. The link calls back on a
+    /// thread of its own, so threads can wait on the barrier anywhere, including on the thread that
+    /// created it.
+    pub fn new() -> Option<(DisplayLink, Self)> {
+        let shared = Arc::new(Shared::default());
+        let releaser = Releaser(Arc::clone(&shared));
+        let link = mailbox::off_run_loop(move |frame| releaser.release(frame))?;
+        link.resume().ok()?;
+        Some((link, VsyncBarrier { shared }))
+    }
+
+    /// Blocks until the next refresh and returns its frame, which every thread waiting on the
+    /// barrier gets.
+    ///
+    /// Returns `None` once the `DisplayLink` driving the barrier has been dropped.
+    pub fn wait(&self) -> Option<FrameTime> {
+        let mut state = self.shared.state.lock().unwrap();
+        let generation = state.generation;
+        while state.generation == generation && !state.closed {
+            state = self.shared.condvar.wait(state).unwrap();
+        }
+        if state.generation == generation {
+            None
+        } else {
+            state.frame
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Integration with Bevy apps.
+
+use crate::{DisplayLink, FrameMailbox, FrameTime};
+use bevy_app::{App, AppExit, First, Plugin, PluginsState};
+use bevy_ecs::{
+    event::{Event, EventWriter},
+    system::{Res, Resource},
+};
+
+/// The event a [`VsyncPlugin`] sends on each refresh.
+#[derive(Clone, Copy, Debug, Event)]
+pub struct VsyncFrame {
+    pub frame: FrameTime,
+}
+
+#[derive(Resource)]
+struct Mailbox(FrameMailbox);
+
+/// Synchronizes a Bevy app with the display's refresh rate using a `DisplayLink`, rather than a
+/// timer, which on macOS and iOS drifts away from the real refresh.
+///
+/// By default, a [`VsyncFrame`] event is sent in the `First` schedule of the update following each
+/// refresh. With `runner` set, the plugin also replaces the app's runner with one running an update
+/// for each refresh, for apps without a windowing runner of their own, e.g. ones rendering
+/// offscreen.
+///
+/// If a `DisplayLink` can't be created, no events are sent and the runner exits with an error.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct VsyncPlugin {
+    pub runner: bool,
+}
+
+impl Plugin for VsyncPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_event::<VsyncFrame>();
+        if let Some((link, mailbox)) = DisplayLink::mailbox() {
+            // Kept in the world so it lives as long as the app.
+            app.insert_non_send_resource(link);
+            app.insert_resource(Mailbox(mailbox));
+        }
+        if self.runner {
+            app.set_runner(run);
+        } else {
+            app.add_systems(First, send_frames);
+        }
+    }
+}
+
+fn send_frames(mailbox: Option<Res<Mailbox>>, mut frames: EventWriter<VsyncFrame>) {
+    if let Some(frame) = mailbox.and_then(|mailbox| mailbox.0.try_recv()) {
+        frames.send(VsyncFrame { frame });
+    }
+}
+
+fn run(mut app: App) -> AppExit {
+    while app.plugins_state() == PluginsState::Adding {
+        std::thread::yield_now();
+    }
+    app.finish();
+    app.cleanup();
+    loop {
+        let frame = match app.world().get_resource::<Mailbox>() {
+            Some(mailbox) => mailbox.0.recv(),
+            None => None,
+        };
+        let frame = match frame {
+            Some(frame) => frame,
+            None => return AppExit::error(),
+        };
+        app.world_mut().send_event(VsyncFrame { frame });
+        app.update();
+        if let Some(exit) = app.should_exit() {
+            return exit;
+        }
+    }
+}
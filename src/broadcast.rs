@@ -0,0 +1,208 @@
+use crate::{DisplayId, DisplayLink, Epoch, FrameTime, FrameTimestamp, PlatformTimestamp};
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex, Weak},
+    time::Duration,
+};
+use time_point::TimePoint;
+
+/// The timestamps of a broadcast link: the `TimePoint` handed to subscribers, and the time since
+/// the first frame, for measuring the intervals between the frames of divided subscribers.
+#[derive(Clone, Copy, Debug)]
+struct Stamp {
+    point:       TimePoint,
+    since_start: Duration,
+}
+
+impl FrameTimestamp for Stamp {
+    fn from_platform(timestamp: PlatformTimestamp, epoch: &Epoch) -> Self {
+        Stamp {
+            point:       TimePoint::from_platform(timestamp, epoch),
+            since_start: Duration::from_platform(timestamp, epoch),
+        }
+    }
+}
+
+struct Subscriber {
+    id:       u64,
+    priority: i32,
+    divisor:  u32,
+    /// The frames skipped since the subscriber was last invoked.
+    skipped:  u32,
+    /// When the subscriber was last invoked, or subscribed.
+    last:     Option<Duration>,
+    callback: Box<dyn FnMut(FrameTime, Duration) + Send>,
+}
+
+impl Subscriber {
+    fn frame(&mut self, frame: FrameTime<Stamp>) {
+        self.skipped += 1;
+        if self.skipped < self.divisor {
+            return;
+        }
+        self.skipped = 0;
+        let now = frame.timestamp.since_start;
+        let delta = self
+            .last
+            .map_or(Duration::ZERO, |last| now.saturating_sub(last));
+        self.last = Some(now);
+        (self.callback)(
+            FrameTime {
+                timestamp: frame.timestamp.point,
+                display: frame.display,
+                delta,
+                gap: frame.gap,
+                paused_for: frame.paused_for,
+            },
+            delta,
+        )
+    }
+}
+
+#[derive(Default)]
+struct Subscribers {
+    list:    Vec<Subscriber>,
+    next_id: u64,
+    /// When the last frame was delivered, so new subscribers measure their first interval from it.
+    last:    Option<Duration>,
+}
+
+impl Debug for Subscribers {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Subscribers")
+            .field("count", &self.list.len())
+            .finish()
+    }
+}
+
+/// One `DisplayLink` delivering its frames to any number of subscribers, created with
+/// [`DisplayLink::broadcast`].
+///
+/// Subscribers share the link's thread, rather than each spinning up a link of its own, and
+/// always see the same frames. They're invoked in order of priority, highest first, and in the
+/// order they subscribed within a priority.
+#[derive(Debug)]
+pub struct Broadcast {
+    link:        DisplayLink,
+    subscribers: Arc<Mutex<Subscribers>>,
+}
+
+impl Broadcast {
+    /// Creates a running broadcast on `display`, or the default display if `None`.
+    pub(crate) fn new(display: Option<DisplayId>) -> Option<Self> {
+        let subscribers = Arc::new(Mutex::new(Subscribers::default()));
+        let link = {
+            let subscribers = Arc::clone(&subscribers);
+            let callback = move |frame: FrameTime<Stamp>| {
+                let mut subscribers = subscribers.lock().unwrap();
+                subscribers.last = Some(frame.timestamp.since_start);
+                for subscriber in &mut subscribers.list {
+                    subscriber.frame(frame)
+                }
+            };
+            match display {
+                Some(display_id) => DisplayLink::on_display_with_timestamp(display_id, callback)?,
+                None => DisplayLink::with_timestamp(callback)?,
+            }
+        };
+        link.resume().ok()?;
+        Some(Broadcast { link, subscribers })
+    }
+
+    /// Invokes `callback` with each frame until the returned `Subscription` is dropped, with a
+    /// priority of 0.
+    pub fn subscribe<F>(&self, mut callback: F) -> Subscription
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        self.add(0, 1, Box::new(move |frame, _| callback(frame)))
+    }
+
+    /// Invokes `callback` with each frame until the returned `Subscription` is dropped, before
+    /// subscribers with a lower `priority` and after those with a higher one, e.g. to run a
+    /// simulation before rendering its results.
+    pub fn subscribe_with_priority<F>(&self, priority: i32, mut callback: F) -> Subscription
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        self.add(priority, 1, Box::new(move |frame, _| callback(frame)))
+    }
+
+    /// Invokes `callback` with every `divisor`th frame until the returned `Subscription` is
+    /// dropped, with a priority of 0, e.g. for a 30 Hz physics step on a 120 Hz display.
+    ///
+    /// The callback is also passed the time elapsed since it was last invoked, which spans all
+    /// the frames in between. For its first frame, that's the time since the last frame before it
+    /// subscribed, or zero if there wasn't one. A `divisor` of 0 is treated as 1.
+    pub fn subscribe_every<F>(&self, divisor: u32, callback: F) -> Subscription
+    where
+        F: 'static + FnMut(FrameTime, Duration) + Send,
+    {
+        self.add(0, divisor.max(1), Box::new(callback))
+    }
+
+    fn add(
+        &self,
+        priority: i32,
+        divisor: u32,
+        callback: Box<dyn FnMut(FrameTime, Duration) + Send>,
+    ) -> Subscription {
+        let mut subscribers = self.subscribers.lock().unwrap();
+        let id = subscribers.next_id;
+        subscribers.next_id += 1;
+        let index = subscribers
+            .list
+            .iter()
+            .position(|subscriber| subscriber.priority < priority)
+            .unwrap_or(subscribers.list.len());
+        let last = subscribers.last;
+        subscribers.list.insert(
+            index,
+            Subscriber {
+                id,
+                priority,
+                divisor,
+                skipped: 0,
+                last,
+                callback,
+            },
+        );
+        Subscription {
+            subscribers: Arc::downgrade(&self.subscribers),
+            id,
+        }
+    }
+
+    /// Returns the `DisplayLink` driving the broadcast, e.g. to pause it while nothing needs
+    /// frames.
+    pub fn link(&self) -> &DisplayLink {
+        &self.link
+    }
+}
+
+/// Unsubscribes from a [`Broadcast`] when dropped.
+///
+/// Dropping waits for the subscriber's callback to return if it's being invoked, so it must not
+/// be dropped from within a subscriber's callback.
+#[derive(Debug)]
+pub struct Subscription {
+    subscribers: Weak<Mutex<Subscribers>>,
+    id:          u64,
+}
+
+impl Drop for Subscription {
+    fn drop(&mut self) {
+        if let Some(subscribers) = self.subscribers.upgrade() {
+            let removed = {
+                let mut subscribers = subscribers.lock().unwrap();
+                let index = subscribers
+                    .list
+                    .iter()
+                    .position(|subscriber| subscriber.id == self.id);
+                index.map(|index| subscribers.list.remove(index))
+            };
+            // Dropped without the lock held, in case the callback owns other subscriptions.
+            drop(removed);
+        }
+    }
+}
@@ -0,0 +1,243 @@
+#[cfg(all(target_os = "macos", feature = "fallback"))]
+use crate::fallback;
+#[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+use crate::ios::FrameRateRange;
+#[cfg(target_os = "macos")]
+use crate::macos::DispatchQueue;
+use crate::{Backend, DisplayId, DisplayLink, FrameTime, RunLoopMode};
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "tvos",
+    target_os = "visionos"
+))]
+use crate::{Inner, PlatformDisplayLink};
+use time_point::TimePoint;
+
+/// Options that only some platforms have.
+#[derive(Clone, Debug, Default)]
+struct PlatformOptions {
+    #[cfg(target_os = "macos")]
+    queue:               Option<DispatchQueue>,
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pause_in_background: bool,
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    frame_rate_range:    Option<FrameRateRange>,
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    run_loop_mode:       RunLoopMode,
+}
+
+/// Configures a `DisplayLink` before creating it, created with [`DisplayLink::builder`].
+///
+/// Options left unset behave like [`DisplayLink::new`]: the platform picks the backend and
+/// display, and the link starts paused.
+#[derive(Clone, Debug)]
+pub struct DisplayLinkBuilder {
+    display:  Option<DisplayId>,
+    backend:  Option<Backend>,
+    paused:   bool,
+    divisor:  u32,
+    fps:      u32,
+    // Empty on platforms without options of their own.
+    #[allow(dead_code)]
+    platform: PlatformOptions,
+}
+
+impl Default for DisplayLinkBuilder {
+    fn default() -> Self {
+        DisplayLinkBuilder {
+            display:  None,
+            backend:  None,
+            paused:   true,
+            divisor:  1,
+            fps:      0,
+            platform: PlatformOptions::default(),
+        }
+    }
+}
+
+impl DisplayLinkBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Syncs the link to the display identified by `display_id`, like [`DisplayLink::on_display`].
+    pub fn display(mut self, display_id: DisplayId) -> Self {
+        self.display = Some(display_id);
+        self
+    }
+
+    /// Drives the link with `backend`, like [`DisplayLink::with_backend`].
+    ///
+    /// Combined with [`DisplayLinkBuilder::display`], the link is moved to the display after it's
+    /// created, which is ignored where [`DisplayLink::set_display`] is unsupported.
+    pub fn backend(mut self, backend: Backend) -> Self {
+        self.backend = Some(backend);
+        self
+    }
+
+    /// Whether the link starts paused, which it does by default.
+    pub fn paused(mut self, paused: bool) -> Self {
+        self.paused = paused;
+        self
+    }
+
+    /// Whether `build` resumes the link before returning it, so the first frames aren't missed
+    /// between creating the link and resuming it. The inverse of [`DisplayLinkBuilder::paused`].
+    ///
+    /// If the link can't be resumed, `build` returns `None` rather than a paused link.
+    pub fn start_running(self, running: bool) -> Self {
+        self.paused(!running)
+    }
+
+    /// Invokes the callback only on every `divisor`th refresh, like [`DisplayLink::set_divisor`].
+    pub fn divisor(mut self, divisor: u32) -> Self {
+        self.divisor = divisor;
+        self
+    }
+
+    /// Invokes the callback at about `fps` frames per second, like
+    /// [`DisplayLink::set_target_frame_rate`].
+    pub fn target_frame_rate(mut self, fps: u32) -> Self {
+        self.fps = fps;
+        self
+    }
+
+    /// Invokes the callback on `queue` rather than the `CVDisplayLink` thread, e.g. a global queue
+    /// of an app's chosen `QosClass`, or a queue of the app's own.
+    ///
+    /// Frames are coalesced: if the queue falls behind, only the latest frame is delivered. One
+    /// frame may still arrive after the link is paused.
+    #[cfg(target_os = "macos")]
+    pub fn dispatch_queue(mut self, queue: DispatchQueue) -> Self {
+        self.platform.queue = Some(queue);
+        self
+    }
+
+    /// Fires the link only in the run loop `mode`, e.g. `RunLoopMode::Default` so frames stop
+    /// during scrolls rather than competing with UIKit's tracking. Links fire in
+    /// `RunLoopMode::Common` by default.
+    ///
+    /// Only links on iOS, tvOS and visionOS fire in a run loop mode, so this does nothing
+    /// elsewhere.
+    #[allow(unused_mut, unused_variables)]
+    pub fn run_loop_mode(mut self, mode: RunLoopMode) -> Self {
+        #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+        {
+            self.platform.run_loop_mode = mode;
+        }
+        self
+    }
+
+    /// Whether the link pauses while the app is in the background, resuming when it returns to the
+    /// foreground. See [`DisplayLink::pause_in_background`].
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn pause_in_background(mut self, pause_in_background: bool) -> Self {
+        self.platform.pause_in_background = pause_in_background;
+        self
+    }
+
+    /// Asks for `fps` frames per second, like [`DisplayLink::set_preferred_frames_per_second`].
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn preferred_frames_per_second(self, fps: u32) -> Self {
+        self.preferred_frame_rate_range(FrameRateRange::fixed(fps as f32))
+    }
+
+    /// Asks for frame rates within `range`, like [`DisplayLink::set_preferred_frame_rate_range`].
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn preferred_frame_rate_range(mut self, range: FrameRateRange) -> Self {
+        self.platform.frame_rate_range = Some(range);
+        self
+    }
+
+    /// Creates the `DisplayLink`, invoking `callback` with the `FrameTime` of each refresh.
+    ///
+    /// Returns `None` if a `DisplayLink` could not be created with these options.
+    pub fn build<F>(self, callback: F) -> Option<DisplayLink>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        #[cfg(target_os = "macos")]
+        let link = match self.platform.queue.clone() {
+            Some(queue) => self.create_on_queue(queue, callback),
+            None => self.create(callback),
+        };
+        #[cfg(not(target_os = "macos"))]
+        let link = self.create(callback);
+        let mut link = link?;
+        if let (Some(_), Some(display_id)) = (self.backend, self.display) {
+            link.set_current_display(display_id);
+        }
+        #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+        if self.platform.pause_in_background {
+            link.pause_in_background();
+        }
+        #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+        if let Some(range) = self.platform.frame_rate_range {
+            link.set_preferred_frame_rate_range(range);
+        }
+        link.set_divisor(self.divisor);
+        link.set_target_frame_rate(self.fps);
+        if !self.paused {
+            link.resume().ok()?;
+        }
+        Some(link)
+    }
+
+    fn create<F>(&self, callback: F) -> Option<DisplayLink>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+        if self.platform.run_loop_mode != RunLoopMode::Common
+            && matches!(self.backend, None | Some(Backend::CoreAnimation))
+        {
+            let (display, mode) = (self.display, &self.platform.run_loop_mode);
+            return DisplayLink::wrap(callback, |slot| {
+                match display {
+                    Some(display_id) => PlatformDisplayLink::on_display_with_run_loop_mode(
+                        display_id,
+                        mode,
+                        slot.handler(),
+                    ),
+                    None => PlatformDisplayLink::with_run_loop_mode(mode, slot.handler()),
+                }
+                .map(Inner::Platform)
+            });
+        }
+        match (self.backend, self.display) {
+            (Some(backend), _) => DisplayLink::with_backend::<TimePoint, _>(backend, callback),
+            (None, Some(display_id)) => DisplayLink::on_display(display_id, callback),
+            (None, None) => DisplayLink::new(callback),
+        }
+    }
+
+    /// Creates the link like `create`, but with its handler invoked on `queue`, like
+    /// [`DisplayLink::on_main_thread`], so pausing the link or replacing its callback takes effect
+    /// on the queue.
+    #[cfg(target_os = "macos")]
+    fn create_on_queue<F>(&self, queue: DispatchQueue, callback: F) -> Option<DisplayLink>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        let (backend, display) = (self.backend, self.display);
+        DisplayLink::wrap(callback, |slot| {
+            let handler = queue.deliver(slot.handler());
+            match (backend, display) {
+                #[cfg(feature = "fallback")]
+                (Some(Backend::Timer), _) => fallback::DisplayLink::with_refresh_rate(
+                    fallback::DEFAULT_REFRESH_RATE,
+                    handler,
+                )
+                .map(Inner::Fallback),
+                (Some(backend), _) => {
+                    PlatformDisplayLink::with_backend(backend, handler).map(Inner::Platform)
+                }
+                (None, Some(display_id)) => {
+                    PlatformDisplayLink::on_display(display_id, handler).map(Inner::Platform)
+                }
+                (None, None) => PlatformDisplayLink::new(handler).map(Inner::Platform),
+            }
+        })
+    }
+}
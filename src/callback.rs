@@ -0,0 +1,568 @@
+use crate::{
+    governor::{Governor, GovernorCallback},
+    FrameTime, FrameTimestamp, PlatformTimestamp,
+};
+use std::{
+    any::Any,
+    fmt::{self, Debug, Formatter},
+    sync::{
+        atomic::{AtomicI64, AtomicU32, AtomicU64, AtomicU8, Ordering},
+        Arc, Mutex,
+    },
+    time::Duration,
+};
+
+/// What a `DisplayLink` does once its callback returns, for callbacks created with
+/// [`DisplayLink::with_control_flow`](crate::DisplayLink::with_control_flow).
+///
+/// Callbacks returning `()` always continue.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub enum ControlFlow {
+    /// Keep invoking the callback.
+    #[default]
+    Continue,
+    /// Pause the link, until it's resumed with `DisplayLink::resume`.
+    Pause,
+    /// Stop the link for good. It can't be resumed.
+    Stop,
+}
+
+impl From<()> for ControlFlow {
+    fn from((): ()) -> Self {
+        ControlFlow::Continue
+    }
+}
+
+/// Handles the frames of a `DisplayLink` created with
+/// [`DisplayLink::with_handler`](crate::DisplayLink::with_handler), for handlers chosen at runtime,
+/// e.g. by a plugin system.
+///
+/// Implemented for closures taking a `FrameTime`.
+pub trait FrameHandler: Send {
+    /// Invoked with the `FrameTime` of each refresh.
+    fn on_frame(&mut self, frame: FrameTime);
+}
+
+impl<F: FnMut(FrameTime) + Send> FrameHandler for F {
+    fn on_frame(&mut self, frame: FrameTime) {
+        self(frame)
+    }
+}
+
+pub(crate) type Callback<T> = Box<dyn FnMut(FrameTime<T>) -> ControlFlow + Send>;
+
+pub(crate) fn boxed<T, F, R>(mut callback: F) -> Callback<T>
+where
+    T: FrameTimestamp,
+    F: 'static + FnMut(FrameTime<T>) -> R + Send,
+    R: Into<ControlFlow>,
+{
+    Box::new(move |frame| callback(frame).into())
+}
+
+/// Why a `DisplayLink` skips frames on the way to its callback.
+#[cfg_attr(
+    not(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "tvos",
+        target_os = "visionos"
+    )),
+    allow(dead_code)
+)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Policy {
+    /// The app asked for every nth frame.
+    Divisor,
+    /// The device is running hot.
+    Thermal,
+    /// The Mac is on battery power.
+    #[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+    Battery,
+    /// The app is inactive, but still on screen.
+    #[cfg_attr(
+        not(any(target_os = "ios", target_os = "tvos", target_os = "visionos")),
+        allow(dead_code)
+    )]
+    Inactive,
+    /// The callback can't keep up, see `Governor`.
+    Governor,
+}
+
+const POLICIES: usize = 5;
+
+/// Skips frames on the way to the callback, so only every nth frame reaches it, n being the
+/// largest divisor any `Policy` asks for, and frames reach it no faster than the target frame
+/// rate.
+#[derive(Debug, Default)]
+pub(crate) struct Throttle {
+    /// The divisor each `Policy` asks for, where 0 and 1 both deliver every frame.
+    divisors: [AtomicU32; POLICIES],
+    /// The frames skipped since the last one delivered.
+    skipped:  AtomicU32,
+    /// The time spanned by the skipped frames, in nanoseconds.
+    carried:  AtomicU64,
+    /// The gaps before the skipped frames, in nanoseconds, or 0 if there were none.
+    gaps:     AtomicU64,
+    /// The time between frames at the target frame rate, in nanoseconds, or 0 for no target.
+    interval: AtomicU64,
+    /// The time elapsed towards the next frame at the target frame rate, in nanoseconds. Negative
+    /// when the last frame was delivered early, so the next one is delivered late to make up.
+    credit:   AtomicI64,
+}
+
+impl Throttle {
+    /// Delivers only every `divisor`th frame for `policy`, from the next frame.
+    pub fn set(&self, policy: Policy, divisor: u32) {
+        self.divisors[policy as usize].store(divisor, Ordering::Relaxed)
+    }
+
+    /// Returns the divisor `policy` asks for.
+    pub fn get(&self, policy: Policy) -> u32 {
+        self.divisors[policy as usize]
+            .load(Ordering::Relaxed)
+            .max(1)
+    }
+
+    /// Paces frames to `fps`, or stops pacing them if it's 0.
+    pub fn set_target(&self, fps: u32) {
+        let interval = if fps == 0 {
+            0
+        } else {
+            1_000_000_000 / u64::from(fps)
+        };
+        self.interval.store(interval, Ordering::Relaxed);
+        // The next frame is due right away.
+        self.credit.store(interval as i64, Ordering::Relaxed);
+    }
+
+    /// Returns the frame rate set with [`Throttle::set_target`].
+    pub fn target(&self) -> u32 {
+        match self.interval.load(Ordering::Relaxed) {
+            0 => 0,
+            interval => (1_000_000_000 / interval) as u32,
+        }
+    }
+
+    /// Returns the divisor in effect, the largest any `Policy` asks for.
+    pub fn divisor(&self) -> u32 {
+        self.divisors
+            .iter()
+            .map(|divisor| divisor.load(Ordering::Relaxed).max(1))
+            .max()
+            .unwrap_or(1)
+    }
+
+    /// Counts a frame `delta` after the previous one, returning the time since the last frame
+    /// delivered if it reaches the callback. Only called with the gate's `calls` lock held.
+    fn pass(&self, delta: Duration) -> Option<Duration> {
+        self.carry(delta);
+        let due = self.due(delta);
+        let skipped = self.skipped.load(Ordering::Relaxed) + 1;
+        if skipped < self.divisor() || !due {
+            self.skipped.store(skipped, Ordering::Relaxed);
+            None
+        } else {
+            self.paced();
+            self.skipped.store(0, Ordering::Relaxed);
+            Some(Duration::from_nanos(
+                self.carried.swap(0, Ordering::Relaxed),
+            ))
+        }
+    }
+
+    /// Counts a refresh `delta` after the previous one towards the next frame at the target frame
+    /// rate, returning whether it's the refresh closest to when that frame is due. Measuring each
+    /// refresh keeps the pace when the display's refresh rate changes.
+    fn due(&self, delta: Duration) -> bool {
+        let interval = self.interval.load(Ordering::Relaxed) as i64;
+        if interval == 0 {
+            return true;
+        }
+        let delta = delta.as_nanos() as i64;
+        let credit = self.credit.fetch_add(delta, Ordering::Relaxed) + delta;
+        // Once less than half a refresh remains, this refresh is closer than the next one.
+        credit >= interval - delta / 2
+    }
+
+    /// Starts counting towards the next frame at the target frame rate, once a frame's delivered.
+    fn paced(&self) {
+        let interval = self.interval.load(Ordering::Relaxed) as i64;
+        if interval != 0 {
+            let credit = self.credit.load(Ordering::Relaxed) - interval;
+            // Bounded, so a stall doesn't cause a burst of frames to catch up.
+            self.credit
+                .store(credit.clamp(-interval / 2, interval / 2), Ordering::Relaxed);
+        }
+    }
+
+    /// Adds `delta` to the time carried over to the next frame delivered.
+    fn carry(&self, delta: Duration) {
+        self.carried
+            .fetch_add(delta.as_nanos() as u64, Ordering::Relaxed);
+    }
+
+    /// Adds `gap` to the gap carried over to the next frame delivered, so a gap before a skipped
+    /// frame isn't lost.
+    fn carry_gap(&self, gap: Option<Duration>) {
+        if let Some(gap) = gap {
+            self.gaps
+                .fetch_add(gap.as_nanos() as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Takes the gap carried over to the frame being delivered.
+    fn take_gap(&self) -> Option<Duration> {
+        match self.gaps.swap(0, Ordering::Relaxed) {
+            0 => None,
+            gap => Some(Duration::from_nanos(gap)),
+        }
+    }
+}
+
+/// The time a `DisplayLink` spent paused by hand or by its callback.
+#[derive(Debug, Default)]
+struct Pauses {
+    /// When the current pause began, while the link is paused.
+    since: Option<PlatformTimestamp>,
+    /// The length of all the pauses that have ended.
+    total: Duration,
+    /// The length of the last pause, until the first frame after it takes it.
+    last:  Option<Duration>,
+}
+
+/// Pauses the platform link once its callback closes the gate, from the callback's thread, with
+/// the gate still locked for the call.
+///
+/// The platform link can't always be paused from within its own callback, so it may be paused
+/// later, from another thread, in which case it must check the gate is still closed first, and do
+/// so under the same lock as resuming the platform link.
+pub(crate) struct Park(ParkFn);
+
+type ParkFn = Box<dyn Fn(&Arc<Gate>) + Send>;
+
+impl Park {
+    pub fn new(park: impl 'static + Fn(&Arc<Gate>) + Send) -> Self {
+        Park(Box::new(park))
+    }
+}
+
+impl Debug for Park {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Park").finish_non_exhaustive()
+    }
+}
+
+const OPEN: u8 = 0;
+const HELD: u8 = 1;
+const STOPPED: u8 = 2;
+
+/// Stops frames from reaching the callback once it returns `ControlFlow::Pause` or
+/// `ControlFlow::Stop`, and has the platform link paused with its `Park`, as it can't always be
+/// paused from within its own callback.
+#[derive(Debug, Default)]
+pub(crate) struct Gate {
+    state:        AtomicU8,
+    /// Held while the callback is invoked, so closing the gate can wait for it to return.
+    calls:        Mutex<()>,
+    /// Lets only some of the frames through while the gate is open.
+    pub throttle: Throttle,
+    pauses:       Mutex<Pauses>,
+    governor:     Mutex<Option<Governor>>,
+    park:         Mutex<Option<Park>>,
+}
+
+impl Gate {
+    pub fn is_open(&self) -> bool {
+        self.state.load(Ordering::Acquire) == OPEN
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.state.load(Ordering::Acquire) == STOPPED
+    }
+
+    /// Reopens the gate if the callback paused the link, returning whether it did.
+    pub fn release(&self) -> bool {
+        self.state
+            .compare_exchange(HELD, OPEN, Ordering::AcqRel, Ordering::Acquire)
+            .is_ok()
+    }
+
+    /// Closes the gate for good, waiting for a call in progress to return first.
+    pub fn close(&self) {
+        let _calls = self.calls.lock().unwrap();
+        self.state.store(STOPPED, Ordering::Release);
+    }
+
+    /// Starts counting paused time, unless the link is already paused.
+    pub fn paused(&self) {
+        let mut pauses = self.pauses.lock().unwrap();
+        if pauses.since.is_none() {
+            pauses.since = Some(PlatformTimestamp::now());
+        }
+    }
+
+    /// Stops counting paused time, so the first frame after this is told how long the pause was.
+    pub fn resumed(&self) {
+        let mut pauses = self.pauses.lock().unwrap();
+        if let Some(since) = pauses.since.take() {
+            let paused =
+                Duration::from_secs_f64(PlatformTimestamp::now().seconds_since(since).max(0.0));
+            pauses.total += paused;
+            pauses.last = Some(paused);
+        }
+    }
+
+    /// Returns the time spent paused so far, including the current pause.
+    pub fn time_paused(&self) -> Duration {
+        let pauses = self.pauses.lock().unwrap();
+        pauses.total
+            + pauses.since.map_or(Duration::ZERO, |since| {
+                Duration::from_secs_f64(PlatformTimestamp::now().seconds_since(since).max(0.0))
+            })
+    }
+
+    /// Steps the rate down when the callback can't keep up, and back up once it can, calling
+    /// `on_change` when it does.
+    pub fn govern(&self, on_change: GovernorCallback) {
+        *self.governor.lock().unwrap() = Some(Governor::new(on_change));
+    }
+
+    /// Stops governing the rate, delivering every frame the other policies let through again.
+    pub fn stop_governing(&self) {
+        *self.governor.lock().unwrap() = None;
+        self.throttle.set(Policy::Governor, 1);
+    }
+
+    /// Has `park` pause the platform link once the callback pauses or stops it.
+    pub fn set_park(&self, park: Park) {
+        *self.park.lock().unwrap() = Some(park);
+    }
+
+    /// Runs `call` with `frame` unless the gate is closed or the throttle skips it, applying the
+    /// `ControlFlow` it returns. The frame's `delta` is widened to span the frames skipped before
+    /// it, as is its `gap` to include any gaps before them, and it's told how long the link was
+    /// paused instead of a gap if it's the first since a resume.
+    fn call<T>(
+        self: &Arc<Self>,
+        mut frame: FrameTime<T>,
+        call: impl FnOnce(FrameTime<T>) -> ControlFlow,
+    ) {
+        let _calls = self.calls.lock().unwrap();
+        if let Some(governor) = &mut *self.governor.lock().unwrap() {
+            governor.refresh(frame.delta, frame.gap.is_some());
+        }
+        self.throttle.carry_gap(frame.gap);
+        if !self.is_open() {
+            self.throttle.carry(frame.delta);
+            return;
+        }
+        frame.delta = match self.throttle.pass(frame.delta) {
+            Some(delta) => delta,
+            None => return,
+        };
+        frame.gap = self.throttle.take_gap();
+        frame.paused_for = self.pauses.lock().unwrap().last.take();
+        if frame.paused_for.is_some() {
+            // The link was paused on purpose, so the time since the last frame isn't a gap.
+            frame.gap = None;
+        }
+        let start = PlatformTimestamp::now();
+        let flow = call(frame);
+        if let Some(governor) = &mut *self.governor.lock().unwrap() {
+            let busy = PlatformTimestamp::now().seconds_since(start);
+            governor.frame(busy, &self.throttle);
+        }
+        match flow {
+            ControlFlow::Continue => return,
+            ControlFlow::Pause => {
+                if self
+                    .state
+                    .compare_exchange(OPEN, HELD, Ordering::AcqRel, Ordering::Acquire)
+                    .is_ok()
+                {
+                    self.paused();
+                }
+            }
+            ControlFlow::Stop => self.state.store(STOPPED, Ordering::Release),
+        }
+        if let Some(Park(park)) = &*self.park.lock().unwrap() {
+            park(self)
+        }
+    }
+}
+
+/// A `CallbackSlot` of any timestamp type.
+pub(crate) trait Slot: Any + Debug + Send + Sync {
+    fn as_any(&self) -> &dyn Any;
+
+    /// Drops the callback, so whatever it captured is released even if the platform link holds
+    /// on to its handler.
+    fn clear(&self);
+}
+
+/// The callback of a `DisplayLink`, shared with the closure given to the platform link so it can
+/// be replaced without recreating the link.
+pub(crate) struct CallbackSlot<T> {
+    callback: Mutex<Callback<T>>,
+    gate:     Arc<Gate>,
+}
+
+impl<T> Debug for CallbackSlot<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("CallbackSlot")
+            .field("gate", &self.gate)
+            .finish()
+    }
+}
+
+impl<T: FrameTimestamp> CallbackSlot<T> {
+    pub fn new(callback: Callback<T>, gate: Arc<Gate>) -> Self {
+        CallbackSlot {
+            callback: Mutex::new(callback),
+            gate,
+        }
+    }
+
+    /// Returns a callback for the platform link, calling whichever callback is in the slot while
+    /// the gate is open.
+    pub fn handler(self: &Arc<Self>) -> impl 'static + FnMut(FrameTime<T>) + Send {
+        let slot = Arc::clone(self);
+        move |frame| {
+            slot.gate
+                .call(frame, |frame| (slot.callback.lock().unwrap())(frame))
+        }
+    }
+
+    /// Waits for a call in progress to return, then replaces the callback, dropping the old one.
+    pub fn replace(&self, callback: Callback<T>) {
+        let old = std::mem::replace(&mut *self.callback.lock().unwrap(), callback);
+        drop(old);
+    }
+}
+
+impl<T: FrameTimestamp> Slot for CallbackSlot<T> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn clear(&self) {
+        self.replace(Box::new(|_| ControlFlow::Stop))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::DisplayId;
+
+    const REFRESH: Duration = Duration::from_millis(10);
+
+    fn frame(gap: Option<Duration>) -> FrameTime<()> {
+        FrameTime {
+            timestamp: (),
+            display: DisplayId::UNKNOWN,
+            delta: gap.unwrap_or(REFRESH),
+            gap,
+            paused_for: None,
+        }
+    }
+
+    #[test]
+    fn largest_divisor_wins() {
+        let throttle = Throttle::default();
+        assert_eq!(throttle.divisor(), 1);
+        throttle.set(Policy::Divisor, 2);
+        throttle.set(Policy::Thermal, 3);
+        assert_eq!(throttle.divisor(), 3);
+        throttle.set(Policy::Thermal, 1);
+        assert_eq!(throttle.divisor(), 2);
+        throttle.set(Policy::Divisor, 0);
+        assert_eq!(throttle.divisor(), 1);
+        assert_eq!(throttle.get(Policy::Divisor), 1);
+    }
+
+    #[test]
+    fn skipped_frames_carry_over() {
+        let throttle = Throttle::default();
+        throttle.set(Policy::Divisor, 3);
+        assert_eq!(throttle.pass(REFRESH), None);
+        assert_eq!(throttle.pass(REFRESH), None);
+        assert_eq!(throttle.pass(REFRESH), Some(REFRESH * 3));
+        assert_eq!(throttle.pass(REFRESH), None);
+    }
+
+    #[test]
+    fn gaps_in_skipped_frames_carry_over() {
+        let gap = Some(Duration::from_secs(1));
+        let gate = Arc::new(Gate::default());
+        gate.throttle.set(Policy::Divisor, 2);
+        let mut gaps = Vec::new();
+        for frame in [
+            frame(None),
+            frame(None),
+            frame(gap),
+            frame(None),
+            frame(None),
+            frame(None),
+        ] {
+            gate.call(frame, |frame| {
+                gaps.push(frame.gap);
+                ControlFlow::Continue
+            });
+        }
+        assert_eq!(gaps, [None, gap, None]);
+    }
+
+    #[test]
+    fn resuming_is_not_a_gap() {
+        let gate = Arc::new(Gate::default());
+        let mut frames = Vec::new();
+        gate.call(frame(None), |_| ControlFlow::Pause);
+        assert!(gate.release());
+        gate.resumed();
+        for gap in [
+            Some(Duration::from_secs(1)),
+            None,
+            Some(Duration::from_secs(1)),
+        ] {
+            gate.call(frame(gap), |frame| {
+                frames.push((frame.gap, frame.paused_for.is_some()));
+                ControlFlow::Continue
+            });
+        }
+        assert_eq!(
+            frames,
+            [
+                (None, true),
+                (None, false),
+                (Some(Duration::from_secs(1)), false)
+            ]
+        );
+    }
+
+    #[test]
+    fn pausing_parks_the_link() {
+        let gate = Arc::new(Gate::default());
+        let parked = Arc::new(AtomicU64::new(0));
+        gate.set_park(Park::new({
+            let parked = Arc::clone(&parked);
+            move |gate| {
+                assert!(!gate.is_open());
+                parked.fetch_add(1, Ordering::Relaxed);
+            }
+        }));
+        let mut calls = 0;
+        for flow in [
+            ControlFlow::Continue,
+            ControlFlow::Pause,
+            ControlFlow::Continue,
+        ] {
+            gate.call(frame(None), |_| {
+                calls += 1;
+                flow
+            });
+        }
+        assert_eq!((calls, parked.load(Ordering::Relaxed)), (2, 1));
+    }
+}
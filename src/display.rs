@@ -0,0 +1,316 @@
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+use crate::worker::Worker;
+use crate::DisplayId;
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+use std::thread;
+use std::time::Duration;
+
+#[cfg(target_os = "android")]
+use crate::android::displays;
+#[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+use crate::ios::displays;
+#[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+use crate::ios::Watcher;
+#[cfg(target_os = "linux")]
+use crate::linux::displays;
+#[cfg(target_os = "macos")]
+use crate::macos::displays;
+#[cfg(target_os = "macos")]
+use crate::macos::Watcher;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use crate::wasm::displays;
+#[cfg(target_os = "windows")]
+use crate::windows::displays;
+
+#[cfg(not(native_backend))]
+fn displays() -> Vec<Display> {
+    Vec::new()
+}
+
+pub(crate) type WatchCallback = Box<dyn FnMut(DisplayEvent) + Send>;
+
+/// How often the display list is polled on platforms without change notifications.
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Reports displays coming and going by listing them every `POLL_INTERVAL`, as Linux and Windows
+/// only notify windows of display changes.
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+#[derive(Debug)]
+struct Watcher {
+    _worker: Worker,
+}
+
+#[cfg(any(target_os = "linux", target_os = "windows"))]
+impl Watcher {
+    fn new(mut callback: WatchCallback) -> Option<Self> {
+        let mut known = displays();
+        let worker = Worker::spawn(
+            "display-link-watcher",
+            || {
+                thread::sleep(POLL_INTERVAL);
+                Some(displays())
+            },
+            move |current: Vec<Display>| {
+                let find = |displays: &[Display], id| {
+                    displays.iter().find(|display| display.id == id).cloned()
+                };
+                for display in &known {
+                    if find(&current, display.id).is_none() {
+                        callback(DisplayEvent::Disconnected(display.id));
+                    }
+                }
+                for display in &current {
+                    match find(&known, display.id) {
+                        None => callback(DisplayEvent::Connected(display.id)),
+                        Some(old) => {
+                            if old.bounds != display.bounds
+                                || old.refresh_rate != display.refresh_rate
+                            {
+                                callback(DisplayEvent::reconfigured(display))
+                            }
+                            if old.mirrors != display.mirrors {
+                                callback(DisplayEvent::mirroring_changed(display))
+                            }
+                        }
+                    }
+                }
+                known = current;
+            },
+        )?;
+        worker.set_paused(false);
+        Some(Watcher { _worker: worker })
+    }
+}
+
+/// A change to the connected displays, see [`Display::watch`].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DisplayEvent {
+    Connected(DisplayId),
+    Disconnected(DisplayId),
+    /// The display's resolution or refresh rate changed, e.g. so a renderer can resize its
+    /// swapchain and retune its frame pacing.
+    Reconfigured {
+        id:             DisplayId,
+        bounds:         Bounds,
+        /// The nominal time between refreshes of the new mode, if it's known.
+        refresh_period: Option<Duration>,
+    },
+    /// The display started or stopped mirroring another, e.g. when AirPlay mirroring kicks in.
+    ///
+    /// Mirrored displays are often paced by the display they mirror, so their effective refresh
+    /// cadence can change or stutter, e.g. so a video player can switch pacing strategies.
+    MirroringChanged {
+        id:             DisplayId,
+        /// The display it now mirrors, or `None` if mirroring stopped.
+        mirrors:        Option<DisplayId>,
+        /// The nominal time between refreshes of the display, if it's known.
+        refresh_period: Option<Duration>,
+    },
+}
+
+impl DisplayEvent {
+    #[cfg_attr(
+        not(any(
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "tvos",
+            target_os = "windows"
+        )),
+        allow(dead_code)
+    )]
+    pub(crate) fn reconfigured(display: &Display) -> Self {
+        DisplayEvent::Reconfigured {
+            id:             display.id,
+            bounds:         display.bounds,
+            refresh_period: display.refresh_period(),
+        }
+    }
+
+    #[cfg_attr(
+        not(any(
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "tvos",
+            target_os = "windows"
+        )),
+        allow(dead_code)
+    )]
+    pub(crate) fn mirroring_changed(display: &Display) -> Self {
+        DisplayEvent::MirroringChanged {
+            id:             display.id,
+            mirrors:        display.mirrors,
+            refresh_period: display.refresh_period(),
+        }
+    }
+}
+
+/// Reports `DisplayEvent`s to the callback given to [`Display::watch`] until it's dropped.
+#[derive(Debug)]
+pub struct DisplayWatcher {
+    #[cfg(any(
+        target_os = "ios",
+        target_os = "linux",
+        target_os = "macos",
+        target_os = "tvos",
+        target_os = "visionos",
+        target_os = "windows"
+    ))]
+    _watcher: Watcher,
+}
+
+/// A rectangle in the desktop's coordinate space, in the platform's units: points on Apple
+/// platforms and CSS pixels on the web, and pixels elsewhere.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Bounds {
+    pub x:      f64,
+    pub y:      f64,
+    pub width:  f64,
+    pub height: f64,
+}
+
+/// The range of refresh rates a variable refresh rate display can run at, in Hz.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RefreshRateRange {
+    pub min: f64,
+    pub max: f64,
+}
+
+/// A connected display, which a `DisplayLink` can be synced to with
+/// [`DisplayLink::on_display`](crate::DisplayLink::on_display).
+#[derive(Clone, Debug)]
+pub struct Display {
+    pub(crate) id:                 DisplayId,
+    pub(crate) name:               Option<String>,
+    pub(crate) bounds:             Bounds,
+    pub(crate) refresh_rate:       Option<f64>,
+    pub(crate) refresh_rate_range: Option<RefreshRateRange>,
+    pub(crate) mirrors:            Option<DisplayId>,
+}
+
+impl Display {
+    /// Returns the connected displays, with the main display first.
+    ///
+    /// This is empty on platforms that can't enumerate displays, e.g. on Android, where that
+    /// needs the Java `DisplayManager`.
+    pub fn all() -> Vec<Display> {
+        displays()
+    }
+
+    /// Calls `callback` whenever a display is connected, disconnected or changes mode, until the
+    /// returned `DisplayWatcher` is dropped, so links can be created and destroyed as displays come
+    /// and go.
+    ///
+    /// On macOS and iOS, events are delivered on the main thread, so its run loop must be running.
+    /// Linux and Windows poll the display list from another thread twice a second. Returns `None`
+    /// on platforms that can't watch displays, e.g. Android, visionOS and the web.
+    pub fn watch<F>(callback: F) -> Option<DisplayWatcher>
+    where
+        F: 'static + FnMut(DisplayEvent) + Send,
+    {
+        #[cfg(any(
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "tvos",
+            target_os = "visionos",
+            target_os = "windows"
+        ))]
+        {
+            Watcher::new(Box::new(callback)).map(|watcher| DisplayWatcher { _watcher: watcher })
+        }
+        #[cfg(not(any(
+            target_os = "ios",
+            target_os = "linux",
+            target_os = "macos",
+            target_os = "tvos",
+            target_os = "visionos",
+            target_os = "windows"
+        )))]
+        {
+            let _ = callback;
+            None
+        }
+    }
+
+    /// Returns the main display, or `None` if there are no displays or they can't be listed.
+    pub fn main() -> Option<Display> {
+        displays().into_iter().next()
+    }
+
+    /// Returns the connected display identified by `id`.
+    pub fn from_id(id: DisplayId) -> Option<Display> {
+        displays().into_iter().find(|display| display.id == id)
+    }
+
+    pub fn id(&self) -> DisplayId {
+        self.id
+    }
+
+    /// Returns the localized name of the display, e.g. `"Built-in Retina Display"`, if the
+    /// platform has one.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        self.bounds
+    }
+
+    /// Returns the nominal refresh rate of the display's current mode, in Hz, if it's known.
+    ///
+    /// This doesn't need a `DisplayLink`, so it can be used at startup, e.g. to pick a fixed
+    /// simulation timestep.
+    pub fn refresh_rate(&self) -> Option<f64> {
+        self.refresh_rate
+    }
+
+    /// Returns the range of refresh rates the display supports, if it has a variable refresh rate
+    /// and the platform reports it.
+    ///
+    /// This is only reported on macOS 12 and newer, e.g. for ProMotion displays.
+    pub fn refresh_rate_range(&self) -> Option<RefreshRateRange> {
+        self.refresh_rate_range
+    }
+
+    /// Returns the `registryID` of the Metal device currently driving the display, e.g. so a
+    /// renderer can check that the GPU doing the work is the one attached to the display its
+    /// `DisplayLink` is synced to by comparing it with `[device registryID]`.
+    ///
+    /// Returns `None` before macOS 10.13, which lacks `registryID`.
+    #[cfg(target_os = "macos")]
+    pub fn metal_registry_id(&self) -> Option<u64> {
+        crate::macos::metal_registry_id(self.id.0)
+    }
+
+    /// Returns the `id<MTLDevice>` currently driving the display, which the caller must release.
+    #[cfg(target_os = "macos")]
+    pub fn metal_device(&self) -> Option<*mut std::ffi::c_void> {
+        crate::macos::metal_device(self.id.0).map(|device| device as *mut std::ffi::c_void)
+    }
+
+    /// Returns the connected displays driven by the Metal device whose `registryID` is
+    /// `registry_id`, with the main display first.
+    #[cfg(target_os = "macos")]
+    pub fn for_metal_registry_id(registry_id: u64) -> Vec<Display> {
+        displays()
+            .into_iter()
+            .filter(|display| display.metal_registry_id() == Some(registry_id))
+            .collect()
+    }
+
+    /// Returns the display this one mirrors, if it's mirroring another.
+    ///
+    /// This is only reported on macOS and iOS, e.g. for an AirPlay screen mirroring the device's
+    /// own screen.
+    pub fn mirrors(&self) -> Option<DisplayId> {
+        self.mirrors
+    }
+
+    fn refresh_period(&self) -> Option<Duration> {
+        self.refresh_rate
+            .map(|refresh_rate| Duration::from_secs_f64(1.0 / refresh_rate))
+    }
+}
@@ -0,0 +1,94 @@
+//! Integration with calloop event loops.
+
+use crate::{DisplayLink, FrameTime};
+use calloop::{
+    ping::{make_ping, PingError, PingSource},
+    EventSource, Poll, PostAction, Readiness, Token, TokenFactory,
+};
+use std::sync::{Arc, Mutex};
+
+/// A calloop `EventSource` producing the frames of a `DisplayLink`, so Smithay-based and other
+/// calloop-driven apps can handle vsync in their existing event loop.
+///
+/// The display link wakes the loop with a calloop `Ping`, and only the latest frame is kept, so
+/// frames the loop was too busy for are skipped.
+#[derive(Debug)]
+pub struct VsyncSource {
+    link:  DisplayLink,
+    frame: Arc<Mutex<Option<FrameTime>>>,
+    ping:  PingSource,
+}
+
+impl VsyncSource {
+    /// Creates a running `DisplayLink` driving a new event source.
+    ///
+    /// Returns `None` if a `DisplayLink` could not be created, like [`DisplayLink::new`], or if
+    /// calloop couldn't create the `Ping` waking the loop.
+    pub fn new() -> Option<Self> {
+        let (ping, source) = make_ping().ok()?;
+        let frame = Arc::new(Mutex::new(None));
+        let link = {
+            let frame = Arc::clone(&frame);
+            DisplayLink::new(move |new_frame| {
+                *frame.lock().unwrap() = Some(new_frame);
+                ping.ping();
+            })?
+        };
+        link.resume().ok()?;
+        Some(VsyncSource {
+            link,
+            frame,
+            ping: source,
+        })
+    }
+
+    /// Returns the `DisplayLink` driving the source, e.g. to pause it while nothing needs drawing.
+    pub fn link(&mut self) -> &mut DisplayLink {
+        &mut self.link
+    }
+}
+
+impl EventSource for VsyncSource {
+    type Event = FrameTime;
+    type Metadata = ();
+    type Ret = ();
+    type Error = PingError;
+
+    fn process_events<F>(
+        &mut self,
+        readiness: Readiness,
+        token: Token,
+        mut callback: F,
+    ) -> Result<PostAction, PingError>
+    where
+        F: FnMut(FrameTime, &mut ()),
+    {
+        let frame = &self.frame;
+        self.ping.process_events(readiness, token, |(), &mut ()| {
+            let frame = frame.lock().unwrap().take();
+            if let Some(frame) = frame {
+                callback(frame, &mut ())
+            }
+        })
+    }
+
+    fn register(
+        &mut self,
+        poll: &mut Poll,
+        token_factory: &mut TokenFactory,
+    ) -> calloop::Result<()> {
+        self.ping.register(poll, token_factory)
+    }
+
+    fn reregister(
+        &mut self,
+        poll: &mut Poll,
+        token_factory: &mut TokenFactory,
+    ) -> calloop::Result<()> {
+        self.ping.reregister(poll, token_factory)
+    }
+
+    fn unregister(&mut self, poll: &mut Poll) -> calloop::Result<()> {
+        self.ping.unregister(poll)
+    }
+}
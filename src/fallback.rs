@@ -0,0 +1,167 @@
+//! A `DisplayLink` that synthesizes ticks from a timer at a fixed rate, for environments without
+//! a real display link, such as CI machines, headless servers, and unsupported OSes.
+
+#[cfg(native_backend)]
+use crate::DisplayId;
+#[cfg(not(native_backend))]
+use crate::Epoch;
+use crate::{
+    callback::Park,
+    frame::{DiscontinuityCallback, RateCallback},
+    worker::Worker,
+    Backend, FrameTime, FrameTimestamp, PauseError, ResumeError, SetDisplayError,
+};
+use std::{
+    task::Waker,
+    thread,
+    time::{Duration, Instant},
+};
+#[cfg(not(native_backend))]
+use time_point::TimePoint;
+
+/// The refresh rate used by [`DisplayLink::new`], in Hz.
+pub const DEFAULT_REFRESH_RATE: f64 = 60.0;
+
+/// There are no displays to tell apart when there's no real display link.
+#[cfg(not(native_backend))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DisplayId(pub u32);
+
+#[cfg(not(native_backend))]
+impl DisplayId {
+    /// The id reported when the display driving a link is unknown, e.g. for the timer fallback.
+    pub const UNKNOWN: DisplayId = DisplayId(0);
+}
+
+/// An `Instant`.
+#[cfg(not(native_backend))]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PlatformTimestamp(pub Instant);
+
+#[cfg(not(native_backend))]
+impl PlatformTimestamp {
+    pub(crate) fn now() -> Self {
+        PlatformTimestamp(Instant::now())
+    }
+
+    pub(crate) fn seconds_since(&self, earlier: Self) -> f64 {
+        if self.0 >= earlier.0 {
+            (self.0 - earlier.0).as_secs_f64()
+        } else {
+            -(earlier.0 - self.0).as_secs_f64()
+        }
+    }
+
+    pub(crate) fn to_time_point(self, _epoch: &Epoch) -> TimePoint {
+        TimePoint::from_std_instant(self.0)
+    }
+}
+
+/// A `DisplayLink` that ticks from a dedicated thread at a fixed rate.
+#[derive(Debug)]
+pub struct DisplayLink {
+    worker: Worker,
+}
+
+impl DisplayLink {
+    /// Creates a `DisplayLink` ticking at `refresh_rate` Hz.
+    ///
+    /// Returns `None` if `refresh_rate` isn't a positive, finite number.
+    pub fn with_refresh_rate<T, F>(refresh_rate: f64, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        if !(refresh_rate.is_finite() && refresh_rate > 0.0) {
+            return None;
+        }
+        let period = Duration::from_secs_f64(1.0 / refresh_rate);
+        let mut deadline: Option<Instant> = None;
+        let worker = Worker::spawn_link(
+            move || {
+                let now = Instant::now();
+                // Keep a steady cadence, unless we fell behind, e.g. after being paused.
+                let next = match deadline {
+                    Some(deadline) if deadline > now => deadline,
+                    _ => now + period,
+                };
+                thread::sleep(next.saturating_duration_since(Instant::now()));
+                deadline = Some(next + period);
+                Some((crate::PlatformTimestamp::now(), DisplayId::UNKNOWN))
+            },
+            callback,
+        )?;
+        Some(DisplayLink { worker })
+    }
+
+    /// Creates a `DisplayLink` ticking at [`DEFAULT_REFRESH_RATE`].
+    pub fn new<T, F>(callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::with_refresh_rate(DEFAULT_REFRESH_RATE, callback)
+    }
+
+    pub fn on_display<T, F>(_display_id: DisplayId, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::new(callback)
+    }
+
+    pub fn set_display(&mut self, _display_id: DisplayId) -> Result<(), SetDisplayError> {
+        Err(SetDisplayError::Unsupported)
+    }
+
+    pub fn display(&self) -> DisplayId {
+        DisplayId::UNKNOWN
+    }
+
+    pub fn on_refresh_rate_changed(&mut self, callback: RateCallback) {
+        self.worker.on_refresh_rate_changed(callback)
+    }
+
+    pub fn on_clock_discontinuity(&mut self, callback: DiscontinuityCallback) {
+        self.worker.on_clock_discontinuity(callback)
+    }
+
+    pub fn register_waker(&mut self, waker: Waker) {
+        self.worker.register_waker(waker)
+    }
+
+    pub fn backend(&self) -> Backend {
+        Backend::Timer
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
+    pub(crate) fn park(&self) -> Park {
+        self.worker.park()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.worker.is_stopped()
+    }
+
+    pub fn pause(&mut self) -> Result<(), PauseError> {
+        if self.is_paused() {
+            Err(PauseError::AlreadyPaused)
+        } else {
+            self.worker.set_paused(true);
+            Ok(())
+        }
+    }
+
+    pub fn resume(&mut self) -> Result<(), ResumeError> {
+        if !self.is_paused() {
+            Err(ResumeError::AlreadyRunning)
+        } else {
+            self.worker.set_paused(false);
+            Ok(())
+        }
+    }
+}
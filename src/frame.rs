@@ -0,0 +1,422 @@
+use crate::{DisplayId, PlatformTimestamp};
+#[cfg(not(no_instant))]
+use std::time::Instant;
+use std::{
+    fmt::{self, Debug, Formatter},
+    sync::{Arc, Mutex},
+    task::Waker,
+    time::Duration,
+};
+use time_point::TimePoint;
+
+/// The payload passed to a `DisplayLink` callback for each display refresh.
+///
+/// `T` is the representation of the timestamp, see [`FrameTimestamp`].
+#[derive(Clone, Copy, Debug)]
+pub struct FrameTime<T = TimePoint> {
+    /// The time the screen will next refresh.
+    pub timestamp:  T,
+    /// The display that produced this refresh.
+    pub display:    DisplayId,
+    /// The time since the previous frame delivered to the callback, or zero for the first frame.
+    ///
+    /// This spans every refresh since then, including those skipped by
+    /// [`DisplayLink::set_divisor`](crate::DisplayLink::set_divisor) or dropped while the link
+    /// was paused.
+    pub delta:      Duration,
+    /// The time since the previous frame, if this is the first frame after a gap far longer than
+    /// the refreshes before it, e.g. after the process was suspended or stopped in a debugger.
+    ///
+    /// Fixed-timestep simulations should skip over the gap rather than try to catch up across it.
+    /// The first frame after the link is resumed reports the pause as
+    /// [`FrameTime::paused_for`] instead. A gap before frames skipped by
+    /// [`DisplayLink::set_divisor`](crate::DisplayLink::set_divisor) is reported by the next
+    /// frame delivered.
+    pub gap:        Option<Duration>,
+    /// How long the link was paused for, if this is the first frame since it was resumed, see
+    /// [`DisplayLink::time_paused`](crate::DisplayLink::time_paused).
+    pub paused_for: Option<Duration>,
+}
+
+/// The reference points a `DisplayLink` uses to convert `PlatformTimestamp`s into other
+/// representations.
+///
+/// An `Epoch` is captured when a `DisplayLink` delivers its first frame.
+#[derive(Clone, Copy, Debug)]
+pub struct Epoch {
+    start:        PlatformTimestamp,
+    #[cfg_attr(no_instant, allow(dead_code))]
+    platform_now: PlatformTimestamp,
+    #[cfg(not(no_instant))]
+    instant_now:  Instant,
+}
+
+impl Epoch {
+    fn new(start: PlatformTimestamp) -> Self {
+        Epoch {
+            start,
+            platform_now: PlatformTimestamp::now(),
+            #[cfg(not(no_instant))]
+            instant_now: Instant::now(),
+        }
+    }
+
+    /// Re-anchors the conversion into `Instant`s at the current time, once the two clocks drifted
+    /// apart.
+    #[cfg(not(no_instant))]
+    fn reanchor(&mut self) {
+        self.platform_now = PlatformTimestamp::now();
+        self.instant_now = Instant::now();
+    }
+
+    /// The timestamp of the first frame delivered by the `DisplayLink`.
+    pub fn start(&self) -> PlatformTimestamp {
+        self.start
+    }
+
+    /// Converts `timestamp` into an `Instant`.
+    #[cfg(not(no_instant))]
+    pub fn to_instant(&self, timestamp: PlatformTimestamp) -> Instant {
+        let secs = timestamp.seconds_since(self.platform_now);
+        if secs >= 0.0 {
+            self.instant_now + Duration::from_secs_f64(secs)
+        } else {
+            self.instant_now - Duration::from_secs_f64(-secs)
+        }
+    }
+
+    /// Returns the time elapsed between the first frame and `timestamp`.
+    pub fn since_start(&self, timestamp: PlatformTimestamp) -> Duration {
+        Duration::from_secs_f64(timestamp.seconds_since(self.start).max(0.0))
+    }
+}
+
+/// A representation of the time of a display refresh.
+///
+/// Implemented for `TimePoint`, `Instant`, `Duration` (the time since the first frame) and
+/// `PlatformTimestamp` (the raw timestamp reported by the OS). `Instant` isn't available on
+/// `wasm32-unknown-unknown`.
+pub trait FrameTimestamp: Sized + 'static {
+    /// Converts the `PlatformTimestamp` reported by the OS into `Self`.
+    fn from_platform(timestamp: PlatformTimestamp, epoch: &Epoch) -> Self;
+}
+
+impl FrameTimestamp for TimePoint {
+    fn from_platform(timestamp: PlatformTimestamp, epoch: &Epoch) -> Self {
+        timestamp.to_time_point(epoch)
+    }
+}
+
+#[cfg(not(no_instant))]
+impl FrameTimestamp for Instant {
+    fn from_platform(timestamp: PlatformTimestamp, epoch: &Epoch) -> Self {
+        epoch.to_instant(timestamp)
+    }
+}
+
+impl FrameTimestamp for Duration {
+    fn from_platform(timestamp: PlatformTimestamp, epoch: &Epoch) -> Self {
+        epoch.since_start(timestamp)
+    }
+}
+
+impl FrameTimestamp for PlatformTimestamp {
+    fn from_platform(timestamp: PlatformTimestamp, _epoch: &Epoch) -> Self {
+        timestamp
+    }
+}
+
+pub(crate) type RateCallback = Box<dyn FnMut(f64) + Send>;
+
+/// How many consecutive frames must agree on a new rate before it's reported, so late and dropped
+/// frames aren't mistaken for rate changes.
+const STABLE_FRAMES: u32 = 4;
+
+/// How far apart two rates can be, relative to the first, and still count as the same rate.
+const RATE_TOLERANCE: f64 = 0.1;
+
+#[derive(Default)]
+struct RateState {
+    last:      Option<PlatformTimestamp>,
+    rate:      Option<f64>,
+    /// The sum of the rates measured since the rate last changed, and how many there are.
+    candidate: Option<(f64, u32)>,
+    callback:  Option<RateCallback>,
+}
+
+/// Measures the rate frames are delivered at from the time between them, and reports when it
+/// changes, e.g. as a ProMotion display ramps or a monitor switches modes.
+#[derive(Default)]
+pub(crate) struct RateMonitor {
+    state: Mutex<RateState>,
+}
+
+impl Debug for RateMonitor {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("RateMonitor")
+            .field("rate", &self.state.lock().unwrap().rate)
+            .finish()
+    }
+}
+
+impl RateMonitor {
+    /// Sets the callback, which is called with the rate, in Hz, once it's been measured and
+    /// whenever it changes after that.
+    pub fn set_callback(&self, callback: RateCallback) {
+        let mut state = self.state.lock().unwrap();
+        state.callback = Some(callback);
+        state.rate = None;
+    }
+
+    fn frame(&self, timestamp: PlatformTimestamp) {
+        let mut state = self.state.lock().unwrap();
+        let period = match state.last.replace(timestamp) {
+            Some(last) => timestamp.seconds_since(last),
+            None => return,
+        };
+        if state.callback.is_none() || period <= 0.0 {
+            return;
+        }
+        let rate = 1.0 / period;
+        let same = |a: f64, b: f64| (a - b).abs() <= a * RATE_TOLERANCE;
+        if matches!(state.rate, Some(current) if same(current, rate)) {
+            state.candidate = None;
+            return;
+        }
+        let (sum, count) = match state.candidate {
+            Some((sum, count)) if same(sum / f64::from(count), rate) => (sum + rate, count + 1),
+            _ => (rate, 1),
+        };
+        if count < STABLE_FRAMES {
+            state.candidate = Some((sum, count));
+            return;
+        }
+        let rate = sum / f64::from(count);
+        state.candidate = None;
+        state.rate = Some(rate);
+        if let Some(callback) = &mut state.callback {
+            callback(rate)
+        }
+    }
+}
+
+/// A jump between the clock behind `PlatformTimestamp`s and `Instant`, e.g. across system sleep
+/// on configurations where only one of them keeps counting while asleep.
+///
+/// The `Instant`s a `DisplayLink` delivers are re-anchored when this happens, so they don't line
+/// up with those delivered before it, see [`DisplayLink::on_clock_discontinuity`].
+///
+/// [`DisplayLink::on_clock_discontinuity`]: crate::DisplayLink::on_clock_discontinuity
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ClockDiscontinuity {
+    /// The time between the last two frames according to the platform clock.
+    pub platform_elapsed: Duration,
+    /// The time between the last two frames according to `Instant`.
+    pub instant_elapsed:  Duration,
+}
+
+pub(crate) type DiscontinuityCallback = Box<dyn FnMut(ClockDiscontinuity) + Send>;
+
+/// How far the platform clock and `Instant` can disagree about the time between two frames before
+/// the `Epoch` is re-anchored. Well above the jitter in when frames are delivered.
+#[cfg(not(no_instant))]
+const MAX_CLOCK_SKEW: Duration = Duration::from_millis(250);
+
+/// How long, in seconds, the time between two frames must be to count as a gap.
+const MIN_GAP: f64 = 0.25;
+
+/// How many times longer than the time between the previous two frames a gap must be, so slow
+/// timers aren't mistaken for gaps.
+const GAP_FACTOR: f64 = 4.0;
+
+/// What a `DisplayLink` notifies of each frame besides its callback, which, unlike the callback,
+/// can be set after the link is created.
+#[derive(Default)]
+pub(crate) struct FrameObservers {
+    pub rate:      RateMonitor,
+    waker:         Mutex<Option<Waker>>,
+    #[cfg_attr(no_instant, allow(dead_code))]
+    discontinuity: Mutex<Option<DiscontinuityCallback>>,
+}
+
+impl Debug for FrameObservers {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("FrameObservers")
+            .field("rate", &self.rate)
+            .field("waker", &self.waker)
+            .finish()
+    }
+}
+
+impl FrameObservers {
+    /// Sets the waker woken on each frame, replacing the previous one.
+    pub fn set_waker(&self, waker: Waker) {
+        *self.waker.lock().unwrap() = Some(waker);
+    }
+
+    /// Sets the callback called when the `Epoch` is re-anchored, replacing the previous one.
+    pub fn set_discontinuity_callback(&self, callback: DiscontinuityCallback) {
+        *self.discontinuity.lock().unwrap() = Some(callback);
+    }
+
+    fn frame(&self, timestamp: PlatformTimestamp) {
+        self.rate.frame(timestamp);
+        if let Some(waker) = &*self.waker.lock().unwrap() {
+            waker.wake_by_ref()
+        }
+    }
+}
+
+/// Turns `PlatformTimestamp`s into `FrameTime`s, capturing the `Epoch` on the first frame.
+#[derive(Debug, Default)]
+pub(crate) struct Clock {
+    epoch:     Option<Epoch>,
+    /// The timestamp of the last frame, and when it was delivered.
+    #[cfg(not(no_instant))]
+    last:      Option<(PlatformTimestamp, Instant)>,
+    /// The timestamp of the previous frame, and the time between the two before it, in seconds.
+    previous:  Option<PlatformTimestamp>,
+    period:    Option<f64>,
+    observers: Arc<FrameObservers>,
+}
+
+impl Clock {
+    /// Returns the observers notified of the frames passed to [`Clock::frame`].
+    pub fn observers(&self) -> Arc<FrameObservers> {
+        Arc::clone(&self.observers)
+    }
+
+    pub fn frame<T: FrameTimestamp>(
+        &mut self,
+        timestamp: PlatformTimestamp,
+        display: DisplayId,
+    ) -> FrameTime<T> {
+        #[cfg(not(no_instant))]
+        self.check_continuity(timestamp);
+        self.observers.frame(timestamp);
+        let elapsed = self
+            .previous
+            .replace(timestamp)
+            .map(|previous| timestamp.seconds_since(previous).max(0.0));
+        FrameTime {
+            timestamp: self.timestamp(timestamp),
+            display,
+            delta: elapsed.map_or(Duration::ZERO, Duration::from_secs_f64),
+            gap: elapsed.and_then(|elapsed| self.gap(elapsed)),
+            paused_for: None,
+        }
+    }
+
+    /// Returns `elapsed`, the time since the previous frame, if it's far longer than the time
+    /// between the frames before it.
+    fn gap(&mut self, elapsed: f64) -> Option<Duration> {
+        let period = self.period.replace(elapsed)?;
+        if elapsed > MIN_GAP && elapsed > period * GAP_FACTOR {
+            // The frame after the gap is measured against the frames before it, not the gap.
+            self.period = Some(period);
+            Some(Duration::from_secs_f64(elapsed))
+        } else {
+            None
+        }
+    }
+
+    /// Re-anchors the `Epoch` if the platform clock and `Instant` disagree about the time since
+    /// the last frame, e.g. after the system slept.
+    #[cfg(not(no_instant))]
+    fn check_continuity(&mut self, timestamp: PlatformTimestamp) {
+        let now = Instant::now();
+        let last = self.last.replace((timestamp, now));
+        let (epoch, (last_timestamp, last_now)) = match (&mut self.epoch, last) {
+            (Some(epoch), Some(last)) => (epoch, last),
+            _ => return,
+        };
+        let platform_elapsed =
+            Duration::from_secs_f64(timestamp.seconds_since(last_timestamp).max(0.0));
+        let instant_elapsed = now - last_now;
+        let skew = platform_elapsed
+            .saturating_sub(instant_elapsed)
+            .max(instant_elapsed.saturating_sub(platform_elapsed));
+        if skew > MAX_CLOCK_SKEW {
+            epoch.reanchor();
+            if let Some(callback) = &mut *self.observers.discontinuity.lock().unwrap() {
+                callback(ClockDiscontinuity {
+                    platform_elapsed,
+                    instant_elapsed,
+                })
+            }
+        }
+    }
+
+    pub fn timestamp<T: FrameTimestamp>(&mut self, timestamp: PlatformTimestamp) -> T {
+        let epoch = self.epoch.get_or_insert_with(|| Epoch::new(timestamp));
+        T::from_platform(timestamp, epoch)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn long_pause_is_a_gap() {
+        let mut clock = Clock::default();
+        assert_eq!(clock.gap(1.0 / 60.0), None);
+        assert_eq!(clock.gap(1.0 / 60.0), None);
+        assert_eq!(clock.gap(0.5), Some(Duration::from_secs_f64(0.5)));
+        // The next frame is measured against the frames before the gap.
+        assert_eq!(clock.gap(1.0 / 60.0), None);
+        assert_eq!(clock.gap(0.5), Some(Duration::from_secs_f64(0.5)));
+    }
+
+    #[test]
+    fn short_or_gradual_slowdowns_are_not_gaps() {
+        let mut clock = Clock::default();
+        // The first frame has nothing to compare to.
+        assert_eq!(clock.gap(1.0), None);
+        let mut clock = Clock::default();
+        clock.gap(1.0 / 120.0);
+        // Far longer than the last frame, but too short to notice.
+        assert_eq!(clock.gap(0.2), None);
+        // Slow timers are only slow.
+        clock.gap(0.3);
+        assert_eq!(clock.gap(1.0), None);
+    }
+
+    #[cfg(target_os = "linux")]
+    fn discontinuities(clock: &Clock) -> Arc<Mutex<Vec<ClockDiscontinuity>>> {
+        let discontinuities = Arc::new(Mutex::new(Vec::new()));
+        let sink = Arc::clone(&discontinuities);
+        clock
+            .observers
+            .set_discontinuity_callback(Box::new(move |discontinuity| {
+                sink.lock().unwrap().push(discontinuity)
+            }));
+        discontinuities
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn clocks_in_step_are_continuous() {
+        let mut clock = Clock::default();
+        let discontinuities = discontinuities(&clock);
+        let _: FrameTime = clock.frame(PlatformTimestamp::now(), DisplayId::UNKNOWN);
+        let _: FrameTime = clock.frame(PlatformTimestamp::now(), DisplayId::UNKNOWN);
+        assert!(discontinuities.lock().unwrap().is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn clock_jump_is_a_discontinuity() {
+        let mut clock = Clock::default();
+        let discontinuities = discontinuities(&clock);
+        let now = PlatformTimestamp::now();
+        let _: FrameTime = clock.frame(now, DisplayId::UNKNOWN);
+        // The platform clock kept counting through a sleep that `Instant` didn't see.
+        let later = PlatformTimestamp(now.0 + 10_000_000_000);
+        let _: FrameTime = clock.frame(later, DisplayId::UNKNOWN);
+        let discontinuities = discontinuities.lock().unwrap();
+        assert_eq!(discontinuities.len(), 1);
+        assert!(discontinuities[0].platform_elapsed >= Duration::from_secs(10));
+        assert!(discontinuities[0].instant_elapsed < MAX_CLOCK_SKEW);
+    }
+}
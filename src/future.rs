@@ -0,0 +1,89 @@
+use crate::{DisplayLink, FrameTime};
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+#[derive(Debug, Default)]
+struct Slot {
+    frame: Option<FrameTime>,
+    waker: Option<Waker>,
+}
+
+/// Hands the latest frame from a `DisplayLink` callback to whichever task polls for it, waking
+/// the task with nothing but `std::task::Waker`, so it works under any executor.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct FrameSlot(Arc<Mutex<Slot>>);
+
+impl FrameSlot {
+    /// Creates a `DisplayLink` filling the slot, and starts it.
+    pub fn link(&self) -> Option<DisplayLink> {
+        let slot = self.clone();
+        let link = DisplayLink::new(move |frame| slot.put(frame))?;
+        link.resume().ok()?;
+        Some(link)
+    }
+
+    /// Replaces any frame that hasn't been taken yet, and wakes the waiting task.
+    fn put(&self, frame: FrameTime) {
+        let waker = {
+            let mut slot = self.0.lock().unwrap();
+            slot.frame = Some(frame);
+            slot.waker.take()
+        };
+        if let Some(waker) = waker {
+            waker.wake()
+        }
+    }
+
+    /// Takes the latest frame, or registers the task to be woken by the next one.
+    pub fn poll_take(&self, cx: &mut Context<'_>) -> Poll<FrameTime> {
+        let mut slot = self.0.lock().unwrap();
+        match slot.frame.take() {
+            Some(frame) => Poll::Ready(frame),
+            None => {
+                slot.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+/// A `Future` resolving to the next frame, created with [`DisplayLink::next_frame`].
+///
+/// Resolves to `None` if a `DisplayLink` could not be created.
+#[derive(Debug)]
+pub struct NextFrame {
+    /// Dropped once the frame arrives, so the display link only runs while it's awaited.
+    link: Option<DisplayLink>,
+    slot: FrameSlot,
+}
+
+impl NextFrame {
+    pub(crate) fn new() -> Self {
+        let slot = FrameSlot::default();
+        NextFrame {
+            link: slot.link(),
+            slot,
+        }
+    }
+}
+
+impl Future for NextFrame {
+    type Output = Option<FrameTime>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<FrameTime>> {
+        if self.link.is_none() {
+            return Poll::Ready(None);
+        }
+        match self.slot.poll_take(cx) {
+            Poll::Ready(frame) => {
+                self.link = None;
+                Poll::Ready(Some(frame))
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
@@ -0,0 +1,208 @@
+use crate::callback::{Policy, Throttle};
+use std::{
+    fmt::{self, Debug, Formatter},
+    time::Duration,
+};
+
+/// The frame rate a [governed](crate::DisplayLink::govern_frame_rate) `DisplayLink` stepped to.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct GovernorChange {
+    /// Only every `divisor`th refresh reaches the callback now.
+    pub divisor:    u32,
+    /// The frames per second that delivers at the current refresh rate.
+    pub frame_rate: f64,
+}
+
+pub(crate) type GovernorCallback = Box<dyn FnMut(GovernorChange) + Send>;
+
+/// How many frames the callback's time is averaged over before the governor may step.
+const WINDOW: u32 = 30;
+/// How many windows in a row must fit the faster rate before stepping back up. It only takes one
+/// to step down, so the rate doesn't flap between two steps.
+const WINDOWS_UP: u32 = 4;
+/// The share of the faster rate's time per frame the callback must stay under to step back up.
+const HEADROOM: f64 = 0.75;
+/// The largest divisor the governor steps down to.
+const MAX_DIVISOR: u32 = 4;
+
+/// Measures how long the callback takes, and skips more or fewer refreshes so it keeps up.
+pub(crate) struct Governor {
+    /// The time between refreshes, in seconds, once it's been measured.
+    refresh:   f64,
+    /// The time spent in the callback over the current window, in seconds, and the frames in it.
+    busy:      f64,
+    frames:    u32,
+    /// The windows in a row the callback would have kept up at the faster rate.
+    calm:      u32,
+    on_change: GovernorCallback,
+}
+
+impl Debug for Governor {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("Governor")
+            .field("refresh", &self.refresh)
+            .field("calm", &self.calm)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Governor {
+    pub fn new(on_change: GovernorCallback) -> Self {
+        Governor {
+            refresh: 0.0,
+            busy: 0.0,
+            frames: 0,
+            calm: 0,
+            on_change,
+        }
+    }
+
+    /// Measures the time between refreshes from every refresh, whether or not it reaches the
+    /// callback, skipping the time across a gap.
+    pub fn refresh(&mut self, delta: Duration, gap: bool) {
+        if !gap && delta > Duration::ZERO {
+            self.refresh = delta.as_secs_f64();
+        }
+    }
+
+    /// Records that the callback took `busy` seconds, and steps the rate once a window is over.
+    pub fn frame(&mut self, busy: f64, throttle: &Throttle) {
+        self.busy += busy;
+        self.frames += 1;
+        if self.frames < WINDOW {
+            return;
+        }
+        let average = self.busy / f64::from(self.frames);
+        self.busy = 0.0;
+        self.frames = 0;
+        if self.refresh <= 0.0 {
+            return;
+        }
+
+        let divisor = throttle.divisor();
+        let own = throttle.get(Policy::Governor);
+        let step = if average > self.refresh * f64::from(divisor) {
+            self.calm = 0;
+            Some(divisor + 1).filter(|&step| step <= MAX_DIVISOR)
+        } else if own > 1 && average < self.refresh * f64::from(own - 1) * HEADROOM {
+            self.calm += 1;
+            Some(own - 1).filter(|_| self.calm >= WINDOWS_UP)
+        } else {
+            self.calm = 0;
+            None
+        };
+        if let Some(step) = step {
+            self.calm = 0;
+            throttle.set(Policy::Governor, step);
+            // Other policies may skip as many refreshes either way.
+            let stepped = throttle.divisor();
+            if stepped != divisor {
+                (self.on_change)(GovernorChange {
+                    divisor:    stepped,
+                    frame_rate: 1.0 / (self.refresh * f64::from(stepped)),
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+
+    const REFRESH: Duration = Duration::from_millis(10);
+
+    fn governor() -> (Governor, Arc<Mutex<Vec<u32>>>) {
+        let changes = Arc::new(Mutex::new(Vec::new()));
+        let mut governor = {
+            let changes = Arc::clone(&changes);
+            Governor::new(Box::new(move |change| {
+                changes.lock().unwrap().push(change.divisor)
+            }))
+        };
+        governor.refresh(REFRESH, false);
+        (governor, changes)
+    }
+
+    /// Feeds the governor a window of frames that each took `busy` seconds.
+    fn window(governor: &mut Governor, throttle: &Throttle, busy: f64) {
+        for _ in 0..WINDOW {
+            governor.frame(busy, throttle);
+        }
+    }
+
+    #[test]
+    fn steps_down_after_one_slow_window() {
+        let (mut governor, changes) = governor();
+        let throttle = Throttle::default();
+        for _ in 0..WINDOW - 1 {
+            governor.frame(0.015, &throttle);
+        }
+        assert_eq!(throttle.divisor(), 1);
+        governor.frame(0.015, &throttle);
+        assert_eq!(throttle.divisor(), 2);
+        assert_eq!(*changes.lock().unwrap(), [2]);
+    }
+
+    #[test]
+    fn steps_up_only_after_calm_windows() {
+        let (mut governor, changes) = governor();
+        let throttle = Throttle::default();
+        window(&mut governor, &throttle, 0.015);
+        for _ in 1..WINDOWS_UP {
+            window(&mut governor, &throttle, 0.001);
+            assert_eq!(throttle.divisor(), 2);
+        }
+        window(&mut governor, &throttle, 0.001);
+        assert_eq!(throttle.divisor(), 1);
+        assert_eq!(*changes.lock().unwrap(), [2, 1]);
+    }
+
+    #[test]
+    fn busy_window_resets_calm() {
+        let (mut governor, _) = governor();
+        let throttle = Throttle::default();
+        window(&mut governor, &throttle, 0.015);
+        for _ in 1..WINDOWS_UP {
+            window(&mut governor, &throttle, 0.001);
+        }
+        // Fits the current rate, but not the faster one.
+        window(&mut governor, &throttle, 0.009);
+        window(&mut governor, &throttle, 0.001);
+        assert_eq!(throttle.divisor(), 2);
+    }
+
+    #[test]
+    fn clamps_at_max_divisor() {
+        let (mut governor, changes) = governor();
+        let throttle = Throttle::default();
+        for _ in 0..MAX_DIVISOR + 2 {
+            window(&mut governor, &throttle, 1.0);
+        }
+        assert_eq!(throttle.divisor(), MAX_DIVISOR);
+        assert_eq!(*changes.lock().unwrap(), [2, 3, 4]);
+    }
+
+    #[test]
+    fn waits_for_the_refresh_rate() {
+        let mut governor = Governor::new(Box::new(|_| ()));
+        let throttle = Throttle::default();
+        governor.refresh(REFRESH * 10, true);
+        window(&mut governor, &throttle, 1.0);
+        assert_eq!(throttle.divisor(), 1);
+    }
+
+    #[test]
+    fn steps_past_other_policies() {
+        let (mut governor, changes) = governor();
+        let throttle = Throttle::default();
+        throttle.set(Policy::Thermal, 2);
+        window(&mut governor, &throttle, 0.015);
+        assert_eq!(throttle.get(Policy::Governor), 1);
+        // Slower than every other refresh, so the governor asks for every third.
+        window(&mut governor, &throttle, 0.025);
+        assert_eq!(throttle.get(Policy::Governor), 3);
+        assert_eq!(*changes.lock().unwrap(), [3]);
+    }
+}
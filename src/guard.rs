@@ -0,0 +1,26 @@
+use crate::DisplayLink;
+
+/// Keeps a `DisplayLink` paused until dropped, created with [`DisplayLink::pause_guard`].
+///
+/// The link is only resumed if the guard paused it, so a link that was already paused stays
+/// paused.
+#[derive(Debug)]
+pub struct PauseGuard<'a> {
+    link:   &'a DisplayLink,
+    resume: bool,
+}
+
+impl<'a> PauseGuard<'a> {
+    pub(crate) fn new(link: &'a DisplayLink) -> Self {
+        let resume = link.set_paused(true);
+        PauseGuard { link, resume }
+    }
+}
+
+impl Drop for PauseGuard<'_> {
+    fn drop(&mut self) {
+        if self.resume {
+            self.link.set_paused(false);
+        }
+    }
+}
@@ -1,56 +1,75 @@
-#![cfg(target_os = "ios")]
-
-const EPSILON: f64 = 0.0001;
-
-macro_rules! foreign_obj_type {
-    {type CType = $raw_ident:ident;
-    fn drop = $drop_func:ident;
-    pub struct $owned_ident:ident;
-    pub struct $ref_ident:ident;
-    } => {
-        foreign_types::foreign_type! {
-            type CType = $raw_ident;
-            fn drop = $drop_func;
-            pub struct $owned_ident;
-            pub struct $ref_ident;
-        }
-
-        unsafe impl ::objc::Message for $raw_ident {
-        }
-        unsafe impl ::objc::Message for $ref_ident {
-        }
-
-        impl ::std::fmt::Debug for $ref_ident {
-            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-                unsafe {
-                    use ::objc_foundation::INSString;
-                    // TODO: might leak, not 100% sure...
-                    let string: &::objc_foundation::NSString = msg_send![self, debugDescription];
-                    write!(f, "{}", string.as_str())
-                }
-            }
-        }
-
-        impl ::std::fmt::Debug for $owned_ident {
-            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
-                ::std::ops::Deref::deref(self).fmt(f)
-            }
-        }
-    };
-}
+#![cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
 
 pub mod cadisplaylink;
+pub mod cametaldisplaylink;
+mod lifecycle;
+mod power;
+mod thread;
+
+pub use crate::{
+    ios::{cadisplaylink::CAFrameRateRange as FrameRateRange, power::is_low_power_mode_enabled},
+    RunLoopMode,
+};
 
-use crate::{ios::cadisplaylink::DisplayLink as RawDisplayLink, PauseError, ResumeError};
+use crate::{
+    callback::Park,
+    display::WatchCallback,
+    frame::{Clock, DiscontinuityCallback, Epoch, FrameObservers, RateCallback},
+    ios::{
+        cadisplaylink::{CADisplayLink, DisplayLink as RawDisplayLink, DisplayLinkRef},
+        cametaldisplaylink::MetalDisplayLink as RawMetalDisplayLink,
+        lifecycle::LifecycleObserver,
+        power::PowerObserver,
+        thread::RunLoopThread,
+    },
+    thermal::{self, ThermalObserver},
+    Backend, Bounds, Display, DisplayEvent, FrameTime, FrameTimestamp, PauseError, ResumeError,
+    SetDisplayError,
+};
+use foreign_types::{ForeignType, ForeignTypeRef};
 use objc::{
     class,
     declare::ClassDecl,
     msg_send,
-    runtime::{Object, Sel, NO, YES},
+    runtime::{Object, Sel, BOOL, NO, YES},
     sel, sel_impl,
 };
-use std::{ffi::c_void, panic, ptr, sync::Once};
-use time_point::{Duration, TimePoint};
+use objc_foundation::{INSString, NSString};
+use std::{
+    any::Any,
+    ffi::c_void,
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+    mem, panic, ptr,
+    sync::{
+        atomic::{AtomicU8, Ordering},
+        Arc, Mutex, Once,
+    },
+    task::Waker,
+};
+use time_point::TimePoint;
+
+/// Returns `true` when running as a Mac Catalyst app.
+///
+/// Catalyst apps are built for `target_os = "ios"` with `target_abi = "macabi"`, so they use this
+/// backend rather than the `CVDisplayLink` one, even though they run on macOS.
+pub fn is_catalyst() -> bool {
+    cfg!(catalyst)
+}
+
+/// Returns `true` when running in the iOS, tvOS or visionOS Simulator.
+///
+/// The simulator's `CADisplayLink` is paced by the host rather than a real display, so it never
+/// runs above 60 Hz and its `targetTimestamp` is coarse. Frames are timed from `timestamp` there
+/// instead.
+pub fn is_simulated() -> bool {
+    cfg!(simulator) || std::env::var_os("SIMULATOR_DEVICE_NAME").is_some()
+}
+
+pub(crate) fn is_main_thread() -> bool {
+    let is_main: BOOL = unsafe { msg_send![class!(NSThread), isMainThread] };
+    is_main != NO
+}
 
 pub fn is_ios10() -> bool {
     type NSInteger = std::os::raw::c_long;
@@ -61,168 +80,906 @@ pub fn is_ios10() -> bool {
     version[0] >= 10
 }
 
-#[derive(Debug)]
-pub struct DisplayLink {
-    display_link: RawDisplayLink,
-    raw_callback: *mut c_void,
-    drop_callback: unsafe fn(*mut c_void),
+/// A `UIScreen` identifying a display.
+///
+/// The screen is not retained, so this should only be used to compare against other screens.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DisplayId(pub *mut Object);
+
+unsafe impl Send for DisplayId {}
+unsafe impl Sync for DisplayId {}
+
+impl RunLoopMode {
+    /// Calls `f` with the mode's name.
+    unsafe fn with_name<R>(&self, f: impl FnOnce(*mut NSString) -> R) -> R {
+        match self {
+            RunLoopMode::Common => f(cadisplaylink::NSRunLoopCommonModes),
+            RunLoopMode::Default => f(cadisplaylink::NSDefaultRunLoopMode),
+            RunLoopMode::Custom(name) => {
+                let name = NSString::from_str(name);
+                f(&*name as *const NSString as *mut NSString)
+            }
+        }
+    }
 }
 
-impl Drop for DisplayLink {
-    fn drop(&mut self) {
-        unsafe { (self.drop_callback)(self.raw_callback) }
+impl DisplayId {
+    /// The id reported when the display driving a link is unknown, e.g. for the timer fallback.
+    pub const UNKNOWN: DisplayId = DisplayId(ptr::null_mut());
+
+    /// Returns the `DisplayId` of `[UIScreen mainScreen]`.
+    ///
+    /// visionOS has no `UIScreen`, so this is always `DisplayId::UNKNOWN` there.
+    pub fn main() -> Self {
+        if cfg!(target_os = "visionos") {
+            DisplayId::UNKNOWN
+        } else {
+            DisplayId(unsafe { msg_send![class!(UIScreen), mainScreen] })
+        }
     }
 }
 
-extern "C" fn run_callback_pre_ios10<F: 'static + FnMut(TimePoint)>(
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+struct CGRect {
+    x:      f64,
+    y:      f64,
+    width:  f64,
+    height: f64,
+}
+
+/// `UIScreen` has no localized name, so names are always `None`. There are no screens on visionOS.
+pub(crate) fn displays() -> Vec<Display> {
+    if cfg!(target_os = "visionos") {
+        return Vec::new();
+    }
+    unsafe {
+        let screens: *mut Object = msg_send![class!(UIScreen), screens];
+        let count: usize = msg_send![screens, count];
+        (0..count)
+            .map(|i| display(msg_send![screens, objectAtIndex: i]))
+            .collect()
+    }
+}
+
+unsafe fn display(screen: *mut Object) -> Display {
+    let rect: CGRect = msg_send![screen, bounds];
+    let responds: BOOL = msg_send![screen, respondsToSelector: sel!(maximumFramesPerSecond)];
+    let refresh_rate = if responds != NO {
+        let fps: isize = msg_send![screen, maximumFramesPerSecond];
+        // The simulator reports the host's rate, but never runs above 60 Hz.
+        let fps = if is_simulated() { fps.min(60) } else { fps };
+        Some(fps as f64)
+    } else {
+        None
+    };
+    Display {
+        id: DisplayId(screen),
+        name: None,
+        bounds: Bounds {
+            x:      rect.x,
+            y:      rect.y,
+            width:  rect.width,
+            height: rect.height,
+        },
+        refresh_rate,
+        refresh_rate_range: None,
+        mirrors: {
+            let mirrored: *mut Object = msg_send![screen, mirroredScreen];
+            Some(DisplayId(mirrored)).filter(|_| !mirrored.is_null())
+        },
+    }
+}
+
+#[cfg(not(target_os = "visionos"))]
+#[link(name = "UIKit", kind = "framework")]
+extern "C" {
+    #[allow(improper_ctypes)]
+    static UIScreenDidConnectNotification: *mut objc_foundation::NSString;
+    #[allow(improper_ctypes)]
+    static UIScreenDidDisconnectNotification: *mut objc_foundation::NSString;
+    #[allow(improper_ctypes)]
+    static UIScreenModeDidChangeNotification: *mut objc_foundation::NSString;
+}
+
+unsafe fn screen_event(
     this: &Object,
-    _: Sel,
-    display_link: *mut Object,
+    notification: *mut Object,
+    event: unsafe fn(*mut Object) -> DisplayEvent,
 ) {
+    let callback: *mut c_void = *this.get_ivar("_data");
+    let callback = &mut *(callback as *mut WatchCallback);
+    let screen: *mut Object = msg_send![notification, object];
+    callback(event(screen))
+}
+
+extern "C" fn screen_did_connect(this: &Object, _: Sel, notification: *mut Object) {
     unsafe {
-        let callback: *mut c_void = *this.get_ivar("_data");
-        let callback = &mut *(callback as *mut Callback<F>);
+        screen_event(this, notification, |screen| {
+            DisplayEvent::Connected(DisplayId(screen))
+        })
+    }
+}
 
-        let t: f64 = msg_send![display_link, timestamp];
-        let duration: f64 = msg_send![display_link, duration];
+extern "C" fn screen_did_disconnect(this: &Object, _: Sel, notification: *mut Object) {
+    unsafe {
+        screen_event(this, notification, |screen| {
+            DisplayEvent::Disconnected(DisplayId(screen))
+        })
+    }
+}
 
-        let (start_os, start_rust) = match callback.start_time {
-            Some((start_os, start_rust)) => (start_os, start_rust),
-            None => {
-                let os_cur_time = cadisplaylink::CACurrentMediaTime();
-                let rust_cur_time = TimePoint::from_std_instant(std::time::Instant::now());
-                let start_os = t;
-                debug_assert!(
-                    start_os <= os_cur_time
-                        || (os_cur_time - start_os).abs() / os_cur_time <= EPSILON
+extern "C" fn screen_mode_did_change(this: &Object, _: Sel, notification: *mut Object) {
+    unsafe {
+        screen_event(this, notification, |screen| {
+            DisplayEvent::reconfigured(&display(screen))
+        })
+    }
+}
+
+/// Reports screens coming, going and changing mode with `UIScreenDidConnectNotification`,
+/// `UIScreenDidDisconnectNotification` and `UIScreenModeDidChangeNotification`, which are posted
+/// on the main thread. There are no screens on visionOS, so there's nothing to watch there.
+///
+/// There's no notification for a screen starting or stopping mirroring, so `mirroredScreen` is
+/// checked whenever a screen connects or changes mode.
+#[derive(Debug)]
+pub(crate) struct Watcher {
+    observer: *mut Object,
+    callback: *mut WatchCallback,
+}
+
+impl Watcher {
+    #[cfg(target_os = "visionos")]
+    pub fn new(_callback: WatchCallback) -> Option<Self> {
+        None
+    }
+
+    #[cfg(not(target_os = "visionos"))]
+    pub fn new(mut callback: WatchCallback) -> Option<Self> {
+        let mut mirroring: Vec<(DisplayId, Option<DisplayId>)> = displays()
+            .iter()
+            .map(|display| (display.id, display.mirrors))
+            .collect();
+        let callback: WatchCallback = Box::new(move |event| {
+            let id = match event {
+                DisplayEvent::Connected(id) | DisplayEvent::Reconfigured { id, .. } => Some(id),
+                DisplayEvent::Disconnected(id) => {
+                    mirroring.retain(|(known, _)| *known != id);
+                    None
+                }
+                DisplayEvent::MirroringChanged { .. } => None,
+            };
+            callback(event);
+            if let Some(id) = id {
+                let display = unsafe { display(id.0) };
+                match mirroring.iter_mut().find(|(known, _)| *known == id) {
+                    Some((_, mirrors)) if *mirrors == display.mirrors => (),
+                    Some((_, mirrors)) => {
+                        *mirrors = display.mirrors;
+                        callback(DisplayEvent::mirroring_changed(&display))
+                    }
+                    None => {
+                        mirroring.push((id, display.mirrors));
+                        if display.mirrors.is_some() {
+                            callback(DisplayEvent::mirroring_changed(&display))
+                        }
+                    }
+                }
+            }
+        });
+        static OBSERVER_CLASS_CREATOR: Once = Once::new();
+        OBSERVER_CLASS_CREATOR.call_once(|| {
+            let mut decl =
+                ClassDecl::new("DisplayLinkScreenConnectionObserver", class!(NSObject)).unwrap();
+            decl.add_ivar::<*mut c_void>("_data");
+            unsafe {
+                decl.add_method(
+                    sel!(screenDidConnect:),
+                    screen_did_connect as extern "C" fn(&Object, Sel, *mut Object),
+                );
+                decl.add_method(
+                    sel!(screenDidDisconnect:),
+                    screen_did_disconnect as extern "C" fn(&Object, Sel, *mut Object),
+                );
+                decl.add_method(
+                    sel!(screenModeDidChange:),
+                    screen_mode_did_change as extern "C" fn(&Object, Sel, *mut Object),
                 );
-                let d = os_cur_time - start_os;
-                let d = Duration::from_secs_f64(d);
-                let start_rust = rust_cur_time - d;
-                callback.start_time = Some((start_os, start_rust));
-                (start_os, start_rust)
+            }
+            decl.register();
+        });
+
+        let callback = Box::into_raw(Box::new(callback));
+        unsafe {
+            let observer: *mut Object =
+                msg_send![class!(DisplayLinkScreenConnectionObserver), alloc];
+            let observer: *mut Object = msg_send![observer, init];
+            (*observer).set_ivar::<*mut c_void>("_data", callback as *mut c_void);
+            let center: *mut Object = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![
+                center,
+                addObserver: observer
+                selector: sel!(screenDidConnect:)
+                name: UIScreenDidConnectNotification
+                object: ptr::null_mut::<Object>()
+            ];
+            let () = msg_send![
+                center,
+                addObserver: observer
+                selector: sel!(screenDidDisconnect:)
+                name: UIScreenDidDisconnectNotification
+                object: ptr::null_mut::<Object>()
+            ];
+            let () = msg_send![
+                center,
+                addObserver: observer
+                selector: sel!(screenModeDidChange:)
+                name: UIScreenModeDidChangeNotification
+                object: ptr::null_mut::<Object>()
+            ];
+            Some(Watcher { observer, callback })
+        }
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        unsafe {
+            let center: *mut Object = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center, removeObserver: self.observer];
+            let () = msg_send![self.observer, release];
+            drop(Box::from_raw(self.callback));
+        }
+    }
+}
+
+/// The callback of a link, shared with the `DisplayLinkCallbackHolder` the `CADisplayLink` calls,
+/// so it can be dropped safely while a run loop on another thread may be calling it.
+///
+/// It's passed the `CFTimeInterval` of the next frame.
+struct SharedCallback {
+    callback: Mutex<Option<Render>>,
+    /// Applied on the link's own thread before the next frame, for observers posting from other
+    /// threads.
+    rates:    Mutex<Rates>,
+    on_grant: Mutex<Option<GrantCallback>>,
+}
+
+type Render = Box<dyn FnMut(f64)>;
+
+/// The frame rate a link asked for, and the rate the system granted it.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct FrameRateGrant {
+    /// The range the link asked for, after any cap for Low Power Mode.
+    pub requested: FrameRateRange,
+    /// The frames per second the system is delivering, from the time between the current frame
+    /// and the next one.
+    pub granted:   f64,
+}
+
+pub(crate) type GrantCallback = Box<dyn FnMut(FrameRateGrant) + Send>;
+
+/// How far apart two granted rates can be, relative to the first, and still count as the same
+/// rate. The system schedules frames on whole refreshes, so granted rates barely jitter.
+const GRANT_TOLERANCE: f64 = 0.01;
+
+/// The frame rates asked for by the app and by Low Power Mode.
+#[derive(Debug, Default)]
+struct Rates {
+    /// The range the app asked for, where all zeros lets the system pick.
+    preferred: FrameRateRange,
+    /// The most frames per second Low Power Mode allows, or 0 while it's off.
+    cap:       f32,
+    /// Set when the rates changed since the link last applied them.
+    changed:   bool,
+    /// The frames per second the system granted, once a frame has been delivered.
+    granted:   Option<f64>,
+    /// Set when the rates were applied, until the rate granted for them is reported.
+    awaiting:  bool,
+}
+
+impl Rates {
+    /// The preferred range, capped while Low Power Mode is on.
+    fn range(&self) -> FrameRateRange {
+        let cap = |fps: f32| {
+            if fps > 0.0 {
+                fps.min(self.cap)
+            } else {
+                self.cap
             }
         };
-        let t = t + duration;
+        let mut range = self.preferred;
+        if self.cap > 0.0 {
+            range.maximum = cap(range.maximum);
+            range.preferred = cap(range.preferred);
+            range.minimum = range.minimum.min(range.maximum);
+        }
+        range
+    }
 
-        let diff = Duration::from_secs_f64(t - start_os);
-        let instant = start_rust + diff;
-        (callback.f)(instant)
+    /// Records the rate granted for a frame, `interval` being the time until the next one, or 0 if
+    /// it isn't known, and applies the rates if they changed. Returns the grant if it changed, or
+    /// if it's the first since the rates were last applied.
+    unsafe fn frame(
+        &mut self,
+        display_link: &DisplayLinkRef,
+        interval: f64,
+    ) -> Option<FrameRateGrant> {
+        let grant = if interval > 0.0 {
+            let granted = 1.0 / interval;
+            let changed = !matches!(
+                self.granted.replace(granted),
+                Some(previous) if (granted - previous).abs() <= previous * GRANT_TOLERANCE
+            );
+            (changed || mem::take(&mut self.awaiting)).then(|| FrameRateGrant {
+                requested: self.range(),
+                granted,
+            })
+        } else {
+            None
+        };
+        if mem::take(&mut self.changed) {
+            self.apply(display_link);
+            self.awaiting = true;
+        }
+        grant
+    }
+
+    /// Asks the system for the current range, where ranges are supported, or for its preferred
+    /// rate where they aren't. Before iOS 10, links can't ask for a rate at all.
+    unsafe fn apply(&self, display_link: &DisplayLinkRef) {
+        let range = self.range();
+        if display_link.supports_frame_rate_ranges() {
+            display_link.set_preferred_frame_rate_range(range);
+        } else if display_link.supports_preferred_frames_per_second() {
+            let fps = if range.preferred > 0.0 {
+                range.preferred
+            } else {
+                range.maximum
+            };
+            display_link.set_preferred_frames_per_second(fps.round() as isize);
+        }
     }
 }
 
-extern "C" fn run_callback_ios10<F: 'static + FnMut(TimePoint)>(
-    this: &Object,
-    _: Sel,
-    display_link: *mut Object,
-) {
+// The holder's reference may be released on the thread of the link's run loop, so the reference
+// count is atomic. The callback itself only leaves the thread that created it when it's `Send`, as
+// only `DisplayLink::on_run_loop` adds links to another thread's run loop, and links can't leave
+// their thread.
+unsafe impl Send for SharedCallback {}
+unsafe impl Sync for SharedCallback {}
+
+impl Debug for SharedCallback {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("SharedCallback").finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug)]
+pub struct DisplayLink {
+    display_link: RawDisplayLink,
+    callback:     Arc<SharedCallback>,
+    observers:    Arc<FrameObservers>,
+    display:      DisplayId,
+    /// The thread running the link, for links made with [`DisplayLink::on_dedicated_thread`].
+    /// Dropped after the link is invalidated.
+    thread:       Option<RunLoopThread>,
+    /// Set when the link pauses while the app is in the background.
+    lifecycle:    Option<LifecycleObserver>,
+    /// Set when the link pauses while the device is locked.
+    lock:         Option<LifecycleObserver>,
+    /// Set when the link is throttled while the app is inactive.
+    inactive:     Option<LifecycleObserver>,
+    /// Set when the link slows down in Low Power Mode.
+    power:        Option<PowerObserver>,
+    thermal:      Option<ThermalObserver>,
+    /// The `Reason`s the link was paused for, as bits, so it resumes once they're all over.
+    suspended:    Arc<AtomicU8>,
+}
+
+impl Drop for DisplayLink {
+    fn drop(&mut self) {
+        // The observers can touch the link, so they go first.
+        self.lifecycle = None;
+        self.lock = None;
+        self.power = None;
+        unsafe { self.display_link.invalidate() }
+        // Waits for a callback running on another thread's run loop to return.
+        drop(self.callback.callback.lock().unwrap().take());
+    }
+}
+
+/// The `CFTimeInterval` of the next frame, in the time base of `CACurrentMediaTime`.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct PlatformTimestamp(pub f64);
+
+impl PlatformTimestamp {
+    pub(crate) fn now() -> Self {
+        PlatformTimestamp(unsafe { cadisplaylink::CACurrentMediaTime() })
+    }
+
+    pub(crate) fn seconds_since(&self, earlier: Self) -> f64 {
+        self.0 - earlier.0
+    }
+
+    pub(crate) fn to_time_point(self, epoch: &Epoch) -> TimePoint {
+        TimePoint::from_std_instant(epoch.to_instant(self))
+    }
+}
+
+unsafe fn shared_callback(this: &Object) -> &SharedCallback {
+    let callback: *const c_void = *this.get_ivar("_data");
+    &*(callback as *const SharedCallback)
+}
+
+unsafe fn run_callback(this: &Object, t: f64) {
+    if let Some(f) = &mut *shared_callback(this).callback.lock().unwrap() {
+        // Threads other than the main one don't drain autorelease pools of their own.
+        objc::rc::autoreleasepool(|| f(t))
+    }
+}
+
+extern "C" fn run_callback_pre_ios10(this: &Object, _: Sel, display_link: *mut Object) {
     unsafe {
-        let callback: *mut c_void = *this.get_ivar("_data");
-        let callback = &mut *(callback as *mut Callback<F>);
+        let t: f64 = msg_send![display_link, timestamp];
+        let duration: f64 = msg_send![display_link, duration];
+        // Without `targetTimestamp` the granted rate isn't known, but the simulator still honors
+        // the rates asked for.
+        shared_callback(this)
+            .rates
+            .lock()
+            .unwrap()
+            .frame(DisplayLinkRef::from_ptr(display_link.cast()), 0.0);
+        run_callback(this, t + duration)
+    }
+}
 
+extern "C" fn run_callback_ios10(this: &Object, _: Sel, display_link: *mut Object) {
+    unsafe {
+        let shared = shared_callback(this);
+        let timestamp: f64 = msg_send![display_link, timestamp];
         let t: f64 = msg_send![display_link, targetTimestamp];
         let duration: f64 = msg_send![display_link, duration];
+        let grant = shared
+            .rates
+            .lock()
+            .unwrap()
+            .frame(DisplayLinkRef::from_ptr(display_link.cast()), t - timestamp);
+        if let (Some(grant), Some(on_grant)) = (grant, &mut *shared.on_grant.lock().unwrap()) {
+            on_grant(grant)
+        }
+        run_callback(this, t + duration)
+    }
+}
 
-        let (start_os, start_rust) = match callback.start_time {
-            Some((start_os, start_rust)) => (start_os, start_rust),
-            None => {
-                let os_cur_time = cadisplaylink::CACurrentMediaTime();
-                let rust_cur_time = TimePoint::from_std_instant(std::time::Instant::now());
-                let start_os = t;
-                debug_assert!(
-                    start_os <= os_cur_time
-                        || (os_cur_time - start_os).abs() / os_cur_time <= EPSILON
-                );
-                let d = os_cur_time - start_os;
-                let d = Duration::from_secs_f64(d);
-                let start_rust = rust_cur_time - d;
-                callback.start_time = Some((start_os, start_rust));
-                (start_os, start_rust)
-            }
-        };
-        let t = t + duration;
+/// Why a link was paused on its own, rather than by hand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Reason {
+    /// The app, or the scene the link follows, is in the background.
+    Background = 1 << 0,
+    /// The device is locked.
+    Locked = 1 << 1,
+}
+
+/// The `CADisplayLink` a `Park` pauses. The park only runs in the link's callback, on the thread
+/// whose run loop the link was added to, so the pointer never leaves that thread.
+struct Parked(*mut CADisplayLink);
+
+unsafe impl Send for Parked {}
+
+impl Parked {
+    fn pause(&self, suspended: &AtomicU8) {
+        // The callback paused the link itself, so nothing should resume it on the callback's
+        // behalf.
+        suspended.store(0, Ordering::Relaxed);
+        unsafe { DisplayLinkRef::from_ptr(self.0).set_paused(YES) }
+    }
+}
 
-        let diff = Duration::from_secs_f64(t - start_os);
-        let instant = start_rust + diff;
-        (callback.f)(instant)
+/// Pauses `display_link` when e.g. the app enters the background, and resumes it once that's over
+/// if that's what paused it, and nothing else keeps it paused.
+unsafe fn suspend(
+    display_link: *mut CADisplayLink,
+    suspended: &AtomicU8,
+    reason: Reason,
+    active: bool,
+) {
+    let display_link = DisplayLinkRef::from_ptr(display_link);
+    let bit = reason as u8;
+    let reasons = suspended.load(Ordering::Relaxed);
+    if active {
+        if display_link.is_paused() == NO {
+            display_link.set_paused(YES);
+            suspended.store(bit, Ordering::Relaxed);
+        } else if reasons != 0 {
+            suspended.store(reasons | bit, Ordering::Relaxed);
+        }
+    } else if reasons & bit != 0 {
+        suspended.store(reasons & !bit, Ordering::Relaxed);
+        if reasons == bit {
+            display_link.set_paused(NO);
+        }
+    }
+}
+
+/// Releases the holder's reference to the `SharedCallback` once the `CADisplayLink` releases the
+/// holder, on invalidation.
+extern "C" fn dealloc_callback_holder(this: &Object, _: Sel) {
+    unsafe {
+        let callback: *const c_void = *this.get_ivar("_data");
+        drop(Arc::from_raw(callback as *const SharedCallback));
+        let () = msg_send![super(this, class!(NSObject)), dealloc];
     }
 }
 
 impl DisplayLink {
-    /// Creates a new iOS `DisplayLink` instance.
+    /// Creates a new iOS `DisplayLink` instance. This is also used on tvOS, on Mac Catalyst, and on
+    /// visionOS, where the link isn't tied to a `UIScreen`.
     ///
     /// iOS does _not_ require the callback to be `Send`.
-    pub fn new<F>(callback: F) -> Option<Self>
+    pub fn new<T, F>(callback: F) -> Option<Self>
     where
-        F: 'static + FnMut(TimePoint),
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>),
+    {
+        unsafe { Self::with_screen(None, None, &RunLoopMode::Common, callback) }
+    }
+
+    /// Creates a link like [`DisplayLink::new`], firing only in the run loop `mode`, e.g.
+    /// `RunLoopMode::Default` to yield to UIKit during gestures.
+    pub fn with_run_loop_mode<T, F>(mode: &RunLoopMode, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>),
+    {
+        unsafe { Self::with_screen(None, None, mode, callback) }
+    }
+
+    /// Creates a link synced to `screen`, or to the main screen if it's `None`, firing in `mode` of
+    /// `run_loop`, or of the current run loop if it's `None`.
+    unsafe fn with_screen<T, F>(
+        screen: Option<*mut Object>,
+        run_loop: Option<*mut Object>,
+        mode: &RunLoopMode,
+        mut callback: F,
+    ) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>),
     {
         static CALLBACK_CLASS_CREATOR: Once = Once::new();
         CALLBACK_CLASS_CREATOR.call_once(|| {
             let mut decl = ClassDecl::new("DisplayLinkCallbackHolder", class!(NSObject)).unwrap();
-            decl.add_ivar::<*mut c_void>("_data");
-            let callback = if is_ios10() {
-                run_callback_ios10::<F>
+            decl.add_ivar::<*const c_void>("_data");
+            // The simulator's `targetTimestamp` is too coarse to anchor on. visionOS versions
+            // start at 1 and Catalyst reports the macOS version, but both always have
+            // `targetTimestamp`.
+            let callback = if is_simulated() {
+                run_callback_pre_ios10
+            } else if cfg!(target_os = "visionos") || is_catalyst() || is_ios10() {
+                run_callback_ios10
             } else {
-                run_callback_pre_ios10::<F>
+                run_callback_pre_ios10
             };
-            unsafe {
-                decl.add_method(sel!(call:), callback);
-            }
+            decl.add_method(
+                sel!(call:),
+                callback as extern "C" fn(&Object, Sel, *mut Object),
+            );
+            decl.add_method(
+                sel!(dealloc),
+                dealloc_callback_holder as extern "C" fn(&Object, Sel),
+            );
             decl.register();
         });
 
-        let raw_callback;
-        let mut display_link = unsafe {
-            let callback = {
-                let dl_callback: *mut Object = msg_send![class!(DisplayLinkCallbackHolder), alloc];
-                let dl_callback: *mut Object = msg_send![dl_callback, init];
-
-                let callback = Callback {
-                    start_time: None,
-                    f: callback,
-                };
-
-                let dl_callback: &mut Object = &mut *dl_callback;
-                raw_callback = Box::into_raw(Box::new(callback)) as *mut _;
-                dl_callback.set_ivar::<*mut c_void>("_data", raw_callback);
-                dl_callback
-            };
-            let dl = RawDisplayLink::with_target_selector(callback, sel!(call:));
-            // let () = msg_send![callback, release]; // retained by displaylink
-            dl
-        };
-        unsafe {
-            display_link.set_paused(YES);
-            display_link.add_to_current();
-        }
+        let mut clock = Clock::default();
+        let observers = clock.observers();
+        let display = screen.map_or_else(DisplayId::main, DisplayId);
+        let f: Render = Box::new(move |t| callback(clock.frame(PlatformTimestamp(t), display)));
+        let shared = Arc::new(SharedCallback {
+            callback: Mutex::new(Some(f)),
+            rates:    Mutex::default(),
+            on_grant: Mutex::new(None),
+        });
 
-        unsafe fn drop_callback<F: 'static + FnMut(TimePoint)>(callback: *mut c_void) {
-            ptr::drop_in_place::<Callback<F>>(callback as _)
-        }
+        let holder: *mut Object = msg_send![class!(DisplayLinkCallbackHolder), alloc];
+        let holder: *mut Object = msg_send![holder, init];
+        (*holder).set_ivar::<*const c_void>(
+            "_data",
+            Arc::into_raw(Arc::clone(&shared)) as *const c_void,
+        );
+        let mut display_link = match screen {
+            Some(screen) => {
+                RawDisplayLink::with_screen_target_selector(screen, holder, sel!(call:))
+            }
+            None => RawDisplayLink::with_target_selector(holder, sel!(call:)),
+        };
+        // The display link retains the holder until it's invalidated.
+        let () = msg_send![holder, release];
+        display_link.set_paused(YES);
+        mode.with_name(|mode| match run_loop {
+            Some(run_loop) => display_link.add_to_run_loop_for_mode(run_loop, mode),
+            None => display_link.add_to_current_for_mode(mode),
+        });
 
         Some(DisplayLink {
             display_link,
-            raw_callback,
-            drop_callback: drop_callback::<F>,
+            callback: shared,
+            observers,
+            display,
+            thread: None,
+            lifecycle: None,
+            lock: None,
+            inactive: None,
+            power: None,
+            thermal: None,
+            suspended: Arc::new(AtomicU8::new(0)),
         })
     }
 
-    pub fn on_display<F>(_display_id: u32, callback: F) -> Option<Self>
+    /// Creates a link like [`DisplayLink::with_run_loop_mode`], added to `run_loop` rather than the
+    /// current run loop, e.g. the run loop of a render thread, so the callback is invoked on that
+    /// run loop's thread.
+    ///
+    /// Dropping the link invalidates it, removing it from `run_loop`, and waits for a callback
+    /// already running there to return, so it must not be dropped from within the callback.
+    ///
+    /// # Safety
+    ///
+    /// `run_loop` must be a valid `NSRunLoop *` that outlives the returned `DisplayLink`, or is
+    /// only released after the link is dropped.
+    pub unsafe fn on_run_loop<T, F>(
+        run_loop: *mut Object,
+        mode: &RunLoopMode,
+        callback: F,
+    ) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::with_screen(None, Some(run_loop), mode, callback)
+    }
+
+    /// Creates a link like [`DisplayLink::new`], running on a thread of its own that spins an
+    /// `NSRunLoop` for it, so heavy per-frame work doesn't hold up the main run loop.
+    ///
+    /// The thread stops when the link is dropped, which must not happen from within the callback.
+    pub fn on_dedicated_thread<T, F>(callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        let thread = RunLoopThread::spawn()?;
+        let mut link =
+            unsafe { Self::on_run_loop(thread.run_loop(), &RunLoopMode::Common, callback)? };
+        link.thread = Some(thread);
+        Some(link)
+    }
+
+    /// Creates a link synced to the screen `scene` is shown on, which pauses while the scene is in
+    /// the background. visionOS scenes have no screen, so the link isn't tied to one there.
+    ///
+    /// # Safety
+    ///
+    /// `scene` must be a valid `UIWindowScene *` that outlives the returned `DisplayLink`, and this
+    /// must be called on the main thread.
+    pub unsafe fn for_window_scene<T, F>(scene: *mut Object, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        let screen = if cfg!(target_os = "visionos") {
+            None
+        } else {
+            let screen: *mut Object = msg_send![scene, screen];
+            Some(screen).filter(|screen| !screen.is_null())
+        };
+        let mut link = Self::with_screen(screen, None, &RunLoopMode::Common, callback)?;
+        link.pause_in_scene_background(scene);
+        Some(link)
+    }
+
+    /// Creates a link synced to the `UIScreen` identified by `display_id`, e.g. an external display
+    /// or AirPlay screen from [`Display::all`], rather than the device's own screen.
+    ///
+    /// Returns `None` if the screen is no longer connected. `DisplayId::UNKNOWN`, and every id on
+    /// visionOS, gets a link that isn't tied to a screen.
+    pub fn on_display<T, F>(display_id: DisplayId, callback: F) -> Option<Self>
     where
-        F: 'static + FnMut(TimePoint) + Send,
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
     {
-        Self::new(callback)
+        Self::on_display_with_run_loop_mode(display_id, &RunLoopMode::Common, callback)
+    }
+
+    /// Creates a link like [`DisplayLink::on_display`], firing only in the run loop `mode`.
+    pub fn on_display_with_run_loop_mode<T, F>(
+        display_id: DisplayId,
+        mode: &RunLoopMode,
+        callback: F,
+    ) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        if cfg!(target_os = "visionos") || display_id == DisplayId::UNKNOWN {
+            return Self::with_run_loop_mode(mode, callback);
+        }
+        if !displays().iter().any(|display| display.id == display_id) {
+            return None;
+        }
+        unsafe { Self::with_screen(Some(display_id.0), None, mode, callback) }
+    }
+
+    /// `Backend::CoreAnimation` is the only backend on iOS.
+    pub fn with_backend<T, F>(backend: Backend, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>),
+    {
+        match backend {
+            Backend::CoreAnimation => Self::new(callback),
+            _ => None,
+        }
+    }
+
+    pub fn set_display(&mut self, _display_id: DisplayId) -> Result<(), SetDisplayError> {
+        Err(SetDisplayError::Unsupported)
+    }
+
+    pub fn display(&self) -> DisplayId {
+        self.display
+    }
+
+    pub fn on_refresh_rate_changed(&mut self, callback: RateCallback) {
+        self.observers.rate.set_callback(callback)
     }
 
-    pub fn set_current_display(&mut self, _display_id: u32) {
-        // nothing
+    pub fn on_clock_discontinuity(&mut self, callback: DiscontinuityCallback) {
+        self.observers.set_discontinuity_callback(callback)
+    }
+
+    pub fn register_waker(&mut self, waker: Waker) {
+        self.observers.set_waker(waker)
+    }
+
+    pub fn backend(&self) -> Backend {
+        Backend::CoreAnimation
     }
 
     pub fn is_paused(&self) -> bool {
         NO != unsafe { self.display_link.is_paused() }
     }
 
+    pub(crate) fn park(&self) -> Park {
+        let link = Parked(self.display_link.as_ptr());
+        let suspended = Arc::clone(&self.suspended);
+        Park::new(move |gate| {
+            if !gate.is_open() {
+                link.pause(&suspended)
+            }
+        })
+    }
+
+    /// Returns the `CADisplayLink *` backing the link. The link still owns it.
+    pub fn as_raw(&self) -> *mut c_void {
+        self.display_link.as_ptr().cast()
+    }
+
+    /// `CADisplayLink`s keep running until they're invalidated on drop.
+    pub fn is_stopped(&self) -> bool {
+        false
+    }
+
+    /// Pauses the link whenever the app enters the background, resuming it when the app returns
+    /// to the foreground if it was running.
+    pub fn pause_in_background(&mut self) {
+        self.lifecycle = Some(LifecycleObserver::for_app(
+            self.suspender(Reason::Background),
+        ));
+    }
+
+    /// Like [`DisplayLink::pause_in_background`], following `scene` rather than the whole app.
+    ///
+    /// # Safety
+    ///
+    /// `scene` must be a valid `UIScene *`.
+    pub unsafe fn pause_in_scene_background(&mut self, scene: *mut Object) {
+        self.lifecycle = Some(LifecycleObserver::for_scene(
+            scene,
+            self.suspender(Reason::Background),
+        ));
+    }
+
+    /// Pauses the link while the device is locked, resuming it once it's unlocked if it was
+    /// running, and calls `on_lock` on the main thread with whether it's locked whenever that
+    /// changes.
+    pub fn pause_while_locked(&mut self, mut on_lock: Box<dyn FnMut(bool) + Send>) {
+        let mut suspender = self.suspender(Reason::Locked);
+        self.lock = Some(LifecycleObserver::for_protected_data(Box::new(
+            move |locked| {
+                suspender(locked);
+                on_lock(locked)
+            },
+        )));
+    }
+
+    /// Asks for `fps` frames per second from the next frame, where 0 lets the system pick, which
+    /// is the display's maximum rate. iOS 10 and newer.
+    pub fn set_preferred_frames_per_second(&self, fps: u32) {
+        self.set_preferred_frame_rate_range(FrameRateRange::fixed(fps as f32))
+    }
+
+    /// Returns the preferred rate of the range the link asks for, or 0 if the system picks.
+    pub fn preferred_frames_per_second(&self) -> u32 {
+        self.preferred_frame_rate_range().preferred as u32
+    }
+
+    /// Asks for frame rates within `range` from the next frame. Before iOS 15, which added
+    /// `preferredFrameRateRange`, this asks for the preferred rate instead, or the maximum if
+    /// there's no preferred rate.
+    pub fn set_preferred_frame_rate_range(&self, range: FrameRateRange) {
+        let mut rates = self.callback.rates.lock().unwrap();
+        rates.preferred = range;
+        rates.changed = true;
+    }
+
+    /// Returns the range the link asks for, where all zeros lets the system pick.
+    pub fn preferred_frame_rate_range(&self) -> FrameRateRange {
+        self.callback.rates.lock().unwrap().preferred
+    }
+
+    /// Returns the frames per second the system granted the link, once it's delivered a frame.
+    pub fn granted_frames_per_second(&self) -> Option<f64> {
+        self.callback.rates.lock().unwrap().granted
+    }
+
+    /// Calls `callback` on the link's thread with the rate the system granted, on the first frame
+    /// after the rate the link asks for changes, and whenever the granted rate changes.
+    pub fn on_frame_rate_granted(&mut self, callback: GrantCallback) {
+        *self.callback.on_grant.lock().unwrap() = Some(callback);
+    }
+
+    /// Caps the link at `max_fps` frames per second while Low Power Mode is on, from the next
+    /// frame, and calls `on_change` with whether it's on, right away and whenever it's switched on
+    /// or off. `on_change` may be called from any thread.
+    pub fn limit_in_low_power_mode(
+        &mut self,
+        max_fps: u32,
+        mut on_change: Box<dyn FnMut(bool) + Send>,
+    ) {
+        let callback = Arc::clone(&self.callback);
+        self.power = Some(PowerObserver::new(Box::new(move |low_power| {
+            let mut rates = callback.rates.lock().unwrap();
+            rates.cap = if low_power { max_fps as f32 } else { 0.0 };
+            rates.changed = true;
+            drop(rates);
+            on_change(low_power)
+        })));
+    }
+
+    /// Calls `on_change` on the main thread with `true` when the app becomes inactive, and `false`
+    /// when it's active again.
+    pub fn on_active_changed(&mut self, on_change: Box<dyn FnMut(bool)>) {
+        self.inactive = Some(LifecycleObserver::for_active(on_change));
+    }
+
+    /// Calls `on_change` with the thermal state, right away and whenever it changes.
+    pub fn on_thermal_state_changed(&mut self, on_change: thermal::OnChange) {
+        self.thermal = Some(ThermalObserver::new(on_change));
+    }
+
+    /// Returns a callback pausing the link for `reason`, for a `LifecycleObserver`.
+    fn suspender(&self, reason: Reason) -> Box<dyn FnMut(bool)> {
+        let display_link = self.display_link.as_ptr();
+        let suspended = Arc::clone(&self.suspended);
+        Box::new(move |active| unsafe { suspend(display_link, &suspended, reason, active) })
+    }
+
     pub fn pause(&mut self) -> Result<(), PauseError> {
+        // Pausing by hand overrides resuming in the foreground.
+        self.suspended.store(0, Ordering::Relaxed);
         if self.is_paused() {
             Err(PauseError::AlreadyPaused)
         } else {
@@ -234,6 +991,7 @@ impl DisplayLink {
     }
 
     pub fn resume(&mut self) -> Result<(), ResumeError> {
+        self.suspended.store(0, Ordering::Relaxed);
         if !self.is_paused() {
             Err(ResumeError::AlreadyRunning)
         } else {
@@ -245,7 +1003,161 @@ impl DisplayLink {
     }
 }
 
-struct Callback<F: 'static + FnMut(TimePoint)> {
-    start_time: Option<(f64, TimePoint)>,
-    f: F,
+/// An update from a [`MetalDisplayLink`], telling a Metal renderer what to draw and when it will be
+/// shown.
+#[derive(Debug)]
+pub struct MetalUpdate<T = TimePoint> {
+    /// The frame, with `timestamp` being the update's `targetTimestamp`, i.e. the deadline for
+    /// finishing the frame.
+    pub frame:                  FrameTime<T>,
+    /// The update's `targetPresentationTimestamp`, i.e. when the drawable is expected to be shown.
+    pub presentation_timestamp: T,
+    /// The `CAMetalDrawable` to render into and present.
+    ///
+    /// The drawable is only retained for the duration of the callback.
+    pub drawable:               *mut Object,
+}
+
+struct MetalCallback<T, F> {
+    clock: Clock,
+    f:     F,
+    _t:    PhantomData<fn() -> T>,
+}
+
+unsafe fn render_metal<T, F>(context: *mut c_void, update: *mut Object)
+where
+    T: FrameTimestamp,
+    F: FnMut(MetalUpdate<T>),
+{
+    let callback = &mut *(context as *mut MetalCallback<T, F>);
+    let target: f64 = msg_send![update, targetTimestamp];
+    let presentation: f64 = msg_send![update, targetPresentationTimestamp];
+    let drawable: *mut Object = msg_send![update, drawable];
+    let frame = callback
+        .clock
+        .frame(PlatformTimestamp(target), DisplayId::main());
+    let presentation_timestamp = callback.clock.timestamp(PlatformTimestamp(presentation));
+    (callback.f)(MetalUpdate {
+        frame,
+        presentation_timestamp,
+        drawable,
+    })
+}
+
+extern "C" fn run_metal_callback(
+    this: &Object,
+    _: Sel,
+    _display_link: *mut Object,
+    update: *mut Object,
+) {
+    unsafe {
+        let context: *mut c_void = *this.get_ivar("_data");
+        let render: *mut c_void = *this.get_ivar("_render");
+        let render: unsafe fn(*mut c_void, *mut Object) = mem::transmute(render);
+        render(context, update)
+    }
+}
+
+/// A `DisplayLink` backed by `CAMetalDisplayLink`, which hands each update a drawable from a
+/// `CAMetalLayer` and paces them for the layer.
+///
+/// This is only available on iOS 17, tvOS 17, visionOS 1 and newer.
+#[derive(Debug)]
+pub struct MetalDisplayLink {
+    func:         Box<dyn Any>,
+    delegate:     *mut Object,
+    display_link: RawMetalDisplayLink,
+}
+
+impl Drop for MetalDisplayLink {
+    fn drop(&mut self) {
+        unsafe {
+            // The link doesn't retain its delegate, so it must stop calling it first.
+            self.display_link.invalidate();
+            let () = msg_send![self.delegate, release];
+        }
+    }
+}
+
+impl MetalDisplayLink {
+    /// Creates a new `MetalDisplayLink` updating `layer`, which must be a `CAMetalLayer`.
+    ///
+    /// The returned `MetalDisplayLink` will be in a paused state. Returns `None` if
+    /// `CAMetalDisplayLink` isn't available. Like [`DisplayLink`], the callback is invoked on the
+    /// current run loop and doesn't need to be `Send`.
+    ///
+    /// # Safety
+    ///
+    /// `layer` must be a valid `CAMetalLayer *`.
+    pub unsafe fn new<T, F>(layer: *mut Object, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(MetalUpdate<T>),
+    {
+        if !cametaldisplaylink::is_available() {
+            return None;
+        }
+
+        static DELEGATE_CLASS_CREATOR: Once = Once::new();
+        DELEGATE_CLASS_CREATOR.call_once(|| {
+            let mut decl = ClassDecl::new("DisplayLinkMetalDelegate", class!(NSObject)).unwrap();
+            decl.add_ivar::<*mut c_void>("_data");
+            decl.add_ivar::<*mut c_void>("_render");
+            decl.add_method(
+                sel!(metalDisplayLink:needsUpdate:),
+                run_metal_callback as extern "C" fn(&Object, Sel, *mut Object, *mut Object),
+            );
+            decl.register();
+        });
+
+        let func = Box::new(MetalCallback::<T, F> {
+            clock: Clock::default(),
+            f:     callback,
+            _t:    PhantomData,
+        });
+        let raw = Box::into_raw(func);
+        let func = Box::from_raw(raw);
+        let mut display_link = RawMetalDisplayLink::with_metal_layer(layer)?;
+        let delegate: *mut Object = msg_send![class!(DisplayLinkMetalDelegate), alloc];
+        let delegate: *mut Object = msg_send![delegate, init];
+        (*delegate).set_ivar::<*mut c_void>("_data", raw as *mut c_void);
+        (*delegate).set_ivar::<*mut c_void>(
+            "_render",
+            render_metal::<T, F> as unsafe fn(*mut c_void, *mut Object) as *mut c_void,
+        );
+        display_link.set_delegate(delegate);
+        display_link.set_paused(YES);
+        display_link.add_to_current();
+        Some(MetalDisplayLink {
+            func,
+            delegate,
+            display_link,
+        })
+    }
+
+    pub fn is_paused(&self) -> bool {
+        NO != unsafe { self.display_link.is_paused() }
+    }
+
+    pub fn pause(&mut self) -> Result<(), PauseError> {
+        if self.is_paused() {
+            Err(PauseError::AlreadyPaused)
+        } else {
+            unsafe {
+                self.display_link.set_paused(YES);
+            }
+            Ok(())
+        }
+    }
+
+    pub fn resume(&mut self) -> Result<(), ResumeError> {
+        if !self.is_paused() {
+            Err(ResumeError::AlreadyRunning)
+        } else {
+            unsafe {
+                self.display_link.set_paused(NO);
+            }
+            Ok(())
+        }
+    }
 }
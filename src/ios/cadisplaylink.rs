@@ -2,7 +2,7 @@
 
 use objc::{
     class, msg_send,
-    runtime::{Object, Sel, BOOL},
+    runtime::{Object, Sel, BOOL, NO},
     sel, sel_impl,
 };
 use objc_foundation::NSString;
@@ -11,10 +11,38 @@ use objc_foundation::NSString;
 #[link(name = "UIKit", kind = "framework")]
 extern "C" {
     #[allow(improper_ctypes)]
-    static NSRunLoopCommonModes: *mut NSString;
+    pub static NSRunLoopCommonModes: *mut NSString;
+    #[allow(improper_ctypes)]
+    pub static NSDefaultRunLoopMode: *mut NSString;
     pub fn CACurrentMediaTime() -> f64;
 }
 
+/// Apple docs: [CAFrameRateRange](https://developer.apple.com/documentation/quartzcore/caframeraterange?language=objc)
+///
+/// A range of all zeros lets the system pick, which is the display's maximum rate.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CAFrameRateRange {
+    pub minimum:   f32,
+    pub maximum:   f32,
+    pub preferred: f32,
+}
+
+impl CAFrameRateRange {
+    pub fn new(minimum: f32, maximum: f32, preferred: f32) -> Self {
+        CAFrameRateRange {
+            minimum,
+            maximum,
+            preferred,
+        }
+    }
+
+    /// The range that only allows `fps`, like setting `preferredFramesPerSecond`.
+    pub fn fixed(fps: f32) -> Self {
+        Self::new(fps, fps, fps)
+    }
+}
+
 pub enum CADisplayLink {}
 
 foreign_obj_type! {
@@ -26,13 +54,39 @@ foreign_obj_type! {
 
 impl DisplayLink {
     /// Apple docs: [displayLinkWithTarget:selector:](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1621228-displaylinkwithtarget?language=objc)
+    ///
+    /// # Safety
+    ///
+    /// `object` must respond to `selector`, taking the link, for as long as the link isn't
+    /// invalidated. The link retains `object` until then.
     pub unsafe fn with_target_selector(object: *mut Object, selector: Sel) -> Self {
         msg_send![class!(CADisplayLink), displayLinkWithTarget:object selector:selector]
     }
+
+    /// Apple docs: [displayLinkWithTarget:selector:](https://developer.apple.com/documentation/uikit/uiscreen/1617836-displaylinkwithtarget?language=objc)
+    ///
+    /// The link is synced to `screen` rather than the main screen.
+    ///
+    /// # Safety
+    ///
+    /// `screen` must be a valid `UIScreen *`, and `object` must respond to `selector`, taking the
+    /// link, for as long as the link isn't invalidated. The link retains `object` until then.
+    pub unsafe fn with_screen_target_selector(
+        screen: *mut Object,
+        object: *mut Object,
+        selector: Sel,
+    ) -> Self {
+        msg_send![screen, displayLinkWithTarget:object selector:selector]
+    }
 }
 
 impl DisplayLinkRef {
     /// Apple docs: [addToRunLoop:forMode:](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1621323-addtorunloop?language=objc)
+    ///
+    /// # Safety
+    ///
+    /// `run_loop` must be a valid `NSRunLoop *` and `mode` a valid `NSString *`, and the link's
+    /// target must be safe to call on `run_loop`'s thread.
     pub unsafe fn add_to_run_loop_for_mode(&mut self, run_loop: *mut Object, mode: *mut NSString) {
         msg_send![
             self,
@@ -42,16 +96,42 @@ impl DisplayLinkRef {
     }
 
     /// Calls `self.add_to_run_loop_for_mode([NSRunLoop currentRunLoop], NSRunLoopCommonModes)`
+    ///
+    /// # Safety
+    ///
+    /// The link's target must be safe to call on the current thread.
     pub unsafe fn add_to_current(&mut self) {
-        self.add_to_run_loop_for_mode(
-            msg_send![class!(NSRunLoop), currentRunLoop],
-            NSRunLoopCommonModes,
-        )
+        self.add_to_current_for_mode(NSRunLoopCommonModes)
+    }
+
+    /// Calls `self.add_to_run_loop_for_mode([NSRunLoop currentRunLoop], mode)`
+    ///
+    /// # Safety
+    ///
+    /// `mode` must be a valid `NSString *`, and the link's target must be safe to call on the
+    /// current thread.
+    pub unsafe fn add_to_current_for_mode(&mut self, mode: *mut NSString) {
+        self.add_to_run_loop_for_mode(msg_send![class!(NSRunLoop), currentRunLoop], mode)
+    }
+
+    /// Apple docs: [invalidate](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1621293-invalidate?language=objc)
+    ///
+    /// This is documented as being thread safe, and can be called more than once.
+    ///
+    /// # Safety
+    ///
+    /// This only messages the link, which is valid for as long as it's borrowed.
+    pub unsafe fn invalidate(&self) {
+        msg_send![self, invalidate]
     }
 
     /// Apple docs: [paused](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1621229-paused?language=objc)
     ///
     /// This is documented as being thread safe.
+    ///
+    /// # Safety
+    ///
+    /// This only messages the link, which is valid for as long as it's borrowed.
     pub unsafe fn set_paused(&self, paused: BOOL) {
         msg_send![self, setPaused: paused]
     }
@@ -59,9 +139,67 @@ impl DisplayLinkRef {
     /// Apple docs: [paused](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1621229-paused?language=objc)
     ///
     /// This is documented as being thread safe.
+    ///
+    /// # Safety
+    ///
+    /// This only messages the link, which is valid for as long as it's borrowed.
     pub unsafe fn is_paused(&self) -> BOOL {
         msg_send![self, isPaused]
     }
+
+    /// Apple docs: [preferredFramesPerSecond](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1648421-preferredframespersecond?language=objc)
+    ///
+    /// 0 lets the system pick, which is the display's maximum rate. iOS 10 and newer.
+    ///
+    /// # Safety
+    ///
+    /// The link must support `preferredFramesPerSecond`, see
+    /// [`DisplayLinkRef::supports_preferred_frames_per_second`].
+    pub unsafe fn set_preferred_frames_per_second(&self, fps: isize) {
+        msg_send![self, setPreferredFramesPerSecond: fps]
+    }
+
+    /// Apple docs: [preferredFramesPerSecond](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1648421-preferredframespersecond?language=objc)
+    ///
+    /// # Safety
+    ///
+    /// This only messages the link, which is valid for as long as it's borrowed.
+    pub unsafe fn preferred_frames_per_second(&self) -> isize {
+        msg_send![self, preferredFramesPerSecond]
+    }
+
+    /// Apple docs: [preferredFrameRateRange](https://developer.apple.com/documentation/quartzcore/cadisplaylink/3875343-preferredframeraterange?language=objc)
+    ///
+    /// iOS 15 and newer, see [`DisplayLinkRef::supports_frame_rate_ranges`].
+    ///
+    /// # Safety
+    ///
+    /// The link must support `preferredFrameRateRange`, see
+    /// [`DisplayLinkRef::supports_frame_rate_ranges`].
+    pub unsafe fn set_preferred_frame_rate_range(&self, range: CAFrameRateRange) {
+        msg_send![self, setPreferredFrameRateRange: range]
+    }
+
+    /// Returns `true` if the link has `preferredFramesPerSecond`, which is new in iOS 10.
+    ///
+    /// # Safety
+    ///
+    /// This only messages the link, which is valid for as long as it's borrowed.
+    pub unsafe fn supports_preferred_frames_per_second(&self) -> bool {
+        let responds: BOOL =
+            msg_send![self, respondsToSelector: sel!(setPreferredFramesPerSecond:)];
+        responds != NO
+    }
+
+    /// Returns `true` if the link has `preferredFrameRateRange`, which is new in iOS 15.
+    ///
+    /// # Safety
+    ///
+    /// This only messages the link, which is valid for as long as it's borrowed.
+    pub unsafe fn supports_frame_rate_ranges(&self) -> bool {
+        let responds: BOOL = msg_send![self, respondsToSelector: sel!(setPreferredFrameRateRange:)];
+        responds != NO
+    }
 }
 
 /// Apple docs: [invalidate](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1621293-invalidate?language=objc)
@@ -0,0 +1,123 @@
+//! Apple docs: [CAMetalDisplayLink](https://developer.apple.com/documentation/quartzcore/cametaldisplaylink?language=objc)
+
+use objc::{
+    class, msg_send,
+    runtime::{Class, Object, BOOL},
+    sel, sel_impl,
+};
+use objc_foundation::NSString;
+
+#[link(name = "Foundation", kind = "framework")]
+extern "C" {
+    #[allow(improper_ctypes)]
+    static NSRunLoopCommonModes: *mut NSString;
+}
+
+pub enum CAMetalDisplayLink {}
+
+foreign_obj_type! {
+    type CType = CAMetalDisplayLink;
+    fn drop = invalidate;
+    pub struct MetalDisplayLink;
+    pub struct MetalDisplayLinkRef;
+}
+
+/// Returns `true` if `CAMetalDisplayLink` is available, i.e. on iOS 17, tvOS 17, visionOS 1 and
+/// newer.
+pub fn is_available() -> bool {
+    Class::get("CAMetalDisplayLink").is_some()
+}
+
+impl MetalDisplayLink {
+    /// Apple docs: [initWithMetalLayer:](https://developer.apple.com/documentation/quartzcore/cametaldisplaylink/4172556-initwithmetallayer?language=objc)
+    ///
+    /// # Safety
+    ///
+    /// `layer` must be a valid `CAMetalLayer *`.
+    pub unsafe fn with_metal_layer(layer: *mut Object) -> Option<Self> {
+        let display_link: *mut CAMetalDisplayLink = msg_send![class!(CAMetalDisplayLink), alloc];
+        let display_link: *mut CAMetalDisplayLink =
+            msg_send![display_link, initWithMetalLayer: layer];
+        if display_link.is_null() {
+            None
+        } else {
+            Some(foreign_types::ForeignType::from_ptr(display_link))
+        }
+    }
+}
+
+impl MetalDisplayLinkRef {
+    /// Apple docs: [delegate](https://developer.apple.com/documentation/quartzcore/cametaldisplaylink/4172554-delegate?language=objc)
+    ///
+    /// The delegate is not retained.
+    ///
+    /// # Safety
+    ///
+    /// `delegate` must be a valid object conforming to `CAMetalDisplayLinkDelegate`, or null, and
+    /// must outlive the link, or be replaced or the link invalidated first.
+    pub unsafe fn set_delegate(&mut self, delegate: *mut Object) {
+        msg_send![self, setDelegate: delegate]
+    }
+
+    /// Apple docs: [addToRunLoop:forMode:](https://developer.apple.com/documentation/quartzcore/cametaldisplaylink/4172551-addtorunloop?language=objc)
+    ///
+    /// # Safety
+    ///
+    /// `run_loop` must be a valid `NSRunLoop *` and `mode` a valid `NSString *`, and the link's
+    /// delegate must be safe to call on `run_loop`'s thread.
+    pub unsafe fn add_to_run_loop_for_mode(&mut self, run_loop: *mut Object, mode: *mut NSString) {
+        msg_send![
+            self,
+            addToRunLoop: run_loop
+            forMode: mode
+        ]
+    }
+
+    /// Calls `self.add_to_run_loop_for_mode([NSRunLoop currentRunLoop], NSRunLoopCommonModes)`
+    ///
+    /// # Safety
+    ///
+    /// The link's delegate must be safe to call on the current thread.
+    pub unsafe fn add_to_current(&mut self) {
+        self.add_to_run_loop_for_mode(
+            msg_send![class!(NSRunLoop), currentRunLoop],
+            NSRunLoopCommonModes,
+        )
+    }
+
+    /// Apple docs: [paused](https://developer.apple.com/documentation/quartzcore/cametaldisplaylink/4172557-paused?language=objc)
+    ///
+    /// # Safety
+    ///
+    /// This only messages the link, which is valid for as long as it's borrowed.
+    pub unsafe fn set_paused(&self, paused: BOOL) {
+        msg_send![self, setPaused: paused]
+    }
+
+    /// Apple docs: [paused](https://developer.apple.com/documentation/quartzcore/cametaldisplaylink/4172557-paused?language=objc)
+    ///
+    /// # Safety
+    ///
+    /// This only messages the link, which is valid for as long as it's borrowed.
+    pub unsafe fn is_paused(&self) -> BOOL {
+        msg_send![self, isPaused]
+    }
+
+    /// Apple docs: [invalidate](https://developer.apple.com/documentation/quartzcore/cametaldisplaylink/4172555-invalidate?language=objc)
+    ///
+    /// The link stops calling its delegate, so the delegate can be released. This can be called
+    /// more than once.
+    ///
+    /// # Safety
+    ///
+    /// Like `addToRunLoop:forMode:`, this must be called on the thread of the link's run loop.
+    pub unsafe fn invalidate(&self) {
+        msg_send![self, invalidate]
+    }
+}
+
+/// Apple docs: [invalidate](https://developer.apple.com/documentation/quartzcore/cametaldisplaylink/4172555-invalidate?language=objc)
+unsafe fn invalidate(p: *mut CAMetalDisplayLink) {
+    let () = msg_send![p, invalidate];
+    let () = msg_send![p, release];
+}
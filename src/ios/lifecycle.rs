@@ -0,0 +1,174 @@
+//! Apple docs: [UIApplicationDidEnterBackgroundNotification](https://developer.apple.com/documentation/uikit/uiapplication/1622941-didenterbackgroundnotification?language=objc),
+//! [UISceneDidEnterBackgroundNotification](https://developer.apple.com/documentation/uikit/uiscene/3197912-didenterbackgroundnotification?language=objc),
+//! [UIApplicationWillResignActiveNotification](https://developer.apple.com/documentation/uikit/uiapplication/1622973-willresignactivenotification?language=objc),
+//! [UIApplicationProtectedDataWillBecomeUnavailable](https://developer.apple.com/documentation/uikit/uiapplication/1622926-protecteddatawillbecomeunavailab?language=objc)
+
+use objc::{
+    class,
+    declare::ClassDecl,
+    msg_send,
+    runtime::{Object, Sel},
+    sel, sel_impl,
+};
+use objc_foundation::NSString;
+use std::{ffi::c_void, ptr, sync::Once};
+
+#[link(name = "UIKit", kind = "framework")]
+extern "C" {
+    #[allow(improper_ctypes)]
+    static UIApplicationDidEnterBackgroundNotification: *mut NSString;
+    #[allow(improper_ctypes)]
+    static UIApplicationWillEnterForegroundNotification: *mut NSString;
+    #[allow(improper_ctypes)]
+    static UISceneDidEnterBackgroundNotification: *mut NSString;
+    #[allow(improper_ctypes)]
+    static UISceneWillEnterForegroundNotification: *mut NSString;
+    #[allow(improper_ctypes)]
+    static UIApplicationWillResignActiveNotification: *mut NSString;
+    #[allow(improper_ctypes)]
+    static UIApplicationDidBecomeActiveNotification: *mut NSString;
+    #[allow(improper_ctypes)]
+    static UIApplicationProtectedDataWillBecomeUnavailable: *mut NSString;
+    #[allow(improper_ctypes)]
+    static UIApplicationProtectedDataDidBecomeAvailable: *mut NSString;
+}
+
+type OnChange = Box<dyn FnMut(bool)>;
+
+unsafe fn notify(this: &Object, background: bool) {
+    let on_change: *mut c_void = *this.get_ivar("_data");
+    let on_change = &mut *(on_change as *mut OnChange);
+    on_change(background)
+}
+
+extern "C" fn did_enter_background(this: &Object, _: Sel, _notification: *mut Object) {
+    unsafe { notify(this, true) }
+}
+
+extern "C" fn will_enter_foreground(this: &Object, _: Sel, _notification: *mut Object) {
+    unsafe { notify(this, false) }
+}
+
+/// Calls back with `true` when the app, or one of its scenes, enters the background, and with
+/// `false` when it's about to return to the foreground. Also used for the app becoming inactive
+/// and active again, and the device locking and unlocking.
+///
+/// Notifications are posted on the main thread.
+#[derive(Debug)]
+pub(crate) struct LifecycleObserver {
+    observer:  *mut Object,
+    on_change: *mut OnChange,
+}
+
+impl LifecycleObserver {
+    pub fn for_app(on_change: OnChange) -> Self {
+        unsafe {
+            Self::new(
+                UIApplicationDidEnterBackgroundNotification,
+                UIApplicationWillEnterForegroundNotification,
+                ptr::null_mut(),
+                on_change,
+            )
+        }
+    }
+
+    /// Observes `scene` rather than the whole app, for scene-based apps with several windows.
+    ///
+    /// # Safety
+    ///
+    /// `scene` must be a valid `UIScene *`.
+    pub unsafe fn for_scene(scene: *mut Object, on_change: OnChange) -> Self {
+        Self::new(
+            UISceneDidEnterBackgroundNotification,
+            UISceneWillEnterForegroundNotification,
+            scene,
+            on_change,
+        )
+    }
+
+    /// Observes the app becoming inactive, e.g. while Control Center or the app switcher is over
+    /// it, and active again.
+    pub fn for_active(on_change: OnChange) -> Self {
+        unsafe {
+            Self::new(
+                UIApplicationWillResignActiveNotification,
+                UIApplicationDidBecomeActiveNotification,
+                ptr::null_mut(),
+                on_change,
+            )
+        }
+    }
+
+    /// Observes the device locking and unlocking, from the app's protected data becoming
+    /// unavailable and available again. Only posted if the device has a passcode.
+    pub fn for_protected_data(on_change: OnChange) -> Self {
+        unsafe {
+            Self::new(
+                UIApplicationProtectedDataWillBecomeUnavailable,
+                UIApplicationProtectedDataDidBecomeAvailable,
+                ptr::null_mut(),
+                on_change,
+            )
+        }
+    }
+
+    /// Observes the `background` and `foreground` notifications posted by `object`, or by anything
+    /// if it's null.
+    unsafe fn new(
+        background: *mut NSString,
+        foreground: *mut NSString,
+        object: *mut Object,
+        on_change: OnChange,
+    ) -> Self {
+        static OBSERVER_CLASS_CREATOR: Once = Once::new();
+        OBSERVER_CLASS_CREATOR.call_once(|| {
+            let mut decl =
+                ClassDecl::new("DisplayLinkLifecycleObserver", class!(NSObject)).unwrap();
+            decl.add_ivar::<*mut c_void>("_data");
+            decl.add_method(
+                sel!(didEnterBackground:),
+                did_enter_background as extern "C" fn(&Object, Sel, *mut Object),
+            );
+            decl.add_method(
+                sel!(willEnterForeground:),
+                will_enter_foreground as extern "C" fn(&Object, Sel, *mut Object),
+            );
+            decl.register();
+        });
+
+        let on_change = Box::into_raw(Box::new(on_change));
+        let observer: *mut Object = msg_send![class!(DisplayLinkLifecycleObserver), alloc];
+        let observer: *mut Object = msg_send![observer, init];
+        (*observer).set_ivar::<*mut c_void>("_data", on_change as *mut c_void);
+        let center: *mut Object = msg_send![class!(NSNotificationCenter), defaultCenter];
+        let () = msg_send![
+            center,
+            addObserver: observer
+            selector: sel!(didEnterBackground:)
+            name: background
+            object: object
+        ];
+        let () = msg_send![
+            center,
+            addObserver: observer
+            selector: sel!(willEnterForeground:)
+            name: foreground
+            object: object
+        ];
+        LifecycleObserver {
+            observer,
+            on_change,
+        }
+    }
+}
+
+impl Drop for LifecycleObserver {
+    fn drop(&mut self) {
+        unsafe {
+            let center: *mut Object = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center, removeObserver: self.observer];
+            let () = msg_send![self.observer, release];
+            drop(Box::from_raw(self.on_change));
+        }
+    }
+}
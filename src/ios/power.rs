@@ -0,0 +1,95 @@
+//! Apple docs: [NSProcessInfoPowerStateDidChangeNotification](https://developer.apple.com/documentation/foundation/nsprocessinfopowerstatedidchangenotification?language=objc)
+
+use objc::{
+    class,
+    declare::ClassDecl,
+    msg_send,
+    runtime::{Object, Sel, BOOL, NO},
+    sel, sel_impl,
+};
+use objc_foundation::NSString;
+use std::{ffi::c_void, ptr, sync::Once};
+
+#[link(name = "Foundation", kind = "framework")]
+extern "C" {
+    #[allow(improper_ctypes)]
+    static NSProcessInfoPowerStateDidChangeNotification: *mut NSString;
+}
+
+/// Apple docs: [lowPowerModeEnabled](https://developer.apple.com/documentation/foundation/nsprocessinfo/1617047-lowpowermodeenabled?language=objc)
+pub fn is_low_power_mode_enabled() -> bool {
+    unsafe {
+        let process_info: *mut Object = msg_send![class!(NSProcessInfo), processInfo];
+        let enabled: BOOL = msg_send![process_info, isLowPowerModeEnabled];
+        enabled != NO
+    }
+}
+
+type OnChange = Box<dyn FnMut(bool)>;
+
+extern "C" fn power_state_did_change(this: &Object, _: Sel, _notification: *mut Object) {
+    unsafe {
+        let on_change: *mut c_void = *this.get_ivar("_data");
+        let on_change = &mut *(on_change as *mut OnChange);
+        on_change(is_low_power_mode_enabled())
+    }
+}
+
+/// Calls back with whether Low Power Mode is on whenever it's switched on or off.
+///
+/// Notifications are posted on whichever thread the system posts them from, which needn't be the
+/// main thread.
+#[derive(Debug)]
+pub(crate) struct PowerObserver {
+    observer:  *mut Object,
+    on_change: *mut OnChange,
+}
+
+impl PowerObserver {
+    /// `on_change` is also called right away, with whether Low Power Mode is on now.
+    pub fn new(mut on_change: OnChange) -> Self {
+        static OBSERVER_CLASS_CREATOR: Once = Once::new();
+        OBSERVER_CLASS_CREATOR.call_once(|| {
+            let mut decl = ClassDecl::new("DisplayLinkPowerObserver", class!(NSObject)).unwrap();
+            decl.add_ivar::<*mut c_void>("_data");
+            unsafe {
+                decl.add_method(
+                    sel!(powerStateDidChange:),
+                    power_state_did_change as extern "C" fn(&Object, Sel, *mut Object),
+                );
+            }
+            decl.register();
+        });
+
+        on_change(is_low_power_mode_enabled());
+        unsafe {
+            let on_change = Box::into_raw(Box::new(on_change));
+            let observer: *mut Object = msg_send![class!(DisplayLinkPowerObserver), alloc];
+            let observer: *mut Object = msg_send![observer, init];
+            (*observer).set_ivar::<*mut c_void>("_data", on_change as *mut c_void);
+            let center: *mut Object = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![
+                center,
+                addObserver: observer
+                selector: sel!(powerStateDidChange:)
+                name: NSProcessInfoPowerStateDidChangeNotification
+                object: ptr::null_mut::<Object>()
+            ];
+            PowerObserver {
+                observer,
+                on_change,
+            }
+        }
+    }
+}
+
+impl Drop for PowerObserver {
+    fn drop(&mut self) {
+        unsafe {
+            let center: *mut Object = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center, removeObserver: self.observer];
+            let () = msg_send![self.observer, release];
+            drop(Box::from_raw(self.on_change));
+        }
+    }
+}
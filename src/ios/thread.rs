@@ -0,0 +1,85 @@
+//! A thread running a run loop of its own, for links delivering frames off the main thread.
+
+use objc::{class, msg_send, runtime::Object, sel, sel_impl};
+use std::{
+    ffi::c_void,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc, Arc,
+    },
+    thread::{self, JoinHandle},
+};
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    static kCFRunLoopDefaultMode: *const c_void;
+    fn CFRunLoopRunInMode(mode: *const c_void, seconds: f64, return_after_source: u8) -> i32;
+    fn CFRunLoopStop(run_loop: *mut c_void);
+}
+
+/// The run loops of a thread, sent back to the thread that spawned it.
+struct RunLoops {
+    ns: *mut Object,
+    cf: *mut c_void,
+}
+
+unsafe impl Send for RunLoops {}
+
+#[derive(Debug)]
+pub(crate) struct RunLoopThread {
+    run_loop:    *mut Object,
+    cf_run_loop: *mut c_void,
+    stopped:     Arc<AtomicBool>,
+    thread:      Option<JoinHandle<()>>,
+}
+
+impl RunLoopThread {
+    /// Spawns a thread running its run loop in the default mode until the `RunLoopThread` is
+    /// dropped.
+    pub fn spawn() -> Option<Self> {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let (sender, receiver) = mpsc::channel();
+        let thread = {
+            let stopped = Arc::clone(&stopped);
+            thread::Builder::new()
+                .name("display-link".to_owned())
+                .spawn(move || unsafe {
+                    let ns: *mut Object = msg_send![class!(NSRunLoop), currentRunLoop];
+                    // A run loop without any sources returns straight away, so a port keeps it
+                    // waiting for the display link until it's added.
+                    let port: *mut Object = msg_send![class!(NSMachPort), port];
+                    let () = msg_send![ns, addPort: port forMode: kCFRunLoopDefaultMode];
+                    let cf: *mut c_void = msg_send![ns, getCFRunLoop];
+                    let _ = sender.send(RunLoops { ns, cf });
+                    // `CFRunLoopStop` is lost if it comes before the run loop starts, so the flag
+                    // is checked in between runs.
+                    while !stopped.load(Ordering::Acquire) {
+                        CFRunLoopRunInMode(kCFRunLoopDefaultMode, 1.0, 0);
+                    }
+                })
+                .ok()?
+        };
+        let RunLoops { ns, cf } = receiver.recv().ok()?;
+        Some(RunLoopThread {
+            run_loop: ns,
+            cf_run_loop: cf,
+            stopped,
+            thread: Some(thread),
+        })
+    }
+
+    /// The thread's `NSRunLoop`, valid until the `RunLoopThread` is dropped.
+    pub fn run_loop(&self) -> *mut Object {
+        self.run_loop
+    }
+}
+
+impl Drop for RunLoopThread {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Release);
+        unsafe { CFRunLoopStop(self.cf_run_loop) }
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}
@@ -1,13 +1,199 @@
+#[cfg(any(
+    target_os = "ios",
+    target_os = "macos",
+    target_os = "tvos",
+    target_os = "visionos"
+))]
+macro_rules! foreign_obj_type {
+    {type CType = $raw_ident:ident;
+    fn drop = $drop_func:ident;
+    pub struct $owned_ident:ident;
+    pub struct $ref_ident:ident;
+    } => {
+        foreign_types::foreign_type! {
+            type CType = $raw_ident;
+            fn drop = $drop_func;
+            pub struct $owned_ident;
+            pub struct $ref_ident;
+        }
+
+        unsafe impl ::objc::Message for $raw_ident {
+        }
+        unsafe impl ::objc::Message for $ref_ident {
+        }
+
+        impl ::std::fmt::Debug for $ref_ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                unsafe {
+                    use ::objc_foundation::INSString;
+                    // TODO: might leak, not 100% sure...
+                    let string: &::objc_foundation::NSString = msg_send![self, debugDescription];
+                    write!(f, "{}", string.as_str())
+                }
+            }
+        }
+
+        impl ::std::fmt::Debug for $owned_ident {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                ::std::ops::Deref::deref(self).fmt(f)
+            }
+        }
+    };
+}
+
+pub mod android;
+mod backend;
+mod barrier;
+#[cfg(feature = "bevy")]
+mod bevy;
+mod broadcast;
+mod builder;
+mod callback;
+mod display;
+#[cfg(feature = "calloop")]
+mod event_source;
+#[cfg(feature = "fallback")]
+pub mod fallback;
+mod frame;
+mod future;
+mod governor;
+mod guard;
 pub mod ios;
+pub mod linux;
 pub mod macos;
+mod mailbox;
+mod pacing;
+mod proxy;
+#[cfg(feature = "winit")]
+mod redraw;
+mod run_loop;
+#[cfg(feature = "tokio")]
+mod runtime;
+mod scope;
+mod set;
+mod shared;
+mod sink;
+mod stream;
+mod thermal;
+mod token;
+pub mod wasm;
+mod watch;
+pub mod windows;
+#[cfg(any(
+    target_os = "android",
+    target_os = "linux",
+    target_os = "windows",
+    feature = "fallback"
+))]
+mod worker;
+
+#[cfg(not(any(native_backend, feature = "fallback")))]
+compile_error!("display-link has no backend for this target; enable the `fallback` feature");
 
+#[cfg(target_os = "android")]
+pub use crate::android::{DisplayId, PlatformTimestamp};
+#[cfg(not(native_backend))]
+pub use crate::fallback::{DisplayId, PlatformTimestamp};
+#[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+pub use crate::ios::{DisplayId, PlatformTimestamp};
+#[cfg(target_os = "linux")]
+pub use crate::linux::{DisplayId, PlatformTimestamp};
+#[cfg(target_os = "macos")]
+pub use crate::macos::{DisplayId, PlatformTimestamp};
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub use crate::wasm::{DisplayId, PlatformTimestamp};
+#[cfg(target_os = "windows")]
+pub use crate::windows::{DisplayId, PlatformTimestamp};
+pub use crate::{
+    backend::Backend,
+    barrier::VsyncBarrier,
+    broadcast::{Broadcast, Subscription},
+    builder::DisplayLinkBuilder,
+    callback::{ControlFlow, FrameHandler},
+    display::{Bounds, Display, DisplayEvent, DisplayWatcher, RefreshRateRange},
+    frame::{ClockDiscontinuity, Epoch, FrameTime, FrameTimestamp},
+    future::NextFrame,
+    governor::GovernorChange,
+    guard::PauseGuard,
+    mailbox::{FrameMailbox, Frames},
+    pacing::PacingPolicy,
+    proxy::LinkProxy,
+    run_loop::RunLoopMode,
+    scope::LinkScope,
+    set::DisplayLinkSet,
+    sink::FrameSink,
+    stream::{FrameStream, StreamFrame},
+    token::LinkToken,
+    watch::{Changed, FrameWatch},
+};
+
+#[cfg(feature = "bevy")]
+pub use crate::bevy::{VsyncFrame, VsyncPlugin};
+#[cfg(feature = "calloop")]
+pub use crate::event_source::VsyncSource;
+#[cfg(feature = "winit")]
+pub use crate::redraw::{RedrawDriver, VsyncEvent};
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "tvos",
+    target_os = "visionos"
+))]
+pub use crate::thermal::ThermalState;
+
+use crate::callback::{CallbackSlot, Gate, Slot};
+#[cfg(feature = "raw-window-handle")]
+use raw_window_handle::{HasDisplayHandle, HasWindowHandle};
+use std::{
+    any::Any,
+    rc::Rc,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
 use thiserror::Error;
 use time_point::TimePoint;
 
-#[cfg(target_os = "ios")]
+#[cfg(target_os = "android")]
+use crate::android::DisplayLink as PlatformDisplayLink;
+#[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
 use crate::ios::DisplayLink as PlatformDisplayLink;
+#[cfg(target_os = "linux")]
+use crate::linux::DisplayLink as PlatformDisplayLink;
 #[cfg(target_os = "macos")]
 use crate::macos::DisplayLink as PlatformDisplayLink;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+use crate::wasm::DisplayLink as PlatformDisplayLink;
+#[cfg(target_os = "windows")]
+use crate::windows::DisplayLink as PlatformDisplayLink;
+
+/// Whether a `DisplayLink` is delivering frames, returned by [`DisplayLink::state`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum LinkState {
+    /// The callback is invoked on each refresh.
+    Running,
+    /// The link is paused, and can be resumed.
+    Paused,
+    /// The link has stopped for good, and resuming it fails: its callback returned
+    /// `ControlFlow::Stop`, it was invalidated through a [`LinkToken`], or the platform stopped
+    /// it, e.g. because its display was disconnected.
+    Invalidated,
+}
+
+#[derive(Debug, Error)]
+pub enum CreateError {
+    #[error("no display is connected")]
+    Headless,
+    #[error("no display link is available")]
+    Unavailable,
+}
+
+#[derive(Debug, Error)]
+pub enum WaitError {
+    #[error("no display link is available")]
+    Unavailable,
+    #[error("timed out waiting for a refresh")]
+    Timeout,
+}
 
 #[derive(Debug, Error)]
 pub enum PauseError {
@@ -19,56 +205,1503 @@ pub enum PauseError {
 pub enum ResumeError {
     #[error("already running")]
     AlreadyRunning,
+    #[error("stopped for good")]
+    Stopped,
+}
+
+#[derive(Debug, Error)]
+pub enum SetDisplayError {
+    #[error("display isn't connected")]
+    NotConnected,
+    #[error("link can't be moved to another display")]
+    Unsupported,
+}
+
+/// The parts of a `DisplayLink` whose ownership was released with [`DisplayLink::into_raw`].
+#[cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "tvos",
+    target_os = "visionos"
+))]
+#[derive(Clone, Copy, Debug)]
+pub struct RawLinkParts {
+    /// The platform link, as returned by [`DisplayLink::as_raw`]. It stays valid until the
+    /// `DisplayLink` is reclaimed and dropped, unless the code it's handed to retains it.
+    pub link:   *mut std::ffi::c_void,
+    /// The `DisplayLink` itself, callback included, to reclaim with
+    /// [`DisplayLink::from_raw_parts`] once the platform link is no longer needed.
+    pub handle: *mut std::ffi::c_void,
 }
 
 /// `DisplayLink` is a timer object used to synchronize drawing with the refresh rate of the
 /// display.
 #[derive(Debug)]
-pub struct DisplayLink(PlatformDisplayLink);
+pub struct DisplayLink {
+    /// Locked by `pause` and `resume`, which take `&self` so the link can be shared.
+    inner:    Mutex<Inner>,
+    /// The `CallbackSlot<T>` the platform link calls into, for `set_callback`.
+    callback: Arc<dyn Slot>,
+    /// The state of links made with `with_state`, for `invalidate`.
+    state:    Option<Arc<Mutex<Box<dyn Any + Send>>>>,
+    gate:     Arc<Gate>,
+}
+
+#[derive(Debug)]
+enum Inner {
+    #[cfg(native_backend)]
+    Platform(PlatformDisplayLink),
+    #[cfg(feature = "fallback")]
+    Fallback(fallback::DisplayLink),
+}
+
+macro_rules! dispatch {
+    ($inner:expr, $link:ident => $body:expr) => {
+        match $inner {
+            #[cfg(native_backend)]
+            Inner::Platform($link) => $body,
+            #[cfg(feature = "fallback")]
+            Inner::Fallback($link) => $body,
+        }
+    };
+}
 
 impl DisplayLink {
-    /// Creates a new `DisplayLink` with a callback that will be invoked with the `TimePoint` the
-    /// screen will next refresh.
+    /// Creates a new `DisplayLink` with a callback that will be invoked with the `FrameTime` of
+    /// each refresh, which holds the `TimePoint` the screen will next refresh and the display that
+    /// produced it.
     ///
     /// The returned `DisplayLink` will be in a paused state. Returns `None` if a `DisplayLink`
-    /// could not be created.
+    /// could not be created. With the `fallback` feature enabled, a timer ticking at
+    /// [`fallback::DEFAULT_REFRESH_RATE`] is used instead when no real display link is available,
+    /// e.g. on a Mac with no display attached.
     ///
     /// ## Panic
     ///
     /// If the callback panics, the process will be aborted.
     pub fn new<F>(callback: F) -> Option<Self>
     where
-        F: 'static + FnMut(TimePoint) + Send,
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::with_timestamp::<TimePoint, F>(callback)
+    }
+
+    /// Creates a new `DisplayLink` like [`DisplayLink::new`], handing each frame to `handler`.
+    ///
+    /// Every handler goes through the same code, rather than a copy per closure type, so handlers
+    /// can be registered dynamically, e.g. by plugins.
+    pub fn with_handler(mut handler: Box<dyn FrameHandler>) -> Option<Self> {
+        Self::new(move |frame| handler.on_frame(frame))
+    }
+
+    /// Replaces the callback without recreating the `DisplayLink`, so the link keeps running, or
+    /// stays paused, without a hitch. If the old callback is being invoked, this waits for it to
+    /// return, and it's dropped before this returns.
+    ///
+    /// Links created with [`DisplayLink::with_timestamp`] and other constructors taking a
+    /// timestamp type must use [`DisplayLink::set_callback_with_timestamp`] instead, or this
+    /// returns `false`, leaving the callback in place. This must not be called from within the
+    /// callback itself.
+    pub fn set_callback<F, R>(&mut self, callback: F) -> bool
+    where
+        F: 'static + FnMut(FrameTime) -> R + Send,
+        R: Into<ControlFlow>,
+    {
+        self.set_callback_with_timestamp::<TimePoint, F, R>(callback)
+    }
+
+    /// Replaces the callback like [`DisplayLink::set_callback`], for links delivering timestamps
+    /// represented as `T`.
+    ///
+    /// Returns `false`, leaving the callback in place, if the link wasn't created with `T`.
+    pub fn set_callback_with_timestamp<T, F, R>(&mut self, callback: F) -> bool
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) -> R + Send,
+        R: Into<ControlFlow>,
+    {
+        match self.callback.as_any().downcast_ref::<CallbackSlot<T>>() {
+            Some(slot) => {
+                slot.replace(callback::boxed(callback));
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Creates a new `DisplayLink` that owns `state`, passing it to the callback along with the
+    /// `FrameTime` of each refresh, so render state doesn't need to be shared with the callback
+    /// behind an `Arc<Mutex<_>>`. [`DisplayLink::into_state`] gives it back.
+    ///
+    /// Like [`DisplayLink::new`], the returned `DisplayLink` will be in a paused state.
+    pub fn with_state<S, F>(state: S, mut callback: F) -> Option<Self>
+    where
+        S: 'static + Send,
+        F: 'static + FnMut(&mut S, FrameTime) + Send,
+    {
+        let state: Arc<Mutex<Box<dyn Any + Send>>> = Arc::new(Mutex::new(Box::new(state)));
+        let mut link = {
+            let state = Arc::clone(&state);
+            Self::new(move |frame| {
+                let mut state = state.lock().unwrap();
+                callback(state.downcast_mut().unwrap(), frame)
+            })?
+        };
+        link.state = Some(state);
+        Some(link)
+    }
+
+    /// Stops the `DisplayLink` and returns the state it was created with by
+    /// [`DisplayLink::with_state`].
+    ///
+    /// Returns `None` if the link wasn't created with a state of type `S`. See
+    /// [`DisplayLink::invalidate`].
+    pub fn into_state<S: 'static + Send>(self) -> Option<S> {
+        self.invalidate()?.downcast().ok().map(|state| *state)
+    }
+
+    /// Tears down the `DisplayLink`, returning the state it owns if it was created with
+    /// [`DisplayLink::with_state`].
+    ///
+    /// Unlike pausing, this is final: the callback won't be invoked again once this returns,
+    /// waiting for it to return if it's being invoked, and it's dropped along with everything it
+    /// captured, even where the OS keeps the underlying link alive for longer, like iOS does
+    /// while a `CADisplayLink` retains its target.
+    pub fn invalidate(self) -> Option<Box<dyn Any + Send>> {
+        self.gate.close();
+        self.callback.clear();
+        let DisplayLink { inner, state, .. } = self;
+        drop(inner);
+        let state = Arc::try_unwrap(state?).ok()?;
+        state.into_inner().ok()
+    }
+
+    /// Creates a new `DisplayLink` whose callback decides what the link does next by returning a
+    /// [`ControlFlow`], so it can pause or stop the link itself, e.g. once an animation finishes.
+    ///
+    /// A link paused by its callback is resumed with [`DisplayLink::resume`], while a stopped link
+    /// never invokes its callback again. Like [`DisplayLink::new`], the returned `DisplayLink` will
+    /// be in a paused state.
+    pub fn with_control_flow<F>(callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) -> ControlFlow + Send,
+    {
+        Self::wrap(callback, Self::new_inner::<TimePoint>)
+    }
+
+    /// Creates a new `DisplayLink` passing a [`LinkProxy`] to the callback along with each frame,
+    /// so per-frame logic can pause or stop the link, change its frame rate, or count frames,
+    /// without capturing the `DisplayLink` itself.
+    ///
+    /// Like [`DisplayLink::new`], the returned `DisplayLink` will be in a paused state.
+    pub fn with_proxy<F>(mut callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(&mut LinkProxy, FrameTime) + Send,
+    {
+        let gate = Arc::new(Gate::default());
+        let mut proxy = LinkProxy::new(Arc::clone(&gate));
+        Self::wrap_gated(
+            gate,
+            move |frame| proxy.frame(frame, &mut callback),
+            Self::new_inner::<TimePoint>,
+        )
+    }
+
+    /// Creates a new `DisplayLink` along with a [`LinkToken`], which invalidates the link once it
+    /// and all of its clones are dropped, for RAII even where the link itself can't be dropped
+    /// reliably, e.g. when it's owned by an object the OS retains.
+    ///
+    /// Like [`DisplayLink::new`], the returned `DisplayLink` will be in a paused state.
+    pub fn with_token<F>(callback: F) -> Option<(Self, LinkToken)>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        let link = Self::new(callback)?;
+        let token = LinkToken::new(Arc::clone(&link.gate), Arc::clone(&link.callback));
+        Some((link, token))
+    }
+
+    /// Returns a [`DisplayLinkBuilder`], for creating a `DisplayLink` with more options than the
+    /// constructors take.
+    pub fn builder() -> DisplayLinkBuilder {
+        DisplayLinkBuilder::new()
+    }
+
+    /// Creates a new `DisplayLink` delivering each frame into `sink`, so event loops without a
+    /// dedicated integration only need to implement [`FrameSink`] for their proxy type.
+    ///
+    /// Like [`DisplayLink::new`], the returned `DisplayLink` will be in a paused state.
+    pub fn with_sink<S: FrameSink>(sink: S) -> Option<Self> {
+        Self::new(move |frame| sink.on_frame(frame))
+    }
+
+    /// Returns a future resolving to the next frame, for code that only occasionally needs to wait
+    /// for a refresh, e.g. to take a screenshot once a frame has been rendered.
+    ///
+    /// A `DisplayLink` runs only until that frame arrives. The future resolves to `None` if a
+    /// `DisplayLink` could not be created, like [`DisplayLink::new`].
+    pub fn next_frame() -> NextFrame {
+        NextFrame::new()
+    }
+
+    /// Creates a running `DisplayLink` whose frames are delivered through a [`FrameStream`] rather
+    /// than a callback, so async code can `loop { let frame = frames.next_frame().await; }`, or
+    /// `while let Some(frame) = frames.next().await` with the `futures` feature.
+    ///
+    /// This works under any executor. Returns `None` if a `DisplayLink` could not be created,
+    /// like [`DisplayLink::new`].
+    pub fn frames() -> Option<FrameStream> {
+        FrameStream::new()
+    }
+
+    /// Creates a running `DisplayLink` sending each frame to the returned `Receiver`, so a thread
+    /// can wait for frames with a blocking `recv` instead of a callback.
+    ///
+    /// At most one frame is buffered, and newer frames are dropped while it's waiting to be
+    /// received. Use [`DisplayLink::mailbox`] to have them replace it instead. `recv` fails once
+    /// the `DisplayLink` is dropped. Like [`DisplayLink::wait_for_next_vsync`], the link calls back
+    /// on a thread of its own, so `recv` can be called on any thread.
+    pub fn channel() -> Option<(Self, std::sync::mpsc::Receiver<FrameTime>)> {
+        let (sender, receiver) = std::sync::mpsc::sync_channel(1);
+        let link = mailbox::off_run_loop(move |frame| {
+            let _ = sender.try_send(frame);
+        })?;
+        link.resume().ok()?;
+        Some((link, receiver))
+    }
+
+    /// Creates a running `DisplayLink` delivering frames to a [`FrameMailbox`], which only ever
+    /// holds the latest frame, so a slow consumer skips the frames it missed instead of building
+    /// up latency behind a queue.
+    pub fn mailbox() -> Option<(Self, FrameMailbox)> {
+        FrameMailbox::new()
+    }
+
+    /// Creates a running `DisplayLink` publishing its frames to a [`FrameWatch`], which reads the
+    /// latest frame with `borrow_latest` and waits for the next one with `changed().await`.
+    pub fn watch() -> Option<(Self, FrameWatch)> {
+        FrameWatch::new()
+    }
+
+    /// Creates a running `DisplayLink` delivering each frame to every subscriber of the returned
+    /// [`Broadcast`], so independent widgets can share one link instead of each creating their
+    /// own, which wastes threads and lets them drift apart.
+    pub fn broadcast() -> Option<Broadcast> {
+        Broadcast::new(None)
+    }
+
+    /// Returns the current thread's shared [`Broadcast`] for the default display, creating it if no
+    /// other handle to it is alive, so independent libraries can all sync to vsync without each
+    /// spinning up a link of their own.
+    ///
+    /// The link is dropped along with the last handle. Display links can't leave the thread that
+    /// created them on Apple platforms and the web, so the shared links are per-thread everywhere;
+    /// call this from the same thread, e.g. the main thread, to share one. Subscriptions can still
+    /// be handed to other threads.
+    pub fn shared() -> Option<Rc<Broadcast>> {
+        shared::get(None)
+    }
+
+    /// Returns the current thread's shared [`Broadcast`] for the display identified by
+    /// `display_id`, like [`DisplayLink::shared`].
+    pub fn shared_on_display(display_id: DisplayId) -> Option<Rc<Broadcast>> {
+        shared::get(Some(display_id))
+    }
+
+    /// Creates a running `DisplayLink` and returns a blocking iterator over its frames, so simple
+    /// tools, e.g. frame-by-frame capture scripts, read like ordinary loops:
+    /// `for frame in DisplayLink::iter()? { ... }`.
+    ///
+    /// Each call to `next` blocks until the next frame. Iteration ends when the display link stops
+    /// for good. Like [`DisplayLink::wait_for_next_vsync`], this can be called on any thread.
+    pub fn iter() -> Option<Frames> {
+        Frames::new()
+    }
+
+    /// Blocks the calling thread, calling `f` with each frame until it returns `false`: a minimal
+    /// main loop for command-line tools and demos.
+    ///
+    /// Returns `WaitError::Unavailable` if a `DisplayLink` could not be created, or stopped for
+    /// good before `f` returned `false`. Like [`DisplayLink::wait_for_next_vsync`], this can be
+    /// called on any thread.
+    pub fn run_while<F>(mut f: F) -> Result<(), WaitError>
+    where
+        F: FnMut(FrameTime) -> bool,
+    {
+        for frame in Self::iter().ok_or(WaitError::Unavailable)? {
+            if !f(frame) {
+                return Ok(());
+            }
+        }
+        Err(WaitError::Unavailable)
+    }
+
+    /// Calls `f` with a [`LinkScope`] spawning display links whose callbacks can borrow from the
+    /// stack, as they don't need to be `'static`.
+    ///
+    /// Every link spawned in the scope is stopped, and its callback has returned for the last
+    /// time, before `scope` returns, even if `f` panics.
+    pub fn scope<'env, F, R>(f: F) -> R
+    where
+        F: for<'scope> FnOnce(&'scope LinkScope<'env>) -> R,
+    {
+        f(&LinkScope::new())
+    }
+
+    /// Blocks the calling thread until the next refresh and returns its frame, for aligning a
+    /// single operation to vsync without keeping a `DisplayLink` running.
+    ///
+    /// Returns `None` if a `DisplayLink` could not be created. On iOS, where frames are usually
+    /// delivered on a run loop the caller would be blocking, the link runs on a dedicated thread,
+    /// so this can be called on any thread, including the main one.
+    pub fn wait_for_next_vsync() -> Option<FrameTime> {
+        let (_link, mailbox) = FrameMailbox::new()?;
+        mailbox.recv()
+    }
+
+    /// Blocks like [`DisplayLink::wait_for_next_vsync`], but for at most `timeout`, returning
+    /// `WaitError::Timeout` if no refresh arrives in time, e.g. while the display is asleep.
+    ///
+    /// Returns `WaitError::Unavailable` if a `DisplayLink` could not be created, or stopped before
+    /// the next refresh.
+    pub fn wait_for_next_vsync_timeout(
+        timeout: std::time::Duration,
+    ) -> Result<FrameTime, WaitError> {
+        let (_link, mailbox) = FrameMailbox::new().ok_or(WaitError::Unavailable)?;
+        mailbox.recv_timeout(timeout)
+    }
+
+    /// Creates a running `DisplayLink` publishing each frame to a tokio `watch` channel, which only
+    /// holds the latest frame, so a task that falls behind skips the frames it missed rather than
+    /// working through stale ones. Await `changed` on the receiver to wait for the next frame.
+    ///
+    /// Frames are sent from the display link's thread without blocking it, so this doesn't need
+    /// to be called from within a runtime. The channel closes when the `DisplayLink` is dropped.
+    #[cfg(feature = "tokio")]
+    pub fn tokio_frames() -> Option<(Self, tokio::sync::watch::Receiver<Option<FrameTime>>)> {
+        runtime::frames()
+    }
+
+    /// Spawns a tokio task that calls `f` and awaits the future it returns once per refresh, e.g.
+    /// to drive an async render loop. Refreshes that happen while the future is pending are
+    /// skipped.
+    ///
+    /// The task runs until the returned `DisplayLink` is dropped. This must be called from within
+    /// a tokio runtime, like `tokio::spawn`.
+    #[cfg(feature = "tokio")]
+    pub fn spawn_on_vsync<F, Fut>(f: F) -> Option<(Self, tokio::task::JoinHandle<()>)>
+    where
+        F: 'static + FnMut(FrameTime) -> Fut + Send,
+        Fut: std::future::Future<Output = ()> + Send,
+    {
+        runtime::spawn_on_vsync(f)
+    }
+
+    /// Creates a new `DisplayLink` like [`DisplayLink::new`], but never falls back to a timer, and
+    /// says why it couldn't be created.
+    ///
+    /// Returns `CreateError::Headless` if there's no display to sync to, which is detected on
+    /// macOS, e.g. on build servers and virtualized CI machines with no display attached. Use
+    /// [`DisplayLink::new`] with the `fallback` feature to degrade to a timer there instead.
+    pub fn try_new<F>(callback: F) -> Result<Self, CreateError>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        #[cfg(target_os = "macos")]
+        {
+            if macos::is_headless() {
+                return Err(CreateError::Headless);
+            }
+        }
+        #[cfg(native_backend)]
+        {
+            Self::wrap(callback, |slot| {
+                PlatformDisplayLink::new(slot.handler()).map(Inner::Platform)
+            })
+            .ok_or(CreateError::Unavailable)
+        }
+        #[cfg(not(native_backend))]
+        {
+            let _ = callback;
+            Err(CreateError::Unavailable)
+        }
+    }
+
+    /// Creates a new `DisplayLink` like [`DisplayLink::new`], but with the timestamps delivered to
+    /// the callback represented as `T`, e.g. `Instant` or the `Duration` since the first frame.
+    pub fn with_timestamp<T, F>(callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::wrap(callback, Self::new_inner)
+    }
+
+    fn new_inner<T: FrameTimestamp>(slot: &Arc<CallbackSlot<T>>) -> Option<Inner> {
+        #[cfg(all(native_backend, feature = "fallback"))]
+        {
+            // The platform link consumes its handler even when it fails, so the fallback gets its
+            // own.
+            PlatformDisplayLink::new(slot.handler())
+                .map(Inner::Platform)
+                .or_else(|| {
+                    fallback::DisplayLink::with_refresh_rate(
+                        fallback::DEFAULT_REFRESH_RATE,
+                        slot.handler(),
+                    )
+                    .map(Inner::Fallback)
+                })
+        }
+        #[cfg(all(native_backend, not(feature = "fallback")))]
+        {
+            PlatformDisplayLink::new(slot.handler()).map(Inner::Platform)
+        }
+        #[cfg(not(native_backend))]
+        {
+            fallback::DisplayLink::with_refresh_rate(fallback::DEFAULT_REFRESH_RATE, slot.handler())
+                .map(Inner::Fallback)
+        }
+    }
+
+    /// Creates a `DisplayLink` from the `Inner` link `create` makes around the handler of a new
+    /// `CallbackSlot` holding `callback`.
+    fn wrap<T, F, R, C>(callback: F, create: C) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) -> R + Send,
+        R: Into<ControlFlow>,
+        C: FnOnce(&Arc<CallbackSlot<T>>) -> Option<Inner>,
+    {
+        Self::wrap_gated(Arc::new(Gate::default()), callback, create)
+    }
+
+    /// Like [`DisplayLink::wrap`], with a `gate` the callback can already reach.
+    fn wrap_gated<T, F, R, C>(gate: Arc<Gate>, callback: F, create: C) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) -> R + Send,
+        R: Into<ControlFlow>,
+        C: FnOnce(&Arc<CallbackSlot<T>>) -> Option<Inner>,
+    {
+        let slot = Arc::new(CallbackSlot::new(
+            callback::boxed(callback),
+            Arc::clone(&gate),
+        ));
+        let inner = create(&slot)?;
+        gate.set_park(dispatch!(&inner, link => link.park()));
+        Some(DisplayLink {
+            inner: Mutex::new(inner),
+            callback: slot,
+            state: None,
+            gate,
+        })
+    }
+
+    /// Creates a new `DisplayLink` that ticks from a timer at `refresh_rate` Hz instead of
+    /// following a display, e.g. for headless environments or tests.
+    ///
+    /// Returns `None` if `refresh_rate` isn't a positive, finite number.
+    #[cfg(feature = "fallback")]
+    pub fn new_fallback<T, F>(refresh_rate: f64, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::wrap(callback, |slot| {
+            fallback::DisplayLink::with_refresh_rate(refresh_rate, slot.handler())
+                .map(Inner::Fallback)
+        })
+    }
+
+    /// Creates a new `DisplayLink` on the main display using `backend`, instead of letting the
+    /// platform pick one.
+    ///
+    /// Returns `None` if `backend` isn't available on this platform or failed to start.
+    /// `Backend::Timer` is available anywhere with the `fallback` feature enabled.
+    pub fn with_backend<T, F>(backend: Backend, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        #[cfg(feature = "fallback")]
+        {
+            if backend == Backend::Timer {
+                return Self::new_fallback(fallback::DEFAULT_REFRESH_RATE, callback);
+            }
+        }
+        #[cfg(native_backend)]
+        {
+            Self::wrap(callback, |slot| {
+                PlatformDisplayLink::with_backend(backend, slot.handler()).map(Inner::Platform)
+            })
+        }
+        #[cfg(not(native_backend))]
+        {
+            None
+        }
+    }
+
+    /// Creates a new `DisplayLink` paced by the DWM compositor instead of the display's vertical
+    /// blank, which better matches what windowed apps present against.
+    ///
+    /// Returns `None` if desktop composition is disabled.
+    #[cfg(target_os = "windows")]
+    pub fn new_dwm<F>(callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::wrap(callback, |slot| {
+            PlatformDisplayLink::new_dwm(slot.handler()).map(Inner::Platform)
+        })
+    }
+
+    /// Creates a new `DisplayLink` whose `CADisplayLink` only fires in the run loop `mode`, e.g.
+    /// `RunLoopMode::Default` so frames stop during scrolls rather than competing with UIKit's
+    /// tracking. [`DisplayLink::new`] fires in `RunLoopMode::Common`.
+    ///
+    /// The same as `DisplayLink::builder().run_loop_mode(mode).build(callback)`.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn with_run_loop_mode<F>(mode: RunLoopMode, callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::builder().run_loop_mode(mode).build(callback)
+    }
+
+    /// Creates a new `DisplayLink` whose `CADisplayLink` is added to `run_loop` in `mode`, rather
+    /// than to the current run loop, e.g. to deliver frames on a render thread running its own
+    /// `NSRunLoop`.
+    ///
+    /// Dropping the link removes it from `run_loop`, waiting for a callback already running there
+    /// to return, so it must not be dropped from within the callback.
+    ///
+    /// # Safety
+    ///
+    /// `run_loop` must be a valid `NSRunLoop *` that outlives the returned `DisplayLink`.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub unsafe fn on_run_loop<F>(
+        run_loop: *mut std::ffi::c_void,
+        mode: RunLoopMode,
+        callback: F,
+    ) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::wrap(callback, |slot| {
+            PlatformDisplayLink::on_run_loop(run_loop.cast(), &mode, slot.handler())
+                .map(Inner::Platform)
+        })
+    }
+
+    /// Creates a new `DisplayLink` whose `CADisplayLink` runs on a thread of its own, spinning an
+    /// `NSRunLoop` there, so the callback is invoked off the main thread and heavy per-frame work
+    /// doesn't jank the UI.
+    ///
+    /// The thread stops when the link is dropped, which must not happen from within the callback.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn on_dedicated_thread<F>(callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::wrap(callback, |slot| {
+            PlatformDisplayLink::on_dedicated_thread(slot.handler()).map(Inner::Platform)
+        })
+    }
+
+    /// Creates a new `DisplayLink` for the `UIWindowScene` `scene`, synced to the screen it's
+    /// shown on and paused while it's in the background, for scene-based apps with several
+    /// windows.
+    ///
+    /// # Safety
+    ///
+    /// `scene` must be a valid `UIWindowScene *` that outlives the returned `DisplayLink`, and this
+    /// must be called on the main thread.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub unsafe fn for_window_scene<F>(scene: *mut std::ffi::c_void, callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::wrap(callback, |slot| {
+            PlatformDisplayLink::for_window_scene(scene.cast(), slot.handler()).map(Inner::Platform)
+        })
+    }
+
+    /// Creates a new `DisplayLink` backed by a `CVDisplayLink` for all active displays, starting on
+    /// the main display.
+    ///
+    /// This is how windowed apps are expected to use `CVDisplayLink`: call
+    /// [`DisplayLink::set_current_display`] whenever the window moves to another screen, and the
+    /// same link follows it.
+    #[cfg(target_os = "macos")]
+    pub fn with_active_displays<F>(callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::wrap(callback, |slot| {
+            PlatformDisplayLink::with_active_displays(slot.handler()).map(Inner::Platform)
+        })
+    }
+
+    /// Creates a new `DisplayLink` whose callback is invoked on the main thread, so UI code can
+    /// use AppKit directly instead of hopping over from the `CVDisplayLink` thread.
+    ///
+    /// Frames are coalesced: if the main thread falls behind, only the latest frame is delivered.
+    /// One frame may still arrive after the link is paused.
+    #[cfg(target_os = "macos")]
+    pub fn on_main_thread<F>(callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
     {
-        PlatformDisplayLink::new(callback).map(DisplayLink)
+        Self::wrap(callback, |slot| {
+            PlatformDisplayLink::on_main_thread(slot.handler()).map(Inner::Platform)
+        })
     }
 
-    pub fn on_display<F>(display_id: u32, callback: F) -> Option<Self>
+    /// Creates a new `DisplayLink` that follows `window` as it moves between screens, so frames
+    /// always come from the display the window is on.
+    ///
+    /// # Safety
+    ///
+    /// `window` must be a valid `NSWindow *` that outlives the returned `DisplayLink`, and this
+    /// must be called on the main thread.
+    #[cfg(target_os = "macos")]
+    pub unsafe fn for_ns_window<F>(window: *mut std::ffi::c_void, callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::wrap(callback, |slot| {
+            PlatformDisplayLink::for_window(window.cast(), slot.handler()).map(Inner::Platform)
+        })
+    }
+
+    /// Creates a new `DisplayLink` synced to the display `screen` shows, for AppKit apps that think
+    /// in terms of `NSScreen`s rather than `CGDirectDisplayID`s. Use
+    /// [`macos::DisplayId::from_ns_screen_index`] with [`DisplayLink::on_display`] to pick a screen
+    /// by its index in `[NSScreen screens]` instead.
+    ///
+    /// Returns `None` if the screen's display can't be resolved.
+    ///
+    /// # Safety
+    ///
+    /// `screen` must be a valid `NSScreen *`.
+    #[cfg(target_os = "macos")]
+    pub unsafe fn for_ns_screen<F>(screen: *mut std::ffi::c_void, callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::on_display(DisplayId::from_ns_screen(screen)?, callback)
+    }
+
+    /// Creates a new `DisplayLink` synced to the display `window` is on, e.g. a `winit` or SDL
+    /// window.
+    ///
+    /// On macOS and X11 the link follows the window as it moves between displays, and on Wayland
+    /// it's driven by the window's surface. On Windows the link starts on the monitor the window
+    /// is on, so call [`DisplayLink::set_display`] when it moves. Other platforms and window
+    /// systems get a [`DisplayLink::new`].
+    ///
+    /// The `DisplayLink` must be dropped before the window is destroyed. On macOS, this must be
+    /// called on the main thread.
+    #[cfg(feature = "raw-window-handle")]
+    pub fn for_window<W, F>(window: &W, callback: F) -> Option<Self>
+    where
+        W: HasDisplayHandle + HasWindowHandle,
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        let display = window.display_handle().ok()?.as_raw();
+        let window = window.window_handle().ok()?.as_raw();
+        Self::wrap(callback, |slot| match (display, window) {
+            #[cfg(target_os = "macos")]
+            (_, raw_window_handle::RawWindowHandle::AppKit(handle)) => unsafe {
+                PlatformDisplayLink::for_view(handle.ns_view.as_ptr().cast(), slot.handler())
+                    .map(Inner::Platform)
+            },
+            #[cfg(target_os = "windows")]
+            (_, raw_window_handle::RawWindowHandle::Win32(handle)) => {
+                PlatformDisplayLink::for_hwnd(handle.hwnd.get() as _, slot.handler())
+                    .map(Inner::Platform)
+            }
+            #[cfg(all(target_os = "linux", feature = "x11"))]
+            (_, raw_window_handle::RawWindowHandle::Xlib(handle)) => {
+                PlatformDisplayLink::for_x11_window(handle.window as u32, slot.handler())
+                    .map(Inner::Platform)
+            }
+            #[cfg(all(target_os = "linux", feature = "x11"))]
+            (_, raw_window_handle::RawWindowHandle::Xcb(handle)) => {
+                PlatformDisplayLink::for_x11_window(handle.window.get(), slot.handler())
+                    .map(Inner::Platform)
+            }
+            // The window outlives the link, so its surface and display do too.
+            #[cfg(all(target_os = "linux", feature = "wayland"))]
+            (
+                raw_window_handle::RawDisplayHandle::Wayland(display),
+                raw_window_handle::RawWindowHandle::Wayland(handle),
+            ) => unsafe {
+                PlatformDisplayLink::for_wayland_surface(
+                    display.display.as_ptr(),
+                    handle.surface.as_ptr(),
+                    slot.handler(),
+                )
+                .map(Inner::Platform)
+            },
+            _ => Self::new_inner(slot),
+        })
+    }
+
+    /// Creates a new `DisplayLink` driven by frame callbacks on a Wayland surface, timed with the
+    /// `wp_presentation` protocol when the compositor supports it.
+    ///
+    /// Frame callbacks are only sent once the surface has been committed, so the callback fires
+    /// at most once per frame the client commits.
+    ///
+    /// `libwayland-client` is loaded at runtime, from the process that created `display`, so
+    /// building with the `wayland` feature doesn't need the Wayland development packages.
+    ///
+    /// # Safety
+    ///
+    /// `display` must be a valid `wl_display *` and `surface` a valid `wl_surface *` belonging to
+    /// it, both outliving the returned `DisplayLink`.
+    #[cfg(all(target_os = "linux", feature = "wayland"))]
+    pub unsafe fn for_wayland_surface<F>(
+        display: *mut std::ffi::c_void,
+        surface: *mut std::ffi::c_void,
+        callback: F,
+    ) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::wrap(callback, |slot| {
+            PlatformDisplayLink::for_wayland_surface(display, surface, slot.handler())
+                .map(Inner::Platform)
+        })
+    }
+
+    /// Creates a new `DisplayLink` driven by the X11 Present extension, following `window` as it
+    /// moves between CRTCs.
+    #[cfg(all(target_os = "linux", feature = "x11"))]
+    pub fn for_x11_window<F>(window: u32, callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::wrap(callback, |slot| {
+            PlatformDisplayLink::for_x11_window(window, slot.handler()).map(Inner::Platform)
+        })
+    }
+
+    /// Creates a new `DisplayLink` driven by vblank events on the CRTC with index `crtc` of the DRM
+    /// device at `device`, e.g. `/dev/dri/card0`.
+    ///
+    /// This works without a display server, e.g. for kiosk apps rendering directly with KMS.
+    #[cfg(all(target_os = "linux", feature = "drm"))]
+    pub fn on_drm_crtc<F>(device: &std::path::Path, crtc: u32, callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::wrap(callback, |slot| {
+            PlatformDisplayLink::on_drm_crtc(device, crtc, slot.handler()).map(Inner::Platform)
+        })
+    }
+
+    /// Creates a new `DisplayLink` synced to the display identified by `display_id`, e.g. a
+    /// `CGDirectDisplayID` on macOS, a `UIScreen` on iOS or an `HMONITOR` on Windows.
+    ///
+    /// On iOS, this is how to sync rendering to an external display or AirPlay screen, rather than
+    /// the device's own screen. [`Display::all`] lists the connected screens.
+    ///
+    /// Platforms where a link can't be tied to a display ignore `display_id`.
+    pub fn on_display<F>(display_id: DisplayId, callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::on_display_with_timestamp(display_id, callback)
+    }
+
+    pub(crate) fn on_display_with_timestamp<T, F>(
+        display_id: DisplayId,
+        callback: F,
+    ) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::wrap(callback, |slot| {
+            #[cfg(native_backend)]
+            {
+                PlatformDisplayLink::on_display(display_id, slot.handler()).map(Inner::Platform)
+            }
+            #[cfg(not(native_backend))]
+            {
+                fallback::DisplayLink::on_display(display_id, slot.handler()).map(Inner::Fallback)
+            }
+        })
+    }
+
+    /// Moves the `DisplayLink` to the display identified by `display_id`, ignoring failures. See
+    /// [`DisplayLink::set_display`].
+    pub fn set_current_display(&mut self, display_id: DisplayId) {
+        let _ = self.set_display(display_id);
+    }
+
+    /// Switches which display drives the callback, without recreating the `DisplayLink`. The
+    /// callback keeps its state and `Epoch`, and a paused link stays paused.
+    ///
+    /// Returns `SetDisplayError::Unsupported` for links that follow a window or surface, timer
+    /// fallbacks, and platforms where a link can't be tied to a display.
+    pub fn set_display(&mut self, display_id: DisplayId) -> Result<(), SetDisplayError> {
+        dispatch!(self.inner_mut(), link => link.set_display(display_id))
+    }
+
+    /// Syncs the `DisplayLink` to one of `displays`, e.g. the displays a window spans, chosen by
+    /// `policy` from their refresh rates, and returns the cadence it will deliver frames at, in
+    /// Hz.
+    ///
+    /// Call this again whenever the window moves, as the displays it spans change. Returns
+    /// `SetDisplayError::NotConnected` if none of `displays` are connected with a known refresh
+    /// rate, and `SetDisplayError::Unsupported` where [`DisplayLink::set_display`] is unsupported.
+    pub fn set_pacing(
+        &mut self,
+        displays: &[DisplayId],
+        policy: PacingPolicy,
+    ) -> Result<f64, SetDisplayError> {
+        let (display_id, refresh_rate) = policy
+            .choose(displays)
+            .ok_or(SetDisplayError::NotConnected)?;
+        self.set_display(display_id)?;
+        Ok(refresh_rate)
+    }
+
+    /// Moves the `DisplayLink` to the main display whenever the display driving it is
+    /// disconnected, rather than letting its callbacks stop, and calls `on_rebind` on the main
+    /// thread with the display it moved to.
+    ///
+    /// Returns `SetDisplayError::Unsupported` for links that follow a window, which AppKit moves
+    /// to another screen itself, and for timer fallbacks.
+    #[cfg(target_os = "macos")]
+    pub fn rebind_on_disconnect<F>(&mut self, on_rebind: F) -> Result<(), SetDisplayError>
+    where
+        F: 'static + FnMut(DisplayId) + Send,
+    {
+        match self.inner_mut() {
+            Inner::Platform(link) => link.rebind_on_disconnect(on_rebind),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => Err(SetDisplayError::Unsupported),
+        }
+    }
+
+    /// Moves the `DisplayLink` to the display an OpenGL context is drawing to, for OpenGL apps that
+    /// pair their `CVDisplayLink` with a `CGLContextObj`.
+    ///
+    /// Returns `SetDisplayError::Unsupported` unless the link is backed by a `CVDisplayLink` that
+    /// doesn't follow a window, e.g. one from [`DisplayLink::with_active_displays`].
+    ///
+    /// # Safety
+    ///
+    /// `cgl_context` must be a valid `CGLContextObj`, and `cgl_pixel_format` the valid
+    /// `CGLPixelFormatObj` it was created with.
+    #[cfg(target_os = "macos")]
+    pub unsafe fn set_display_from_gl_context(
+        &mut self,
+        cgl_context: *mut std::ffi::c_void,
+        cgl_pixel_format: *mut std::ffi::c_void,
+    ) -> Result<(), SetDisplayError> {
+        match self.inner_mut() {
+            Inner::Platform(link) => {
+                link.set_display_from_gl_context(cgl_context, cgl_pixel_format)
+            }
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => Err(SetDisplayError::Unsupported),
+        }
+    }
+
+    /// Pauses the `DisplayLink` whenever the app enters the background, as Apple requires, and
+    /// resumes it when the app returns to the foreground if it was running, observing
+    /// `UIApplicationDidEnterBackgroundNotification` and
+    /// `UIApplicationWillEnterForegroundNotification`.
+    ///
+    /// Pausing or resuming the link by hand while the app is in the background overrides this.
+    /// Timer fallbacks are left running.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn pause_in_background(&mut self) {
+        match self.inner_mut() {
+            Inner::Platform(link) => link.pause_in_background(),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => (),
+        }
+    }
+
+    /// Like [`DisplayLink::pause_in_background`], following the `UIScene` `scene` rather than the
+    /// whole app, for scene-based apps where each window goes to the background on its own, e.g.
+    /// on iPad.
+    ///
+    /// # Safety
+    ///
+    /// `scene` must be a valid `UIScene *` that outlives the `DisplayLink`.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub unsafe fn pause_in_scene_background(&mut self, scene: *mut std::ffi::c_void) {
+        match self.inner_mut() {
+            Inner::Platform(link) => link.pause_in_scene_background(scene.cast()),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => (),
+        }
+    }
+
+    /// Pauses the link while `window` is fully hidden, i.e. covered by other windows, minimized,
+    /// or on another space, so it doesn't render frames nobody sees. It's resumed once part of the
+    /// window is visible again, if it was running when it was hidden.
+    ///
+    /// Pausing or resuming the link by hand while the window is hidden overrides this. Timer
+    /// fallbacks are left running.
+    ///
+    /// # Safety
+    ///
+    /// `window` must be a valid `NSWindow *` that outlives the `DisplayLink`, and this must be
+    /// called on the main thread.
+    #[cfg(target_os = "macos")]
+    pub unsafe fn pause_while_occluded(&mut self, window: *mut std::ffi::c_void) {
+        match self.inner_mut() {
+            Inner::Platform(link) => link.pause_while_occluded(window.cast()),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => (),
+        }
+    }
+
+    /// Pauses the link while the displays are asleep, and resumes it once they wake up, if it was
+    /// running when they went to sleep.
+    ///
+    /// `on_wake` is called on the main thread whenever the displays wake up, after the link is
+    /// resumed, so the app can render a fresh frame right away rather than showing whatever was on
+    /// screen before. Replaces any previous `on_wake`. Pausing or resuming the link by hand while
+    /// the displays are asleep overrides this. Timer fallbacks are left running, and `on_wake`
+    /// isn't called for them.
+    #[cfg(target_os = "macos")]
+    pub fn pause_while_displays_sleep<F>(&mut self, on_wake: F)
+    where
+        F: 'static + FnMut() + Send,
+    {
+        match self.inner_mut() {
+            Inner::Platform(link) => link.pause_while_displays_sleep(Box::new(on_wake)),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => (),
+        }
+    }
+
+    /// Asks the system for `fps` frames per second, from the next frame, e.g. 30 or 60 on a 120Hz
+    /// device for content that doesn't need more, to save power. 0 lets the system pick, which is
+    /// the display's maximum rate.
+    ///
+    /// The system rounds `fps` to a rate the display supports. This asks for a
+    /// [`ios::FrameRateRange`] of just `fps` on iOS 15 and newer, and uses `CADisplayLink`'s
+    /// `preferredFramesPerSecond` on iOS 10 to 14. Earlier versions and timer fallbacks ignore it.
+    ///
+    /// Like [`DisplayLink::set_divisor`], this takes `&self`, so a running link can switch rates
+    /// without being recreated, e.g. between 30 in menus and 120 in gameplay.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn set_preferred_frames_per_second(&self, fps: u32) {
+        match &*self.inner() {
+            Inner::Platform(link) => link.set_preferred_frames_per_second(fps),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => (),
+        }
+    }
+
+    /// Returns the preferred rate the link asks for, or 0 if the system picks.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn preferred_frames_per_second(&self) -> u32 {
+        match &*self.inner() {
+            Inner::Platform(link) => link.preferred_frames_per_second(),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => 0,
+        }
+    }
+
+    /// Asks the system for frame rates within `range`, from the next frame, so ProMotion displays
+    /// can adapt the rate to the content, e.g. between 80 and 120 frames per second for a game.
+    /// This replaces the rate set with [`DisplayLink::set_preferred_frames_per_second`], which
+    /// asks for a range of just that rate.
+    ///
+    /// `CAFrameRateRange` needs iOS 15. Before that, the link asks for `range.preferred` with
+    /// `preferredFramesPerSecond` instead, or for `range.maximum` if there's no preferred rate.
+    /// iPhones only go above 60 frames per second for apps that set
+    /// `CADisableMinimumFrameDurationOnPhone` in their `Info.plist`. Timer fallbacks ignore it.
+    /// Like [`DisplayLink::set_preferred_frames_per_second`], this can be called on a running link.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn set_preferred_frame_rate_range(&self, range: ios::FrameRateRange) {
+        match &*self.inner() {
+            Inner::Platform(link) => link.set_preferred_frame_rate_range(range),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => (),
+        }
+    }
+
+    /// Returns the range the link asks for, where a range of all zeros lets the system pick.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn preferred_frame_rate_range(&self) -> ios::FrameRateRange {
+        match &*self.inner() {
+            Inner::Platform(link) => link.preferred_frame_rate_range(),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => ios::FrameRateRange::default(),
+        }
+    }
+
+    /// Returns the frames per second the system granted the link, which needn't be the rate it
+    /// asked for, e.g. while Low Power Mode is on, or if the display doesn't support that rate.
+    ///
+    /// The rate is measured from the time between the current frame and the next one the system
+    /// scheduled, so it's `None` until the link has delivered a frame. It's also `None` in the
+    /// simulator, before iOS 10, and for timer fallbacks.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn granted_frames_per_second(&self) -> Option<f64> {
+        match &*self.inner() {
+            Inner::Platform(link) => link.granted_frames_per_second(),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => None,
+        }
+    }
+
+    /// Calls `callback` with the rate the link asked for and the rate the system granted, see
+    /// [`DisplayLink::granted_frames_per_second`], so animations can be timed to the rate frames
+    /// actually arrive at. Replaces any previous callback.
+    ///
+    /// `callback` is called on the thread the `DisplayLink` callback runs on, just before the first
+    /// frame after the requested rate changes, and whenever the granted rate changes, e.g. as
+    /// ProMotion ramps down in Low Power Mode. It isn't called for timer fallbacks.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn on_frame_rate_granted<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(ios::FrameRateGrant) + Send,
+    {
+        match self.inner_mut() {
+            Inner::Platform(link) => link.on_frame_rate_granted(Box::new(callback)),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => (),
+        }
+    }
+
+    /// Caps the link at `max_fps` frames per second while Low Power Mode is on, and lifts the cap
+    /// once it's off, so the app renders less often when the user asked to save battery.
+    ///
+    /// `on_change` is called with whether Low Power Mode is on, right away and whenever it's
+    /// switched on or off, so the app can degrade gracefully in other ways too, e.g. by skipping
+    /// expensive effects. It may be called from any thread. The cap applies from the next frame.
+    /// [`ios::is_low_power_mode_enabled`] returns the current state. The cap never raises a lower
+    /// rate set with [`DisplayLink::set_preferred_frames_per_second`]. Timer fallbacks aren't
+    /// capped, and `on_change` isn't called for them.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn limit_in_low_power_mode<F>(&mut self, max_fps: u32, on_change: F)
+    where
+        F: 'static + FnMut(bool) + Send,
+    {
+        match self.inner_mut() {
+            Inner::Platform(link) => link.limit_in_low_power_mode(max_fps, Box::new(on_change)),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => (),
+        }
+    }
+
+    /// Halves the rate frames are delivered at while the Mac is on battery power, by skipping
+    /// every other frame, and delivers every frame again once it's back on AC power.
+    ///
+    /// [`macos::is_on_battery`] returns the current power source. When the link is also
+    /// throttled, e.g. with [`DisplayLink::set_divisor`], the larger divisor wins. Timer fallbacks
+    /// aren't throttled.
+    #[cfg(target_os = "macos")]
+    pub fn throttle_on_battery(&mut self) {
+        let gate = Arc::clone(&self.gate);
+        match self.inner_mut() {
+            Inner::Platform(link) => link.on_power_source_changed(Box::new(move |on_battery| {
+                let divisor = if on_battery { 2 } else { 1 };
+                gate.throttle.set(callback::Policy::Battery, divisor)
+            })),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => (),
+        }
+    }
+
+    /// Steps the rate frames are delivered at down while the device is under thermal pressure, so
+    /// long-running renders don't fight the system's efforts to cool it: only every other frame is
+    /// delivered while the [`ThermalState`] is `Serious`, and every fourth while it's `Critical`.
+    ///
+    /// `on_change` is called with the thermal state, right away and whenever it changes, so the
+    /// app can shed other work too. It may be called from any thread. The rate changes from the
+    /// next frame. Timer fallbacks aren't throttled, and `on_change` isn't called for them.
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "tvos",
+        target_os = "visionos"
+    ))]
+    pub fn throttle_under_thermal_pressure<F>(&mut self, mut on_change: F)
     where
-        F: 'static + FnMut(TimePoint) + Send,
+        F: 'static + FnMut(ThermalState) + Send,
     {
-        PlatformDisplayLink::on_display(display_id, callback).map(DisplayLink)
+        let gate = Arc::clone(&self.gate);
+        match self.inner_mut() {
+            Inner::Platform(link) => link.on_thermal_state_changed(Box::new(move |state| {
+                gate.throttle
+                    .set(callback::Policy::Thermal, state.divisor());
+                on_change(state)
+            })),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => (),
+        }
+    }
+
+    /// Delivers only every `divisor`th frame while the app is inactive but still on screen, e.g.
+    /// while Control Center or the app switcher is over it, and every frame again once it's
+    /// active.
+    ///
+    /// Unlike [`DisplayLink::pause_in_background`], the app keeps getting frames at a reduced rate,
+    /// e.g. to keep a video or an animation moving behind Control Center. Both can be used
+    /// together, as apps become inactive on their way to the background. Timer fallbacks aren't
+    /// throttled.
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    pub fn throttle_while_inactive(&mut self, divisor: u32) {
+        let gate = Arc::clone(&self.gate);
+        match self.inner_mut() {
+            Inner::Platform(link) => link.on_active_changed(Box::new(move |inactive| {
+                let divisor = if inactive { divisor } else { 1 };
+                gate.throttle.set(callback::Policy::Inactive, divisor)
+            })),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => (),
+        }
     }
 
-    pub fn set_current_display(&mut self, display_id: u32) {
-        self.0.set_current_display(display_id)
+    /// Pauses the link while the screen is locked, and resumes it once it's unlocked, if it was
+    /// running when it was locked.
+    ///
+    /// `on_lock` is called on the main thread with `true` when the screen is locked and `false`
+    /// when it's unlocked, so the app knows why frames stopped. Replaces any previous `on_lock`.
+    /// On iOS, this follows the app's protected data, which is only locked away if the device has
+    /// a passcode. Pausing or resuming the link by hand while the screen is locked overrides this.
+    /// Timer fallbacks are left running, and `on_lock` isn't called for them.
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "tvos",
+        target_os = "visionos"
+    ))]
+    pub fn pause_while_locked<F>(&mut self, on_lock: F)
+    where
+        F: 'static + FnMut(bool) + Send,
+    {
+        match self.inner_mut() {
+            Inner::Platform(link) => link.pause_while_locked(Box::new(on_lock)),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => (),
+        }
     }
 
-    /// Returns `true` if the `DisplayLink` is currently paused.
+    /// Returns the platform link backing the `DisplayLink`, for use with other CoreVideo or
+    /// QuartzCore bindings in the same app: a `CVDisplayLinkRef` or `CADisplayLink *` on macOS,
+    /// depending on [`DisplayLink::backend`], and a `CADisplayLink *` elsewhere.
+    ///
+    /// The `DisplayLink` still owns it, so it must not be released or invalidated, nor have its
+    /// output callback or target replaced. Returns null for timer fallbacks.
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "tvos",
+        target_os = "visionos"
+    ))]
+    pub fn as_raw(&self) -> *mut std::ffi::c_void {
+        match &*self.inner() {
+            Inner::Platform(link) => link.as_raw(),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => std::ptr::null_mut(),
+        }
+    }
+
+    /// Releases ownership of the `DisplayLink`, callback included, so the platform link can be
+    /// handed to Objective-C code that manages it from then on. The link keeps invoking the
+    /// callback until it's paused or invalidated there.
+    ///
+    /// Nothing is freed until the parts are passed back to [`DisplayLink::from_raw_parts`], and
+    /// the reclaimed `DisplayLink` is dropped, which also releases the platform link.
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "tvos",
+        target_os = "visionos"
+    ))]
+    pub fn into_raw(self) -> RawLinkParts {
+        RawLinkParts {
+            link:   self.as_raw(),
+            handle: Box::into_raw(Box::new(self)).cast(),
+        }
+    }
+
+    /// Reclaims a `DisplayLink` released with [`DisplayLink::into_raw`].
+    ///
+    /// # Safety
+    ///
+    /// `parts` must come from `into_raw`, and be reclaimed only once, on the thread that released
+    /// them.
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "ios",
+        target_os = "tvos",
+        target_os = "visionos"
+    ))]
+    pub unsafe fn from_raw_parts(parts: RawLinkParts) -> Self {
+        *Box::from_raw(parts.handle.cast::<Self>())
+    }
+
+    /// Adopts an existing `CVDisplayLinkRef`, e.g. one created by other CoreVideo bindings,
+    /// retaining it and replacing its output callback with `callback`. The link is stopped until
+    /// it's resumed.
+    ///
+    /// There's no equivalent for `CADisplayLink`s, whose target is fixed when they're created.
+    ///
+    /// # Safety
+    ///
+    /// `display_link` must be a valid `CVDisplayLinkRef`, which nothing else starts, stops or sets
+    /// the output callback of while the returned `DisplayLink` is alive.
+    #[cfg(target_os = "macos")]
+    pub unsafe fn from_raw<F>(display_link: *mut std::ffi::c_void, callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        Self::wrap(callback, |slot| {
+            PlatformDisplayLink::from_raw(display_link.cast(), slot.handler()).map(Inner::Platform)
+        })
+    }
+
+    /// Translates a `CVTimeStamp` from one time base to another, e.g. a video time into a host
+    /// time. `flags` is a combination of the `CV_TIME_STAMP_*` constants in
+    /// [`macos::cvdisplaylink`] selecting which fields of the output should be filled in.
+    ///
+    /// Returns `None` if the `DisplayLink` is paused, is a timer fallback, or the translation
+    /// failed.
+    #[cfg(target_os = "macos")]
+    pub fn translate_time(
+        &self,
+        in_time: &macos::cvdisplaylink::CVTimeStamp,
+        flags: u64,
+    ) -> Option<macos::cvdisplaylink::CVTimeStamp> {
+        match &*self.inner() {
+            Inner::Platform(link) => link.translate_time(in_time, flags),
+            #[cfg(feature = "fallback")]
+            Inner::Fallback(_) => None,
+        }
+    }
+
+    /// Returns the display the `DisplayLink` is currently synced to, e.g. so code holding a link
+    /// can check it's still on the expected screen. On macOS, this is the `CVDisplayLink`'s
+    /// current `CGDirectDisplayID`.
+    ///
+    /// Returns `DisplayId::UNKNOWN` if it isn't known, e.g. for timer fallbacks, on the web, and
+    /// for Linux links that follow a window or surface until they've delivered a frame.
+    pub fn display(&self) -> DisplayId {
+        dispatch!(&*self.inner(), link => link.display())
+    }
+
+    /// Calls `callback` with the rate frames are delivered at, in Hz, once it's been measured and
+    /// whenever it changes after that, e.g. as a ProMotion display ramps or an external monitor
+    /// switches between 60 and 120 Hz. Replaces any previous callback.
+    ///
+    /// The rate is measured from the time between frames, and a new rate is only reported once it
+    /// has held for a few frames, so dropped frames aren't mistaken for rate changes. `callback` is
+    /// called on the thread the `DisplayLink` callback runs on, just before the frame at the new
+    /// rate is delivered.
+    pub fn on_refresh_rate_changed<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(f64) + Send,
+    {
+        dispatch!(self.inner_mut(), link => link.on_refresh_rate_changed(Box::new(callback)))
+    }
+
+    /// Calls `callback` whenever the `Instant`s the `DisplayLink` delivers are re-anchored, because
+    /// the platform clock and `Instant` disagreed about the time between two frames, e.g. across
+    /// system sleep on some configurations. Replaces any previous callback.
+    ///
+    /// `Instant`s delivered after a discontinuity don't line up with those delivered before it, so
+    /// animation code should reset its timing rather than measure across it. Timestamps measured
+    /// from the first frame aren't re-anchored. `callback` is called on the thread the
+    /// `DisplayLink` callback runs on, just before the first frame after the discontinuity is
+    /// delivered, and never on `wasm32-unknown-unknown`, which has no `Instant`.
+    pub fn on_clock_discontinuity<F>(&mut self, callback: F)
+    where
+        F: 'static + FnMut(ClockDiscontinuity) + Send,
+    {
+        dispatch!(self.inner_mut(), link => link.on_clock_discontinuity(Box::new(callback)))
+    }
+
+    /// Wakes `waker` on each refresh, so custom executors and reactor loops can be woken by the
+    /// `DisplayLink` without it knowing about any particular async runtime. Replaces any
+    /// previously registered waker.
+    ///
+    /// The waker stays registered, and is woken with `wake_by_ref` just before the callback runs,
+    /// on the thread the callback runs on.
+    pub fn register_waker(&mut self, waker: std::task::Waker) {
+        dispatch!(self.inner_mut(), link => link.register_waker(waker))
+    }
+
+    /// Returns the mechanism driving this `DisplayLink`.
+    pub fn backend(&self) -> Backend {
+        dispatch!(&*self.inner(), link => link.backend())
+    }
+
+    /// Returns `true` if the `DisplayLink` is currently paused, or has stopped for good. Use
+    /// [`DisplayLink::state`] to tell the two apart.
     pub fn is_paused(&self) -> bool {
-        self.0.is_paused()
+        self.state() != LinkState::Running
+    }
+
+    /// Returns whether the `DisplayLink` is running, paused, or has stopped for good.
+    pub fn state(&self) -> LinkState {
+        let inner = self.inner();
+        if self.gate.is_stopped() || dispatch!(&*inner, link => link.is_stopped()) {
+            LinkState::Invalidated
+        } else if !self.gate.is_open() || dispatch!(&*inner, link => link.is_paused()) {
+            LinkState::Paused
+        } else {
+            LinkState::Running
+        }
     }
 
     /// Pauses the `DisplayLink`.
     ///
     /// A paused `DisplayLink` will not invoke it's callback. On iOS, it is necessary to pause the
     /// `DisplayLink` in response to events like backgrounding.
-    pub fn pause(&mut self) -> Result<(), PauseError> {
-        self.0.pause()
+    ///
+    /// This takes `&self`, so a link shared with e.g. an event handler can be paused from either
+    /// place, but it must not be called from within the callback: use [`ControlFlow`] or a
+    /// [`LinkProxy`] for that. On Apple platforms, where a `DisplayLink` can't leave the thread
+    /// that created it, share it in an `Rc` rather than an `Arc`.
+    pub fn pause(&self) -> Result<(), PauseError> {
+        let mut inner = self.inner();
+        let result = dispatch!(&mut *inner, link => link.pause());
+        // A link its callback paused or stopped may not be parked yet, so it's paused for real
+        // above.
+        if self.gate.release() || self.gate.is_stopped() {
+            Err(PauseError::AlreadyPaused)
+        } else {
+            if result.is_ok() {
+                self.gate.paused();
+            }
+            result
+        }
     }
 
     /// Resumes the `DisplayLink`.
-    pub fn resume(&mut self) -> Result<(), ResumeError> {
-        self.0.resume()
+    ///
+    /// Returns `ResumeError::Stopped` if the link has stopped for good, e.g. because the callback
+    /// returned `ControlFlow::Stop`; see [`LinkState::Invalidated`]. Like [`DisplayLink::pause`],
+    /// this takes `&self`.
+    pub fn resume(&self) -> Result<(), ResumeError> {
+        let mut inner = self.inner();
+        if self.gate.is_stopped() || dispatch!(&*inner, link => link.is_stopped()) {
+            return Err(ResumeError::Stopped);
+        }
+        let held = self.gate.release();
+        let result = match dispatch!(&mut *inner, link => link.resume()) {
+            Err(ResumeError::AlreadyRunning) if held => Ok(()),
+            result => result,
+        };
+        if result.is_ok() {
+            self.gate.resumed();
+        }
+        result
+    }
+
+    /// Returns the total time the `DisplayLink` has spent paused, whether by
+    /// [`DisplayLink::pause`] or by its callback, so animation clocks can leave it out. The time
+    /// before the link was first resumed isn't counted, nor are pauses the platform makes on its
+    /// own, e.g. while the app is in the background.
+    ///
+    /// The first frame after each resume also carries the length of that pause, as
+    /// [`FrameTime::paused_for`].
+    pub fn time_paused(&self) -> Duration {
+        self.gate.time_paused()
+    }
+
+    /// Invokes the callback only on every `divisor`th refresh, from the next frame, e.g. 2 for 60
+    /// frames per second on a 120 Hz display. 0 and 1 both deliver every frame. The divisor can be
+    /// changed while the link is running.
+    ///
+    /// Frames are skipped on the way to the callback, so this works the same on every backend,
+    /// including those without a rate setting of their own, like `CVDisplayLink`. The
+    /// [`FrameTime::delta`] of each frame delivered spans the refreshes skipped before it. When the
+    /// link is also throttled, e.g. by [`DisplayLink::throttle_under_thermal_pressure`], the
+    /// larger divisor wins.
+    pub fn set_divisor(&self, divisor: u32) {
+        self.gate.throttle.set(callback::Policy::Divisor, divisor)
+    }
+
+    /// Returns the divisor set with [`DisplayLink::set_divisor`], which is 1 by default.
+    pub fn divisor(&self) -> u32 {
+        self.gate.throttle.get(callback::Policy::Divisor)
+    }
+
+    /// Invokes the callback at about `fps` frames per second, from the next frame, for rates that
+    /// don't divide the refresh rate, e.g. 48 on a 120 Hz display. 0 removes the target, which is
+    /// the default. The target can be changed while the link is running.
+    ///
+    /// Frames stay in sync with the display: each frame is delivered on the refresh closest to
+    /// when it's due, so on a 120 Hz display, 48 frames per second alternate between every 2nd
+    /// and every 3rd refresh. The time between refreshes is measured as they arrive, so the pace
+    /// holds when the display's refresh rate changes. Targets at or above the refresh rate
+    /// deliver every frame, and combined with [`DisplayLink::set_divisor`] or throttling, frames
+    /// arrive no faster than either allows. The [`FrameTime::delta`] of each frame delivered spans
+    /// the refreshes skipped before it.
+    pub fn set_target_frame_rate(&self, fps: u32) {
+        self.gate.throttle.set_target(fps)
+    }
+
+    /// Returns the frame rate set with [`DisplayLink::set_target_frame_rate`], or 0 if there's no
+    /// target.
+    pub fn target_frame_rate(&self) -> u32 {
+        self.gate.throttle.target()
+    }
+
+    /// Measures how long the callback takes, and steps the frame rate down when it can't keep up,
+    /// so the app renders at a steady lower rate rather than missing frames all the time, and
+    /// back up once it can. Replaces any previous governor.
+    ///
+    /// The governor skips refreshes like [`DisplayLink::set_divisor`], up to every 4th refresh. It
+    /// steps down as soon as the callback takes longer than the time between frames on average
+    /// over 30 frames, but only steps back up once the callback would have kept up at the faster
+    /// rate, with room to spare, for several such windows in a row, so the rate doesn't flap.
+    ///
+    /// `on_change` is called with the new rate whenever it changes, on the thread the callback
+    /// runs on, just after the frame that made the governor step.
+    pub fn govern_frame_rate<F>(&self, on_change: F)
+    where
+        F: 'static + FnMut(GovernorChange) + Send,
+    {
+        self.gate.govern(Box::new(on_change))
+    }
+
+    /// Stops the governor started with [`DisplayLink::govern_frame_rate`], undoing any steps it
+    /// took.
+    pub fn stop_governing_frame_rate(&self) {
+        self.gate.stop_governing()
+    }
+
+    /// Pauses or resumes the `DisplayLink`, returning whether that changed its state, so callers
+    /// reacting to state changes, e.g. app lifecycle events, don't have to handle
+    /// `PauseError::AlreadyPaused` or `ResumeError::AlreadyRunning`.
+    pub fn set_paused(&self, paused: bool) -> bool {
+        if paused {
+            self.pause().is_ok()
+        } else {
+            self.resume().is_ok()
+        }
+    }
+
+    /// Pauses the `DisplayLink` until the returned guard is dropped, so temporary suspensions,
+    /// e.g. while a modal dialog is up, can't leave the link paused for good on an early return or
+    /// panic.
+    pub fn pause_guard(&self) -> PauseGuard<'_> {
+        PauseGuard::new(self)
+    }
+
+    fn inner(&self) -> MutexGuard<'_, Inner> {
+        self.inner.lock().unwrap()
+    }
+
+    fn inner_mut(&mut self) -> &mut Inner {
+        self.inner.get_mut().unwrap()
     }
 }
@@ -0,0 +1,309 @@
+#![cfg(target_os = "linux")]
+
+#[cfg(feature = "drm")]
+pub mod drm;
+#[cfg(feature = "wayland")]
+pub mod wayland;
+#[cfg(feature = "x11")]
+pub mod x11;
+
+use crate::{
+    callback::Park,
+    frame::{DiscontinuityCallback, Epoch, RateCallback},
+    worker::Worker,
+    Backend, Display, FrameTime, FrameTimestamp, PauseError, ResumeError, SetDisplayError,
+};
+#[cfg(feature = "drm")]
+use std::path::Path;
+use std::task::Waker;
+use time_point::TimePoint;
+
+/// Identifies a display. What the id refers to depends on the backend: for Wayland, it's the
+/// protocol id of the `wl_output`, for X11 it's the RandR CRTC, and for DRM it's the index of the
+/// CRTC.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DisplayId(pub u32);
+
+impl DisplayId {
+    /// The id reported when the display driving a link is unknown, e.g. for the timer fallback.
+    pub const UNKNOWN: DisplayId = DisplayId(u32::MAX);
+}
+
+/// A `CLOCK_MONOTONIC` time, in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PlatformTimestamp(pub u64);
+
+impl PlatformTimestamp {
+    pub(crate) fn now() -> Self {
+        let mut ts = libc::timespec {
+            tv_sec:  0,
+            tv_nsec: 0,
+        };
+        unsafe {
+            libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut ts);
+        }
+        PlatformTimestamp(ts.tv_sec as u64 * 1_000_000_000 + ts.tv_nsec as u64)
+    }
+
+    pub(crate) fn seconds_since(&self, earlier: Self) -> f64 {
+        (self.0 as f64 - earlier.0 as f64) / 1e9
+    }
+
+    pub(crate) fn to_time_point(self, epoch: &Epoch) -> TimePoint {
+        TimePoint::from_std_instant(epoch.to_instant(self))
+    }
+}
+
+/// Lists the CRTCs that `DisplayLink::on_display` would sync to: from RandR in an X session, or
+/// from `/dev/dri/card0` otherwise. Wayland doesn't expose outputs without a connection of the
+/// app's own, so those aren't listed.
+pub(crate) fn displays() -> Vec<Display> {
+    #[cfg(feature = "x11")]
+    {
+        if std::env::var_os("DISPLAY").is_some() {
+            return x11::displays().unwrap_or_default();
+        }
+    }
+    #[cfg(feature = "drm")]
+    {
+        return drm::displays(Path::new("/dev/dri/card0")).unwrap_or_default();
+    }
+    #[allow(unreachable_code)]
+    Vec::new()
+}
+
+/// How `set_display` moves the link to another display.
+#[derive(Debug)]
+enum Retarget {
+    /// The link follows a surface or window, so the display can't be chosen.
+    None,
+    #[cfg(feature = "x11")]
+    X11(x11::Retarget),
+    #[cfg(feature = "drm")]
+    Drm(drm::Retarget),
+}
+
+/// A `DisplayLink` driven from a dedicated thread by one of the Linux backends.
+#[derive(Debug)]
+pub struct DisplayLink {
+    worker:   Worker,
+    retarget: Retarget,
+    backend:  Backend,
+}
+
+impl DisplayLink {
+    #[cfg(feature = "x11")]
+    fn new_x11<T, F>(mut present: x11::Present, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        let retarget = present.retarget().map_or(Retarget::None, Retarget::X11);
+        let worker = Worker::spawn_link(move || present.wait(), callback)?;
+        Some(DisplayLink {
+            worker,
+            retarget,
+            backend: Backend::X11,
+        })
+    }
+
+    #[cfg(feature = "drm")]
+    fn new_drm<T, F>(mut vblank: drm::Vblank, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        let retarget = Retarget::Drm(vblank.retarget());
+        let worker = Worker::spawn_link(move || vblank.wait(), callback)?;
+        Some(DisplayLink {
+            worker,
+            retarget,
+            backend: Backend::Drm,
+        })
+    }
+
+    /// Creates a new Linux `DisplayLink` instance, using the X11 Present extension on the
+    /// primary CRTC, or vblank events from `/dev/dri/card0` outside of an X session.
+    ///
+    /// Wayland links need a surface, so use [`DisplayLink::for_wayland_surface`] for those.
+    pub fn new<T, F>(callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::on_crtc(None, callback)
+    }
+
+    /// `display_id` is the RandR CRTC to sync to, or the CRTC index outside of an X session.
+    pub fn on_display<T, F>(display_id: DisplayId, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::on_crtc(Some(display_id.0), callback)
+    }
+
+    #[allow(unused_variables)]
+    fn on_crtc<T, F>(crtc: Option<u32>, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        #[cfg(feature = "x11")]
+        {
+            if std::env::var_os("DISPLAY").is_some() {
+                return Self::new_x11(x11::Present::on_crtc(crtc)?, callback);
+            }
+        }
+        #[cfg(feature = "drm")]
+        {
+            let vblank = drm::Vblank::open(Path::new("/dev/dri/card0"), crtc.unwrap_or(0))?;
+            return Self::new_drm(vblank, callback);
+        }
+        #[allow(unreachable_code)]
+        None
+    }
+
+    /// Creates a `DisplayLink` on the primary CRTC using `backend`, which must be `Backend::X11`
+    /// or `Backend::Drm`, with the corresponding feature enabled.
+    ///
+    /// Wayland links need a surface, so `Backend::Wayland` always returns `None` here.
+    #[allow(unused_variables)]
+    pub fn with_backend<T, F>(backend: Backend, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        match backend {
+            #[cfg(feature = "x11")]
+            Backend::X11 => Self::new_x11(x11::Present::on_crtc(None)?, callback),
+            #[cfg(feature = "drm")]
+            Backend::Drm => {
+                Self::new_drm(drm::Vblank::open(Path::new("/dev/dri/card0"), 0)?, callback)
+            }
+            _ => None,
+        }
+    }
+
+    /// Creates a `DisplayLink` driven by `DRM_IOCTL_WAIT_VBLANK` on the CRTC with index `crtc`
+    /// of the DRM device at `device`, e.g. `/dev/dri/card0`.
+    ///
+    /// This works without a display server, e.g. for apps rendering directly with KMS.
+    #[cfg(feature = "drm")]
+    pub fn on_drm_crtc<T, F>(device: &Path, crtc: u32, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::new_drm(drm::Vblank::open(device, crtc)?, callback)
+    }
+
+    /// Creates a `DisplayLink` driven by the X11 Present extension, following `window` as it
+    /// moves between CRTCs.
+    #[cfg(feature = "x11")]
+    pub fn for_x11_window<T, F>(window: u32, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::new_x11(x11::Present::for_window(window)?, callback)
+    }
+
+    /// Creates a `DisplayLink` driven by `wl_surface.frame` callbacks on `surface`, with
+    /// presentation times from `wp_presentation` when the compositor supports it.
+    ///
+    /// Frame callbacks are only sent once the surface has been committed, so the callback fires
+    /// at most once per frame the client commits.
+    ///
+    /// # Safety
+    ///
+    /// `display` must be a valid `wl_display *` and `surface` a valid `wl_surface *` belonging to
+    /// it, both outliving the returned `DisplayLink`.
+    #[cfg(feature = "wayland")]
+    pub unsafe fn for_wayland_surface<T, F>(
+        display: *mut std::ffi::c_void,
+        surface: *mut std::ffi::c_void,
+        callback: F,
+    ) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        let mut frames = wayland::Frames::new(display, surface)?;
+        let worker = Worker::spawn_link(move || frames.wait(), callback)?;
+        Some(DisplayLink {
+            worker,
+            retarget: Retarget::None,
+            backend: Backend::Wayland,
+        })
+    }
+
+    /// Links that follow a Wayland surface or an X11 window can't be moved, as the surface or
+    /// window decides which display drives the link.
+    #[allow(unused_variables)]
+    pub fn set_display(&mut self, display_id: DisplayId) -> Result<(), SetDisplayError> {
+        match &self.retarget {
+            Retarget::None => Err(SetDisplayError::Unsupported),
+            #[cfg(feature = "x11")]
+            Retarget::X11(retarget) => retarget.set_crtc(display_id.0),
+            #[cfg(feature = "drm")]
+            Retarget::Drm(retarget) => retarget.set_pipe(display_id.0),
+        }
+    }
+
+    /// X11 and Wayland links report the display of the last frame delivered, as the window or
+    /// surface they follow can move.
+    pub fn display(&self) -> DisplayId {
+        match &self.retarget {
+            #[cfg(feature = "drm")]
+            Retarget::Drm(retarget) => DisplayId(retarget.pipe()),
+            _ => self.worker.display(),
+        }
+    }
+
+    pub fn on_refresh_rate_changed(&mut self, callback: RateCallback) {
+        self.worker.on_refresh_rate_changed(callback)
+    }
+
+    pub fn on_clock_discontinuity(&mut self, callback: DiscontinuityCallback) {
+        self.worker.on_clock_discontinuity(callback)
+    }
+
+    pub fn register_waker(&mut self, waker: Waker) {
+        self.worker.register_waker(waker)
+    }
+
+    pub fn backend(&self) -> Backend {
+        self.backend
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
+    pub(crate) fn park(&self) -> Park {
+        self.worker.park()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.worker.is_stopped()
+    }
+
+    pub fn pause(&mut self) -> Result<(), PauseError> {
+        if self.is_paused() {
+            Err(PauseError::AlreadyPaused)
+        } else {
+            self.worker.set_paused(true);
+            Ok(())
+        }
+    }
+
+    pub fn resume(&mut self) -> Result<(), ResumeError> {
+        if !self.is_paused() {
+            Err(ResumeError::AlreadyRunning)
+        } else {
+            self.worker.set_paused(false);
+            Ok(())
+        }
+    }
+}
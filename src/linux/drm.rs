@@ -0,0 +1,278 @@
+//! Kernel docs: [DRM_IOCTL_WAIT_VBLANK](https://docs.kernel.org/gpu/drm-uapi.html)
+//!
+//! This is the ioctl behind libdrm's `drmWaitVBlank`.
+
+use crate::{
+    linux::{DisplayId, PlatformTimestamp},
+    Bounds, Display, SetDisplayError,
+};
+use libc::{c_long, c_ulong};
+use std::{
+    fs::{File, OpenOptions},
+    io, mem,
+    os::unix::{fs::OpenOptionsExt, io::AsRawFd},
+    path::Path,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc,
+    },
+};
+
+const DRM_VBLANK_RELATIVE: u32 = 0x1;
+const DRM_VBLANK_SECONDARY: u32 = 0x2000_0000;
+const DRM_VBLANK_HIGH_CRTC_SHIFT: u32 = 1;
+const DRM_VBLANK_HIGH_CRTC_MASK: u32 = 0x0000_003e;
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct WaitVblankRequest {
+    type_:    u32,
+    sequence: u32,
+    signal:   c_ulong,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy)]
+struct WaitVblankReply {
+    type_:     u32,
+    sequence:  u32,
+    tval_sec:  c_long,
+    tval_usec: c_long,
+}
+
+#[repr(C)]
+union WaitVblank {
+    request: WaitVblankRequest,
+    reply:   WaitVblankReply,
+}
+
+/// `_IOWR('d', nr, size)`
+const fn drm_iowr(nr: c_ulong, size: usize) -> c_ulong {
+    (3 << 30) | ((size as c_ulong) << 16) | ((b'd' as c_ulong) << 8) | nr
+}
+
+const DRM_IOCTL_WAIT_VBLANK: c_ulong = drm_iowr(0x3a, mem::size_of::<WaitVblank>());
+const DRM_IOCTL_MODE_GETRESOURCES: c_ulong = drm_iowr(0xa0, mem::size_of::<ModeCardRes>());
+const DRM_IOCTL_MODE_GETCRTC: c_ulong = drm_iowr(0xa1, mem::size_of::<ModeCrtc>());
+
+/// `struct drm_mode_card_res`
+#[repr(C)]
+#[derive(Default)]
+struct ModeCardRes {
+    fb_id_ptr:        u64,
+    crtc_id_ptr:      u64,
+    connector_id_ptr: u64,
+    encoder_id_ptr:   u64,
+    count_fbs:        u32,
+    count_crtcs:      u32,
+    count_connectors: u32,
+    count_encoders:   u32,
+    min_width:        u32,
+    max_width:        u32,
+    min_height:       u32,
+    max_height:       u32,
+}
+
+/// `struct drm_mode_modeinfo`
+#[repr(C)]
+#[derive(Default)]
+struct ModeInfo {
+    clock:       u32,
+    hdisplay:    u16,
+    hsync_start: u16,
+    hsync_end:   u16,
+    htotal:      u16,
+    hskew:       u16,
+    vdisplay:    u16,
+    vsync_start: u16,
+    vsync_end:   u16,
+    vtotal:      u16,
+    vscan:       u16,
+    vrefresh:    u32,
+    flags:       u32,
+    type_:       u32,
+    name:        [u8; 32],
+}
+
+/// `struct drm_mode_crtc`
+#[repr(C)]
+#[derive(Default)]
+struct ModeCrtc {
+    set_connectors_ptr: u64,
+    count_connectors:   u32,
+    crtc_id:            u32,
+    fb_id:              u32,
+    x:                  u32,
+    y:                  u32,
+    gamma_size:         u32,
+    mode_valid:         u32,
+    mode:               ModeInfo,
+}
+
+/// Retries `ioctl` while it's interrupted.
+fn ioctl<T>(file: &File, request: c_ulong, arg: &mut T) -> io::Result<()> {
+    loop {
+        if unsafe { libc::ioctl(file.as_raw_fd(), request as _, arg as *mut T) } == 0 {
+            return Ok(());
+        }
+        let error = io::Error::last_os_error();
+        if error.kind() != io::ErrorKind::Interrupted {
+            return Err(error);
+        }
+    }
+}
+
+fn open(path: &Path) -> io::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .write(true)
+        .custom_flags(libc::O_CLOEXEC)
+        .open(path)
+}
+
+/// Returns the active CRTCs of the DRM device at `path`, identified by their index.
+///
+/// DRM has no notion of a primary display, so they're in the order the driver lists them.
+pub(crate) fn displays(path: &Path) -> Option<Vec<Display>> {
+    let file = open(path).ok()?;
+    let mut resources = ModeCardRes::default();
+    ioctl(&file, DRM_IOCTL_MODE_GETRESOURCES, &mut resources).ok()?;
+    let mut crtcs = vec![0u32; resources.count_crtcs as usize];
+    // Only ask for the CRTCs this time.
+    resources = ModeCardRes {
+        crtc_id_ptr: crtcs.as_mut_ptr() as u64,
+        count_crtcs: resources.count_crtcs,
+        ..Default::default()
+    };
+    ioctl(&file, DRM_IOCTL_MODE_GETRESOURCES, &mut resources).ok()?;
+    crtcs.truncate(resources.count_crtcs as usize);
+    let displays = crtcs
+        .into_iter()
+        .enumerate()
+        .filter_map(|(index, crtc_id)| {
+            let mut crtc = ModeCrtc {
+                crtc_id,
+                ..Default::default()
+            };
+            ioctl(&file, DRM_IOCTL_MODE_GETCRTC, &mut crtc).ok()?;
+            if crtc.mode_valid == 0 {
+                return None;
+            }
+            let mode = &crtc.mode;
+            let refresh_rate = if mode.htotal != 0 && mode.vtotal != 0 {
+                // `clock` is in kHz.
+                Some(
+                    f64::from(mode.clock) * 1000.0
+                        / (f64::from(mode.htotal) * f64::from(mode.vtotal)),
+                )
+            } else {
+                None
+            };
+            Some(Display {
+                id: DisplayId(index as u32),
+                name: None,
+                bounds: Bounds {
+                    x:      f64::from(crtc.x),
+                    y:      f64::from(crtc.y),
+                    width:  f64::from(mode.hdisplay),
+                    height: f64::from(mode.vdisplay),
+                },
+                refresh_rate,
+                refresh_rate_range: None,
+                mirrors: None,
+            })
+        })
+        .collect();
+    Some(displays)
+}
+
+/// Encodes a CRTC index into the high bits of a vblank request type.
+fn pipe_flags(pipe: u32) -> u32 {
+    match pipe {
+        0 => 0,
+        1 => DRM_VBLANK_SECONDARY,
+        _ => (pipe << DRM_VBLANK_HIGH_CRTC_SHIFT) & DRM_VBLANK_HIGH_CRTC_MASK,
+    }
+}
+
+/// Returns the time of the vblank, in nanoseconds, and its sequence number.
+fn wait_relative(file: &File, pipe: u32, count: u32) -> io::Result<(u64, u32)> {
+    let mut vblank = WaitVblank {
+        request: WaitVblankRequest {
+            type_:    DRM_VBLANK_RELATIVE | pipe_flags(pipe),
+            sequence: count,
+            signal:   0,
+        },
+    };
+    ioctl(file, DRM_IOCTL_WAIT_VBLANK, &mut vblank)?;
+    let reply = unsafe { vblank.reply };
+    let time = reply.tval_sec as u64 * 1_000_000_000 + reply.tval_usec as u64 * 1000;
+    Ok((time, reply.sequence))
+}
+
+/// Moves a [`Vblank`] onto another CRTC of the same device.
+#[derive(Debug)]
+pub(crate) struct Retarget {
+    file: Arc<File>,
+    pipe: Arc<AtomicU32>,
+}
+
+impl Retarget {
+    pub fn pipe(&self) -> u32 {
+        self.pipe.load(Ordering::Relaxed)
+    }
+
+    pub fn set_pipe(&self, pipe: u32) -> Result<(), SetDisplayError> {
+        // Waiting for 0 vblanks returns immediately, and fails if the CRTC is invalid.
+        wait_relative(&self.file, pipe, 0).map_err(|_| SetDisplayError::NotConnected)?;
+        self.pipe.store(pipe, Ordering::Relaxed);
+        Ok(())
+    }
+}
+
+/// Waits for vblanks on a CRTC of a DRM device.
+#[derive(Debug)]
+pub(crate) struct Vblank {
+    file: Arc<File>,
+    pipe: Arc<AtomicU32>,
+    /// The time and sequence number of the last vblank, used to estimate the refresh period.
+    last: Option<(u64, u32)>,
+}
+
+impl Vblank {
+    /// Opens the DRM device at `path`, for the CRTC with index `pipe`.
+    ///
+    /// Returns `None` if the device can't be opened or the CRTC doesn't exist.
+    pub fn open(path: &Path, pipe: u32) -> Option<Self> {
+        let file = open(path).ok()?;
+        // Waiting for 0 vblanks returns immediately, and fails if the CRTC is invalid.
+        wait_relative(&file, pipe, 0).ok()?;
+        Some(Vblank {
+            file: Arc::new(file),
+            pipe: Arc::new(AtomicU32::new(pipe)),
+            last: None,
+        })
+    }
+
+    /// Returns a handle that can move the link to another CRTC.
+    pub fn retarget(&self) -> Retarget {
+        Retarget {
+            file: Arc::clone(&self.file),
+            pipe: Arc::clone(&self.pipe),
+        }
+    }
+
+    /// Blocks until the next vblank.
+    pub fn wait(&mut self) -> Option<(PlatformTimestamp, DisplayId)> {
+        let pipe = self.pipe.load(Ordering::Relaxed);
+        // The reply time is `CLOCK_MONOTONIC`.
+        let (time, sequence) = wait_relative(&self.file, pipe, 1).ok()?;
+        let period = match self.last.replace((time, sequence)) {
+            Some((last_time, last_sequence)) if sequence > last_sequence => {
+                (time - last_time) / u64::from(sequence - last_sequence)
+            }
+            _ => 0,
+        };
+        Some((PlatformTimestamp(time + period), DisplayId(pipe)))
+    }
+}
@@ -0,0 +1,172 @@
+//! Wayland docs: [wl_surface.frame](https://wayland.app/protocols/wayland#wl_surface:request:frame)
+//! and [wp_presentation](https://wayland.app/protocols/presentation-time)
+
+use crate::linux::{DisplayId, PlatformTimestamp};
+use std::ffi::c_void;
+use wayland_client::{
+    backend::{Backend, ObjectId},
+    globals::{registry_queue_init, GlobalListContents},
+    protocol::{
+        wl_callback::{self, WlCallback},
+        wl_registry::{self, WlRegistry},
+        wl_surface::WlSurface,
+    },
+    Connection, Dispatch, EventQueue, Proxy, QueueHandle,
+};
+use wayland_protocols::wp::presentation_time::client::{
+    wp_presentation::{self, WpPresentation},
+    wp_presentation_feedback::{self, WpPresentationFeedback},
+};
+
+#[derive(Debug, Default)]
+struct State {
+    frame_done: bool,
+    clock_id:   Option<u32>,
+    /// The last presentation time and refresh period reported by `wp_presentation_feedback`, in
+    /// nanoseconds.
+    presented:  Option<(u64, u64)>,
+    display:    Option<DisplayId>,
+}
+
+impl State {
+    /// Predicts the time of the next presentation from the last presentation feedback, falling
+    /// back to the current time.
+    fn next_presentation(&self) -> PlatformTimestamp {
+        let now = PlatformTimestamp::now();
+        match (self.clock_id, self.presented) {
+            (Some(clock_id), Some((presented, refresh)))
+                if clock_id == libc::CLOCK_MONOTONIC as u32 && refresh > 0 =>
+            {
+                let frames = now.0.saturating_sub(presented) / refresh + 1;
+                PlatformTimestamp(presented + frames * refresh)
+            }
+            _ => now,
+        }
+    }
+}
+
+impl Dispatch<WlRegistry, GlobalListContents> for State {
+    fn event(
+        _: &mut Self,
+        _: &WlRegistry,
+        _: wl_registry::Event,
+        _: &GlobalListContents,
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<WlCallback, ()> for State {
+    fn event(
+        state: &mut Self,
+        _: &WlCallback,
+        event: wl_callback::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let wl_callback::Event::Done { .. } = event {
+            state.frame_done = true;
+        }
+    }
+}
+
+impl Dispatch<WpPresentation, ()> for State {
+    fn event(
+        state: &mut Self,
+        _: &WpPresentation,
+        event: wp_presentation::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        if let wp_presentation::Event::ClockId { clk_id } = event {
+            state.clock_id = Some(clk_id);
+        }
+    }
+}
+
+impl Dispatch<WpPresentationFeedback, ()> for State {
+    fn event(
+        state: &mut Self,
+        _: &WpPresentationFeedback,
+        event: wp_presentation_feedback::Event,
+        _: &(),
+        _: &Connection,
+        _: &QueueHandle<Self>,
+    ) {
+        match event {
+            wp_presentation_feedback::Event::SyncOutput { output } => {
+                state.display = Some(DisplayId(output.id().protocol_id()));
+            }
+            wp_presentation_feedback::Event::Presented {
+                tv_sec_hi,
+                tv_sec_lo,
+                tv_nsec,
+                refresh,
+                ..
+            } => {
+                let secs = (u64::from(tv_sec_hi) << 32) | u64::from(tv_sec_lo);
+                let presented = secs * 1_000_000_000 + u64::from(tv_nsec);
+                state.presented = Some((presented, u64::from(refresh)));
+            }
+            _ => (),
+        }
+    }
+}
+
+/// Waits for frame callbacks on a foreign `wl_surface`, using a private event queue so the
+/// application's own queue is left alone.
+#[derive(Debug)]
+pub(crate) struct Frames {
+    connection:   Connection,
+    queue:        EventQueue<State>,
+    surface:      WlSurface,
+    presentation: Option<WpPresentation>,
+    state:        State,
+}
+
+impl Frames {
+    /// # Safety
+    ///
+    /// `display` must be a valid `wl_display *` and `surface` a valid `wl_surface *` belonging to
+    /// it, both outliving the returned `Frames`.
+    pub unsafe fn new(display: *mut c_void, surface: *mut c_void) -> Option<Self> {
+        let backend = Backend::from_foreign_display(display as *mut _);
+        let connection = Connection::from_backend(backend);
+        let surface_id = ObjectId::from_ptr(WlSurface::interface(), surface as *mut _).ok()?;
+        let surface = WlSurface::from_id(&connection, surface_id).ok()?;
+        let (globals, mut queue) = registry_queue_init::<State>(&connection).ok()?;
+        let presentation = globals
+            .bind::<WpPresentation, _, _>(&queue.handle(), 1..=1, ())
+            .ok();
+        let mut state = State::default();
+        queue.roundtrip(&mut state).ok()?;
+        Some(Frames {
+            connection,
+            queue,
+            surface,
+            presentation,
+            state,
+        })
+    }
+
+    /// Blocks until the compositor signals that it's a good time to draw the next frame.
+    pub fn wait(&mut self) -> Option<(PlatformTimestamp, DisplayId)> {
+        let handle = self.queue.handle();
+        self.surface.frame(&handle, ());
+        if let Some(presentation) = &self.presentation {
+            presentation.feedback(&self.surface, &handle, ());
+        }
+        self.connection.flush().ok()?;
+        self.state.frame_done = false;
+        while !self.state.frame_done {
+            self.queue.blocking_dispatch(&mut self.state).ok()?;
+        }
+        Some((
+            self.state.next_presentation(),
+            self.state.display.unwrap_or(DisplayId::UNKNOWN),
+        ))
+    }
+}
@@ -0,0 +1,284 @@
+//! X.Org docs: [Present extension](https://gitlab.freedesktop.org/xorg/proto/xorgproto/-/blob/master/presentproto.txt)
+
+use crate::{
+    linux::{DisplayId, PlatformTimestamp},
+    Bounds, Display, SetDisplayError,
+};
+use std::sync::Arc;
+use x11rb::{
+    connection::Connection,
+    protocol::{
+        present::{self, ConnectionExt as _},
+        randr::{ConnectionExt as _, GetCrtcInfoReply},
+        xproto::{ConfigureWindowAux, ConnectionExt as _, CreateWindowAux, Window, WindowClass},
+        Event,
+    },
+    rust_connection::RustConnection,
+    CURRENT_TIME,
+};
+
+fn crtc_info(conn: &RustConnection, crtc: u32) -> Option<GetCrtcInfoReply> {
+    let info = conn
+        .randr_get_crtc_info(crtc, CURRENT_TIME)
+        .ok()?
+        .reply()
+        .ok()?;
+    if info.mode != 0 {
+        Some(info)
+    } else {
+        None
+    }
+}
+
+/// Returns the enabled CRTC containing the point `(x, y)` of the root window.
+fn crtc_at(conn: &RustConnection, root: Window, x: i32, y: i32) -> Option<u32> {
+    let resources = conn
+        .randr_get_screen_resources_current(root)
+        .ok()?
+        .reply()
+        .ok()?;
+    resources.crtcs.into_iter().find(|&crtc| {
+        crtc_info(conn, crtc).is_some_and(|info| {
+            let (left, top) = (i32::from(info.x), i32::from(info.y));
+            x >= left
+                && y >= top
+                && x < left + i32::from(info.width)
+                && y < top + i32::from(info.height)
+        })
+    })
+}
+
+/// Returns the CRTC of the primary output, or the first enabled CRTC if there's no primary.
+fn primary_crtc(conn: &RustConnection, root: Window) -> Option<u32> {
+    let primary = conn
+        .randr_get_output_primary(root)
+        .ok()
+        .and_then(|cookie| cookie.reply().ok())
+        .and_then(|reply| conn.randr_get_output_info(reply.output, CURRENT_TIME).ok())
+        .and_then(|cookie| cookie.reply().ok())
+        .map(|info| info.crtc)
+        .filter(|&crtc| crtc_info(conn, crtc).is_some());
+    primary.or_else(|| {
+        let resources = conn
+            .randr_get_screen_resources_current(root)
+            .ok()?
+            .reply()
+            .ok()?;
+        resources
+            .crtcs
+            .into_iter()
+            .find(|&crtc| crtc_info(conn, crtc).is_some())
+    })
+}
+
+/// Returns the enabled CRTCs of the default display, with the primary CRTC first.
+pub(crate) fn displays() -> Option<Vec<Display>> {
+    let (conn, screen) = x11rb::connect(None).ok()?;
+    let root = conn.setup().roots[screen].root;
+    let resources = conn
+        .randr_get_screen_resources_current(root)
+        .ok()?
+        .reply()
+        .ok()?;
+    let primary = primary_crtc(&conn, root);
+    let mut displays: Vec<Display> = resources
+        .crtcs
+        .iter()
+        .filter_map(|&crtc| {
+            let info = crtc_info(&conn, crtc)?;
+            let refresh_rate = resources
+                .modes
+                .iter()
+                .find(|mode| mode.id == info.mode)
+                .filter(|mode| mode.htotal != 0 && mode.vtotal != 0)
+                .map(|mode| {
+                    f64::from(mode.dot_clock) / (f64::from(mode.htotal) * f64::from(mode.vtotal))
+                });
+            let name = info.outputs.first().and_then(|&output| {
+                let info = conn
+                    .randr_get_output_info(output, CURRENT_TIME)
+                    .ok()?
+                    .reply()
+                    .ok()?;
+                Some(String::from_utf8_lossy(&info.name).into_owned())
+            });
+            Some(Display {
+                id: DisplayId(crtc),
+                name,
+                bounds: Bounds {
+                    x:      f64::from(info.x),
+                    y:      f64::from(info.y),
+                    width:  f64::from(info.width),
+                    height: f64::from(info.height),
+                },
+                refresh_rate,
+                refresh_rate_range: None,
+                mirrors: None,
+            })
+        })
+        .collect();
+    displays.sort_by_key(|display| Some(display.id.0) != primary);
+    Some(displays)
+}
+
+/// Moves a window created by [`Present::on_crtc`] onto another CRTC.
+#[derive(Debug)]
+pub(crate) struct Retarget {
+    conn:   Arc<RustConnection>,
+    window: Window,
+}
+
+impl Retarget {
+    pub fn set_crtc(&self, crtc: u32) -> Result<(), SetDisplayError> {
+        let info = crtc_info(&self.conn, crtc).ok_or(SetDisplayError::NotConnected)?;
+        let aux = ConfigureWindowAux::new()
+            .x(i32::from(info.x))
+            .y(i32::from(info.y));
+        let _ = self.conn.configure_window(self.window, &aux);
+        let _ = self.conn.flush();
+        Ok(())
+    }
+}
+
+/// Waits for vblanks with `PresentNotifyMSC` on a window, following it across CRTCs.
+#[derive(Debug)]
+pub(crate) struct Present {
+    conn:        Arc<RustConnection>,
+    root:        Window,
+    window:      Window,
+    owns_window: bool,
+    serial:      u32,
+    /// The UST and MSC of the last vblank, used to estimate the refresh period.
+    last:        Option<(u64, u64)>,
+    display:     DisplayId,
+}
+
+impl Present {
+    fn new(conn: RustConnection, root: Window, window: Window, owns_window: bool) -> Option<Self> {
+        conn.present_query_version(1, 2).ok()?.reply().ok()?;
+        let eid = conn.generate_id().ok()?;
+        conn.present_select_input(
+            eid,
+            window,
+            present::EventMask::CONFIGURE_NOTIFY | present::EventMask::COMPLETE_NOTIFY,
+        )
+        .ok()?;
+        let mut present = Present {
+            conn: Arc::new(conn),
+            root,
+            window,
+            owns_window,
+            serial: 0,
+            last: None,
+            display: DisplayId::UNKNOWN,
+        };
+        present.update_display();
+        Some(present)
+    }
+
+    /// Follows `window`, an existing window on the default display.
+    pub fn for_window(window: Window) -> Option<Self> {
+        let (conn, screen) = x11rb::connect(None).ok()?;
+        let root = conn.setup().roots[screen].root;
+        Self::new(conn, root, window, false)
+    }
+
+    /// Creates a hidden window on `crtc`, or on the primary CRTC if `crtc` is `None`.
+    pub fn on_crtc(crtc: Option<u32>) -> Option<Self> {
+        let (conn, screen) = x11rb::connect(None).ok()?;
+        let root = conn.setup().roots[screen].root;
+        let crtc = crtc.or_else(|| primary_crtc(&conn, root))?;
+        let info = crtc_info(&conn, crtc)?;
+        let window = conn.generate_id().ok()?;
+        conn.create_window(
+            0,
+            window,
+            root,
+            info.x,
+            info.y,
+            1,
+            1,
+            0,
+            WindowClass::INPUT_ONLY,
+            0,
+            &CreateWindowAux::new(),
+        )
+        .ok()?;
+        Self::new(conn, root, window, true)
+    }
+
+    /// Returns a handle that can move the window, if it was created by this `Present`.
+    pub fn retarget(&self) -> Option<Retarget> {
+        if self.owns_window {
+            Some(Retarget {
+                conn:   Arc::clone(&self.conn),
+                window: self.window,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Finds the CRTC containing the center of the window.
+    fn update_display(&mut self) {
+        let conn = &self.conn;
+        let crtc = conn
+            .get_geometry(self.window)
+            .ok()
+            .and_then(|cookie| cookie.reply().ok())
+            .and_then(|geometry| {
+                let origin = conn
+                    .translate_coordinates(self.window, self.root, 0, 0)
+                    .ok()?
+                    .reply()
+                    .ok()?;
+                let x = i32::from(origin.dst_x) + i32::from(geometry.width) / 2;
+                let y = i32::from(origin.dst_y) + i32::from(geometry.height) / 2;
+                crtc_at(conn, self.root, x, y)
+            });
+        if let Some(crtc) = crtc {
+            self.display = DisplayId(crtc);
+        }
+    }
+
+    /// Blocks until the next vblank of the CRTC the window is on.
+    pub fn wait(&mut self) -> Option<(PlatformTimestamp, DisplayId)> {
+        self.serial = self.serial.wrapping_add(1);
+        // A divisor of 1 asks for the next vblank, regardless of which CRTC the window is on or
+        // how its MSC was adjusted when it moved.
+        self.conn
+            .present_notify_msc(self.window, self.serial, 0, 1, 0)
+            .ok()?;
+        self.conn.flush().ok()?;
+        loop {
+            match self.conn.wait_for_event().ok()? {
+                Event::PresentCompleteNotify(event) if event.serial == self.serial => {
+                    let period = match self.last.replace((event.ust, event.msc)) {
+                        Some((ust, msc)) if event.msc > msc => {
+                            (event.ust - ust) / (event.msc - msc)
+                        }
+                        _ => 0,
+                    };
+                    // The UST is a `CLOCK_MONOTONIC` time in microseconds.
+                    let next = PlatformTimestamp((event.ust + period) * 1000);
+                    return Some((next, self.display));
+                }
+                Event::PresentConfigureNotify(_) => {
+                    // MSCs from different CRTCs aren't comparable.
+                    self.last = None;
+                    self.update_display();
+                }
+                _ => (),
+            }
+        }
+    }
+}
+
+impl Drop for Present {
+    fn drop(&mut self) {
+        if self.owns_window {
+            let _ = self.conn.destroy_window(self.window);
+            let _ = self.conn.flush();
+        }
+    }
+}
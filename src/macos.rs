@@ -1,16 +1,239 @@
 #![cfg(target_os = "macos")]
 
+pub mod cadisplaylink;
+pub mod coregraphics;
 pub mod cvdisplaylink;
+mod dispatch;
+mod main_thread;
+mod nswindow;
+mod power;
+mod suspend;
+mod workspace;
+
+pub(crate) use crate::macos::main_thread::is_main_thread;
+pub use crate::macos::{
+    dispatch::{DispatchQueue, QosClass},
+    main_thread::MainThreadDisplayLink,
+    power::is_on_battery,
+};
 
 use crate::{
-    macos::cvdisplaylink::{CVDisplayLink, CVTimeStamp, DisplayLink as RawDisplayLink},
-    PauseError, ResumeError,
+    callback::Park,
+    display::WatchCallback,
+    frame::{Clock, DiscontinuityCallback, Epoch, FrameObservers, RateCallback},
+    macos::{
+        cadisplaylink::DisplayLink as ScreenDisplayLink,
+        cvdisplaylink::{
+            CVDisplayLink, CVDisplayLinkRetain, CVGetCurrentHostTime, CVGetHostClockFrequency,
+            CVTimeStamp, DisplayLink as RawDisplayLink, DisplayLinkRef,
+            CV_TIME_STAMP_HOST_TIME_VALID,
+        },
+        nswindow::{OcclusionObserver, ScreenObserver},
+        power::PowerObserver,
+        suspend::{Reason, Runner, Switch},
+        workspace::SessionObserver,
+    },
+    thermal::{self, ThermalObserver},
+    Backend, Bounds, Display, DisplayEvent, FrameTime, FrameTimestamp, PauseError, ResumeError,
+    SetDisplayError,
+};
+use foreign_types::{ForeignType, ForeignTypeRef};
+use objc::{
+    class,
+    declare::ClassDecl,
+    msg_send,
+    runtime::{Object, Sel, BOOL, NO, YES},
+    sel, sel_impl,
+};
+use std::{
+    any::Any,
+    ffi::c_void,
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+    mem,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex, Once,
+    },
+    task::Waker,
 };
-use std::{any::Any, ffi::c_void};
 use time_point::TimePoint;
 
-unsafe extern "C" fn render<F>(
-    _: *mut CVDisplayLink,
+/// A `CGDirectDisplayID` identifying a display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DisplayId(pub u32);
+
+impl DisplayId {
+    /// The id reported when the display driving a link is unknown, e.g. for the timer fallback.
+    pub const UNKNOWN: DisplayId = DisplayId(0);
+
+    /// Returns the display shown by `screen`, from its `NSScreenNumber`.
+    ///
+    /// # Safety
+    ///
+    /// `screen` must be a valid `NSScreen *`.
+    pub unsafe fn from_ns_screen(screen: *mut c_void) -> Option<Self> {
+        cadisplaylink::display_for_ns_screen(screen.cast()).map(DisplayId)
+    }
+
+    /// Returns the display shown by `[NSScreen screens][index]`, where index 0 is the screen with
+    /// the menu bar. Returns `None` if there's no such screen.
+    pub fn from_ns_screen_index(index: usize) -> Option<Self> {
+        unsafe { cadisplaylink::display_for_screen_index(index) }.map(DisplayId)
+    }
+}
+
+fn display(display: u32) -> Display {
+    let rect = unsafe { coregraphics::CGDisplayBounds(display) };
+    Display {
+        id:                 DisplayId(display),
+        name:               unsafe { cadisplaylink::screen_name(display) },
+        bounds:             Bounds {
+            x:      rect.origin.x,
+            y:      rect.origin.y,
+            width:  rect.size.width,
+            height: rect.size.height,
+        },
+        refresh_rate:       coregraphics::refresh_rate(display),
+        refresh_rate_range: unsafe { cadisplaylink::refresh_rate_range(display) },
+        // `kCGNullDirectDisplay` if it isn't mirroring.
+        mirrors:            match unsafe { coregraphics::CGDisplayMirrorsDisplay(display) } {
+            0 => None,
+            mirrored => Some(DisplayId(mirrored)),
+        },
+    }
+}
+
+pub(crate) fn displays() -> Vec<Display> {
+    coregraphics::active_displays()
+        .into_iter()
+        .map(display)
+        .collect()
+}
+
+/// Returns `true` if no display is active, e.g. on a build server or a virtualized CI machine with
+/// no display attached, where a `CVDisplayLink` either can't be created or never calls back.
+pub fn is_headless() -> bool {
+    coregraphics::active_displays().is_empty()
+}
+
+/// Returns the retained `MTLDevice` currently driving `display`.
+pub(crate) fn metal_device(display: u32) -> Option<*mut Object> {
+    let device = unsafe { coregraphics::CGDirectDisplayCopyCurrentMetalDevice(display) };
+    Some(device as *mut Object).filter(|device| !device.is_null())
+}
+
+/// Returns the `registryID` of the `MTLDevice` currently driving `display`, which is only
+/// available on macOS 10.13 and newer.
+pub(crate) fn metal_registry_id(display: u32) -> Option<u64> {
+    let device = metal_device(display)?;
+    unsafe {
+        let responds: BOOL = msg_send![device, respondsToSelector: sel!(registryID)];
+        let registry_id: Option<u64> = if responds != NO {
+            Some(msg_send![device, registryID])
+        } else {
+            None
+        };
+        let () = msg_send![device, release];
+        registry_id
+    }
+}
+
+extern "C" fn display_reconfigured(display: u32, flags: u32, user_info: *mut c_void) {
+    use coregraphics::*;
+    // Every change is reported twice, before and after it happens.
+    if flags & CG_DISPLAY_BEGIN_CONFIGURATION_FLAG != 0 {
+        return;
+    }
+    let callback = unsafe { &mut *(user_info as *mut WatchCallback) };
+    if flags & (CG_DISPLAY_ADD_FLAG | CG_DISPLAY_ENABLED_FLAG) != 0 {
+        callback(DisplayEvent::Connected(DisplayId(display)))
+    } else if flags & (CG_DISPLAY_REMOVE_FLAG | CG_DISPLAY_DISABLED_FLAG) != 0 {
+        callback(DisplayEvent::Disconnected(DisplayId(display)))
+    } else if flags & (CG_DISPLAY_MIRROR_FLAG | CG_DISPLAY_UNMIRROR_FLAG) != 0 {
+        callback(DisplayEvent::mirroring_changed(&self::display(display)))
+    } else if flags & (CG_DISPLAY_SET_MODE_FLAG | CG_DISPLAY_DESKTOP_SHAPE_CHANGED_FLAG) != 0 {
+        callback(DisplayEvent::reconfigured(&self::display(display)))
+    }
+}
+
+/// Reports displays coming, going, changing mode and (un)mirroring with
+/// `CGDisplayRegisterReconfigurationCallback`, which calls back on the main thread.
+#[derive(Debug)]
+pub(crate) struct Watcher {
+    callback: *mut WatchCallback,
+}
+
+impl Watcher {
+    pub fn new(callback: WatchCallback) -> Option<Self> {
+        let callback = Box::into_raw(Box::new(callback));
+        let code = unsafe {
+            coregraphics::CGDisplayRegisterReconfigurationCallback(
+                display_reconfigured,
+                callback as *mut c_void,
+            )
+        };
+        if code == 0 {
+            Some(Watcher { callback })
+        } else {
+            drop(unsafe { Box::from_raw(callback) });
+            None
+        }
+    }
+}
+
+impl Drop for Watcher {
+    fn drop(&mut self) {
+        unsafe {
+            coregraphics::CGDisplayRemoveReconfigurationCallback(
+                display_reconfigured,
+                self.callback as *mut c_void,
+            );
+            drop(Box::from_raw(self.callback));
+        }
+    }
+}
+
+/// The `CVTimeStamp` of the output frame, as given to the `CVDisplayLink` output callback.
+#[derive(Clone, Copy, Debug)]
+pub struct PlatformTimestamp(pub CVTimeStamp);
+
+impl PlatformTimestamp {
+    pub(crate) fn now() -> Self {
+        PlatformTimestamp(CVTimeStamp {
+            host_time: unsafe { CVGetCurrentHostTime() },
+            ..Default::default()
+        })
+    }
+
+    pub(crate) fn seconds_since(&self, earlier: Self) -> f64 {
+        let ticks = self.0.host_time as f64 - earlier.0.host_time as f64;
+        ticks / unsafe { CVGetHostClockFrequency() }
+    }
+
+    pub(crate) fn to_time_point(self, _epoch: &Epoch) -> TimePoint {
+        TimePoint::new(self.0.host_time as _)
+    }
+}
+
+struct Callback<T, F> {
+    clock: Clock,
+    f:     F,
+    _t:    PhantomData<fn() -> T>,
+}
+
+impl<T, F> Callback<T, F> {
+    fn new(callback: F) -> Self {
+        Callback {
+            clock: Clock::default(),
+            f:     callback,
+            _t:    PhantomData,
+        }
+    }
+}
+
+unsafe extern "C" fn render<T, F>(
+    display_link: *mut CVDisplayLink,
     _: *const CVTimeStamp,
     in_out_timestamp: *const CVTimeStamp,
     _: i64,
@@ -18,100 +241,685 @@ unsafe extern "C" fn render<F>(
     display_link_context: *mut c_void,
 ) -> i32
 where
-    F: FnMut(TimePoint),
+    T: FrameTimestamp,
+    F: FnMut(FrameTime<T>),
 {
     let in_out_timestamp = &*in_out_timestamp;
-    let f = &mut *(display_link_context as *mut F);
-    f(TimePoint::new(in_out_timestamp.host_time as _));
+    let display_link = DisplayLinkRef::from_ptr(display_link);
+    let callback = &mut *(display_link_context as *mut Callback<T, F>);
+    let frame = callback.clock.frame(
+        PlatformTimestamp(*in_out_timestamp),
+        DisplayId(display_link.current_display()),
+    );
+    (callback.f)(frame);
     0
 }
 
+/// The callback of a screen link, shared with the `DisplayLinkScreenCallbackHolder` the
+/// `CADisplayLink` calls, so it's only dropped once the link can no longer call it.
+///
+/// It's passed the `targetTimestamp` of the frame.
+struct ScreenCallback(Mutex<Option<RenderScreen>>);
+
+type RenderScreen = Box<dyn FnMut(f64) + Send>;
+
+impl Debug for ScreenCallback {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.debug_struct("ScreenCallback").finish_non_exhaustive()
+    }
+}
+
+fn render_screen<T, F>(
+    mut callback: Callback<T, F>,
+    display: Arc<AtomicU32>,
+) -> impl FnMut(f64) + Send
+where
+    T: FrameTimestamp,
+    F: 'static + FnMut(FrameTime<T>) + Send,
+{
+    move |target_timestamp| {
+        let timestamp = CVTimeStamp {
+            host_time: (target_timestamp * unsafe { CVGetHostClockFrequency() }) as u64,
+            flags: CV_TIME_STAMP_HOST_TIME_VALID,
+            ..Default::default()
+        };
+        let frame = callback.clock.frame(
+            PlatformTimestamp(timestamp),
+            DisplayId(display.load(Ordering::Relaxed)),
+        );
+        (callback.f)(frame);
+    }
+}
+
+unsafe fn screen_callback(this: &Object) -> &ScreenCallback {
+    let callback: *const c_void = *this.get_ivar("_data");
+    &*(callback as *const ScreenCallback)
+}
+
+extern "C" fn run_screen_callback(this: &Object, _: Sel, display_link: *mut Object) {
+    unsafe {
+        if let Some(f) = &mut *screen_callback(this).0.lock().unwrap() {
+            // `targetTimestamp` is in the time base of `CACurrentMediaTime`, which is host time
+            // in seconds.
+            let target_timestamp: f64 = msg_send![display_link, targetTimestamp];
+            f(target_timestamp)
+        }
+    }
+}
+
+/// Releases the holder's reference to the `ScreenCallback` once the last `CADisplayLink` calling
+/// it and the `DisplayLink` release the holder.
+extern "C" fn dealloc_screen_callback_holder(this: &Object, _: Sel) {
+    unsafe {
+        let callback: *const c_void = *this.get_ivar("_data");
+        drop(Arc::from_raw(callback as *const ScreenCallback));
+        let () = msg_send![super(this, class!(NSObject)), dealloc];
+    }
+}
+
+#[derive(Debug)]
+enum Source {
+    /// A `CVDisplayLink`, which calls back on its own thread.
+    CoreVideo(RawDisplayLink),
+    /// A `CADisplayLink` from an `NSScreen`, which calls back on the main run loop.
+    Screen {
+        /// Shared with the `Watcher` that rebinds the link when its display is disconnected.
+        display_link: Arc<Mutex<ScreenDisplayLink>>,
+        target:       *mut Object,
+        callback:     Arc<ScreenCallback>,
+        display:      Arc<AtomicU32>,
+    },
+}
+
 #[derive(Debug)]
 pub struct DisplayLink {
-    is_paused:    bool,
-    func:         Box<dyn Any>,
-    display_link: RawDisplayLink,
+    /// Shared with the observers that pause the link on their own.
+    runner:    Arc<Runner>,
+    /// The callback of a `CVDisplayLink`. Screen links share theirs with their target instead.
+    func:      Option<Box<dyn Any>>,
+    source:    Source,
+    /// Set when the link follows a window across screens.
+    observer:  Option<ScreenObserver>,
+    /// Set when the link pauses while a window is hidden.
+    occlusion: Option<OcclusionObserver>,
+    /// Set when the link pauses while the displays are asleep.
+    sleep:     Option<SessionObserver>,
+    /// Set when the link pauses while the screen is locked.
+    lock:      Option<SessionObserver>,
+    /// Set when the link is throttled on battery power.
+    power:     Option<PowerObserver>,
+    thermal:   Option<ThermalObserver>,
+    /// Set when the link moves to the main display if its display is disconnected.
+    watcher:   Option<Watcher>,
+    observers: Arc<FrameObservers>,
 }
 
 impl Drop for DisplayLink {
     fn drop(&mut self) {
-        if !self.is_paused {
+        // The watcher and the observers pausing the link can touch it, so they go first.
+        self.watcher = None;
+        self.occlusion = None;
+        self.sleep = None;
+        self.lock = None;
+        self.power = None;
+        self.runner.pause();
+        if let Source::Screen {
+            display_link,
+            target,
+            callback,
+            ..
+        } = &self.source
+        {
+            // The run loop may still call the target before the link is invalidated on the main
+            // thread, which finds the callback gone.
+            display_link.lock().unwrap().invalidate();
+            drop(callback.0.lock().unwrap().take());
             unsafe {
-                self.display_link.stop();
+                let () = msg_send![*target, release];
             }
         }
     }
 }
 
 impl DisplayLink {
-    fn new_impl<R, F>(make_raw: R, callback: F) -> Option<Self>
+    fn new_impl<R, T, F>(make_raw: R, callback: F) -> Option<Self>
     where
         R: FnOnce() -> Option<RawDisplayLink>,
-        F: 'static + FnMut(TimePoint) + Send,
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
     {
-        let func = Box::new(callback);
+        // Rather than a link that never calls back.
+        if is_headless() {
+            return None;
+        }
+        let func = Box::new(Callback::<T, F>::new(callback));
+        let observers = func.clock.observers();
         unsafe {
             let raw = Box::into_raw(func);
             let func = Box::from_raw(raw);
             let mut display_link = make_raw()?;
-            display_link.set_output_callback(render::<F>, raw as *mut c_void);
+            display_link.set_output_callback(render::<T, F>, raw as *mut c_void);
             Some(DisplayLink {
-                is_paused: true,
-                func,
-                display_link,
+                runner: Arc::new(Runner::new(Switch::CoreVideo(display_link.clone()))),
+                func: Some(func),
+                source: Source::CoreVideo(display_link),
+                observer: None,
+                occlusion: None,
+                sleep: None,
+                lock: None,
+                power: None,
+                thermal: None,
+                watcher: None,
+                observers,
+            })
+        }
+    }
+
+    fn new_screen<T, F>(screen: *mut Object, display_id: u32, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        static CALLBACK_CLASS_CREATOR: Once = Once::new();
+        CALLBACK_CLASS_CREATOR.call_once(|| {
+            let mut decl =
+                ClassDecl::new("DisplayLinkScreenCallbackHolder", class!(NSObject)).unwrap();
+            decl.add_ivar::<*const c_void>("_data");
+            unsafe {
+                decl.add_method(
+                    sel!(call:),
+                    run_screen_callback as extern "C" fn(&Object, Sel, *mut Object),
+                );
+                decl.add_method(
+                    sel!(dealloc),
+                    dealloc_screen_callback_holder as extern "C" fn(&Object, Sel),
+                );
+            }
+            decl.register();
+        });
+
+        let display = Arc::new(AtomicU32::new(display_id));
+        let callback = Callback::<T, F>::new(callback);
+        let observers = callback.clock.observers();
+        let callback = Arc::new(ScreenCallback(Mutex::new(Some(Box::new(render_screen(
+            callback,
+            Arc::clone(&display),
+        ))))));
+        unsafe {
+            let target: *mut Object = msg_send![class!(DisplayLinkScreenCallbackHolder), alloc];
+            let target: *mut Object = msg_send![target, init];
+            (*target).set_ivar::<*const c_void>(
+                "_data",
+                Arc::into_raw(Arc::clone(&callback)) as *const c_void,
+            );
+            let display_link = match Self::screen_link(screen, target) {
+                Some(display_link) => display_link,
+                None => {
+                    let () = msg_send![target, release];
+                    return None;
+                }
+            };
+            let display_link = Arc::new(Mutex::new(display_link));
+            Some(DisplayLink {
+                runner: Arc::new(Runner::new(Switch::Screen(Arc::clone(&display_link)))),
+                func: None,
+                source: Source::Screen {
+                    display_link,
+                    target,
+                    callback,
+                    display,
+                },
+                observer: None,
+                occlusion: None,
+                sleep: None,
+                lock: None,
+                power: None,
+                thermal: None,
+                watcher: None,
+                observers,
             })
         }
     }
 
-    /// Creates a new iOS `DisplayLink` instance.
+    unsafe fn screen_link(screen: *mut Object, target: *mut Object) -> Option<ScreenDisplayLink> {
+        let mut display_link =
+            ScreenDisplayLink::with_screen_target_selector(screen, target, sel!(call:))?;
+        display_link.set_paused(YES);
+        display_link.add_to_main();
+        Some(display_link)
+    }
+
+    /// Creates a new macOS `DisplayLink` instance.
+    ///
+    /// This uses a `CVDisplayLink`, which calls back on its own thread. On macOS 14 and newer,
+    /// [`DisplayLink::with_backend`] can use the `CADisplayLink` of the main screen instead, which
+    /// calls back on the main run loop.
     ///
     /// macos _does_ require the callback to be `Send`.
-    pub fn new<F>(callback: F) -> Option<Self>
+    pub fn new<T, F>(callback: F) -> Option<Self>
     where
-        F: 'static + FnMut(TimePoint) + Send,
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
     {
         Self::new_impl(|| unsafe { RawDisplayLink::new() }, callback)
     }
 
-    pub fn on_display<F>(display_id: u32, callback: F) -> Option<Self>
+    pub fn on_display<T, F>(DisplayId(display_id): DisplayId, callback: F) -> Option<Self>
     where
-        F: 'static + FnMut(TimePoint) + Send,
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
     {
         Self::new_impl(
-            || unsafe { RawDisplayLink::on_display(display_id) },
+            || unsafe {
+                // Created for all active displays, so it can be moved later.
+                let mut display_link = RawDisplayLink::new()?;
+                if display_link.set_current_display(display_id) {
+                    Some(display_link)
+                } else {
+                    None
+                }
+            },
             callback,
         )
     }
 
-    pub fn set_current_display(&mut self, display_id: u32) {
-        unsafe { self.display_link.set_current_display(display_id) }
+    /// Creates a `DisplayLink` like [`DisplayLink::new`], whose callback is invoked on the main
+    /// thread, so it can touch AppKit directly.
+    ///
+    /// Frames from the `CVDisplayLink` are handed over to the main queue, and only the latest is
+    /// delivered if the main thread falls behind. One frame may still be delivered after pausing
+    /// the link.
+    pub fn on_main_thread<T, F>(callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp + Send,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::new_impl(
+            || unsafe { RawDisplayLink::new() },
+            DispatchQueue::main().deliver(callback),
+        )
     }
 
-    pub fn is_paused(&self) -> bool {
-        self.is_paused
+    /// Creates a `DisplayLink` like [`DisplayLink::new`], whose callback is invoked on `queue`,
+    /// with frames coalesced like [`DisplayLink::on_main_thread`].
+    pub fn on_dispatch_queue<T, F>(queue: DispatchQueue, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp + Send,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::new(queue.deliver(callback))
     }
 
-    pub fn pause(&mut self) -> Result<(), PauseError> {
-        if self.is_paused {
-            Err(PauseError::AlreadyPaused)
+    /// Adopts `display_link`, retaining it, and replaces its output callback with `callback`. The
+    /// link is stopped until it's resumed.
+    ///
+    /// # Safety
+    ///
+    /// `display_link` must be a valid `CVDisplayLinkRef`, which nothing else starts, stops or sets
+    /// the output callback of while the returned `DisplayLink` is alive.
+    pub unsafe fn from_raw<T, F>(display_link: *mut CVDisplayLink, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        if display_link.is_null() {
+            return None;
+        }
+        Self::new_impl(
+            || {
+                let mut display_link = RawDisplayLink::from_ptr(CVDisplayLinkRetain(display_link));
+                display_link.stop();
+                Some(display_link)
+            },
+            callback,
+        )
+    }
+
+    /// Returns the `CVDisplayLinkRef` or `CADisplayLink *` backing the link, depending on
+    /// `backend`. The link still owns it.
+    pub fn as_raw(&self) -> *mut c_void {
+        match &self.source {
+            Source::CoreVideo(display_link) => display_link.as_ptr().cast(),
+            Source::Screen { display_link, .. } => display_link.lock().unwrap().as_ptr().cast(),
+        }
+    }
+
+    /// Creates a `CVDisplayLink` for all active displays, starting on the main display.
+    ///
+    /// Unlike the `CADisplayLink` of a screen, the same link is moved by `set_display`,
+    /// so this suits windowed apps that retarget the link whenever their window changes screens.
+    pub fn with_active_displays<T, F>(callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::new_impl(|| unsafe { RawDisplayLink::new() }, callback)
+    }
+
+    /// Creates a `DisplayLink` that follows `window` as it moves between screens.
+    ///
+    /// This is a `CVDisplayLink` that's retargeted whenever the window posts
+    /// `NSWindowDidChangeScreenNotification`.
+    ///
+    /// # Safety
+    ///
+    /// `window` must be a valid `NSWindow *` that outlives the returned `DisplayLink`, and this
+    /// must be called on the main thread.
+    pub unsafe fn for_window<T, F>(window: *mut Object, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        let display_id =
+            cadisplaylink::display_for_window(window).or_else(|| cadisplaylink::main_display())?;
+        let mut link = Self::on_display(DisplayId(display_id), callback)?;
+        let on_change: Box<dyn FnMut(u32)> = match &link.source {
+            Source::CoreVideo(display_link) => {
+                let mut display_link = display_link.clone();
+                Box::new(move |display_id| {
+                    display_link.set_current_display(display_id);
+                })
+            }
+            Source::Screen { display, .. } => {
+                let display = Arc::clone(display);
+                Box::new(move |display_id| display.store(display_id, Ordering::Relaxed))
+            }
+        };
+        link.observer = Some(ScreenObserver::new(window, on_change));
+        Some(link)
+    }
+
+    /// Like [`DisplayLink::for_window`], for the window `view` is in. Returns `None` if `view`
+    /// isn't in a window.
+    ///
+    /// # Safety
+    ///
+    /// `view` must be a valid `NSView *` whose window outlives the returned `DisplayLink`, and this
+    /// must be called on the main thread.
+    pub unsafe fn for_view<T, F>(view: *mut Object, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        let window: *mut Object = msg_send![view, window];
+        if window.is_null() {
+            None
         } else {
-            unsafe {
-                self.display_link.stop();
-                self.is_paused = true;
+            Self::for_window(window, callback)
+        }
+    }
+
+    /// Creates a `DisplayLink` on the main display using `backend`, which must be
+    /// `Backend::CoreVideo`, or `Backend::CoreAnimation` on macOS 14 and newer.
+    pub fn with_backend<T, F>(backend: Backend, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        match backend {
+            Backend::CoreVideo => Self::new_impl(|| unsafe { RawDisplayLink::new() }, callback),
+            Backend::CoreAnimation if cadisplaylink::is_available() => unsafe {
+                let display_id = cadisplaylink::main_display()?;
+                let screen = cadisplaylink::screen_for_display(display_id)?;
+                Self::new_screen(screen, display_id, callback)
+            },
+            _ => None,
+        }
+    }
+
+    /// Links that follow a window can't be moved, as the window decides which display drives the
+    /// link.
+    pub fn set_display(&mut self, DisplayId(display_id): DisplayId) -> Result<(), SetDisplayError> {
+        if self.observer.is_some() {
+            return Err(SetDisplayError::Unsupported);
+        }
+        let is_running = self.runner.is_running();
+        match &mut self.source {
+            Source::CoreVideo(display_link) => unsafe {
+                if display_link.set_current_display(display_id) {
+                    Ok(())
+                } else {
+                    Err(SetDisplayError::NotConnected)
+                }
+            },
+            Source::Screen {
+                display_link,
+                target,
+                display,
+                ..
+            } => unsafe {
+                // A screen's link can't be moved, so replace it with one from the new screen.
+                // The target, and with it the callback, is kept.
+                let screen = cadisplaylink::screen_for_display(display_id)
+                    .ok_or(SetDisplayError::NotConnected)?;
+                let new_link =
+                    Self::screen_link(screen, *target).ok_or(SetDisplayError::NotConnected)?;
+                display.store(display_id, Ordering::Relaxed);
+                if is_running {
+                    new_link.set_paused(NO);
+                }
+                // The old link is invalidated on the main thread, which may be waiting for the
+                // lock, so it's dropped after the lock is released.
+                let old_link = mem::replace(&mut *display_link.lock().unwrap(), new_link);
+                drop(old_link);
                 Ok(())
+            },
+        }
+    }
+
+    /// Moves the link to the main display whenever the display driving it is disconnected, and
+    /// calls `on_rebind` on the main thread with the display it moved to.
+    ///
+    /// Links that follow a window can't be moved, as AppKit moves the window to another screen.
+    pub fn rebind_on_disconnect<F>(&mut self, mut on_rebind: F) -> Result<(), SetDisplayError>
+    where
+        F: 'static + FnMut(DisplayId) + Send,
+    {
+        if self.observer.is_some() {
+            return Err(SetDisplayError::Unsupported);
+        }
+        let mut rebind: Box<dyn FnMut(u32) -> Option<u32> + Send> = match &self.source {
+            Source::CoreVideo(display_link) => {
+                let mut display_link = display_link.clone();
+                Box::new(move |lost| unsafe {
+                    if display_link.current_display() != lost {
+                        return None;
+                    }
+                    let main = coregraphics::CGMainDisplayID();
+                    if display_link.set_current_display(main) {
+                        Some(main)
+                    } else {
+                        None
+                    }
+                })
+            }
+            Source::Screen {
+                display_link,
+                target,
+                display,
+                ..
+            } => {
+                struct Target(*mut Object);
+                // Only used on the main thread, where the watcher calls back.
+                unsafe impl Send for Target {}
+
+                let display_link = Arc::clone(display_link);
+                let target = Target(*target);
+                let display = Arc::clone(display);
+                Box::new(move |lost| unsafe {
+                    if display.load(Ordering::Relaxed) != lost {
+                        return None;
+                    }
+                    let main = cadisplaylink::main_display()?;
+                    let screen = cadisplaylink::screen_for_display(main)?;
+                    let new_link = Self::screen_link(screen, target.0)?;
+                    let mut display_link = display_link.lock().unwrap();
+                    if display_link.is_paused() == NO {
+                        new_link.set_paused(NO);
+                    }
+                    *display_link = new_link;
+                    display.store(main, Ordering::Relaxed);
+                    Some(main)
+                })
+            }
+        };
+        let watcher = Watcher::new(Box::new(move |event| {
+            if let DisplayEvent::Disconnected(DisplayId(lost)) = event {
+                if let Some(display) = rebind(lost) {
+                    on_rebind(DisplayId(display));
+                }
+            }
+        }))
+        .ok_or(SetDisplayError::Unsupported)?;
+        self.watcher = Some(watcher);
+        Ok(())
+    }
+
+    /// Moves a `CVDisplayLink` to the display the OpenGL context is drawing to.
+    ///
+    /// # Safety
+    ///
+    /// `cgl_context` must be a valid `CGLContextObj`, and `cgl_pixel_format` the valid
+    /// `CGLPixelFormatObj` it was created with.
+    pub unsafe fn set_display_from_gl_context(
+        &mut self,
+        cgl_context: *mut c_void,
+        cgl_pixel_format: *mut c_void,
+    ) -> Result<(), SetDisplayError> {
+        match &mut self.source {
+            Source::CoreVideo(display_link) if self.observer.is_none() => {
+                if display_link.set_current_display_from_gl_context(cgl_context, cgl_pixel_format) {
+                    Ok(())
+                } else {
+                    Err(SetDisplayError::NotConnected)
+                }
             }
+            _ => Err(SetDisplayError::Unsupported),
+        }
+    }
+
+    /// Translates `in_time` into another time base, filling in the fields selected by `flags`.
+    ///
+    /// Returns `None` if the `DisplayLink` is paused, isn't backed by a `CVDisplayLink`, or the
+    /// translation failed.
+    pub fn translate_time(&self, in_time: &CVTimeStamp, flags: u64) -> Option<CVTimeStamp> {
+        match &self.source {
+            Source::CoreVideo(display_link) if self.runner.is_running() => unsafe {
+                display_link.translate_time(in_time, flags)
+            },
+            _ => None,
+        }
+    }
+
+    pub fn display(&self) -> DisplayId {
+        match &self.source {
+            Source::CoreVideo(display_link) => DisplayId(unsafe { display_link.current_display() }),
+            Source::Screen { display, .. } => DisplayId(display.load(Ordering::Relaxed)),
+        }
+    }
+
+    pub fn on_refresh_rate_changed(&mut self, callback: RateCallback) {
+        self.observers.rate.set_callback(callback)
+    }
+
+    pub fn on_clock_discontinuity(&mut self, callback: DiscontinuityCallback) {
+        self.observers.set_discontinuity_callback(callback)
+    }
+
+    pub fn register_waker(&mut self, waker: Waker) {
+        self.observers.set_waker(waker)
+    }
+
+    pub fn backend(&self) -> Backend {
+        match self.source {
+            Source::CoreVideo(_) => Backend::CoreVideo,
+            Source::Screen { .. } => Backend::CoreAnimation,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        !self.runner.is_running()
+    }
+
+    pub(crate) fn park(&self) -> Park {
+        let runner = Arc::clone(&self.runner);
+        Park::new(move |gate| {
+            let runner = Arc::clone(&runner);
+            let gate = Arc::clone(gate);
+            // A `CVDisplayLink` waits for its callback to return when it's stopped.
+            DispatchQueue::global(QosClass::UserInteractive)
+                .run_async(move || runner.pause_if(|| !gate.is_open()))
+        })
+    }
+
+    /// macOS links keep running until they're dropped.
+    pub fn is_stopped(&self) -> bool {
+        false
+    }
+
+    /// Pauses the link while `window` is fully hidden, resuming it once part of the window is
+    /// visible again if it was running.
+    ///
+    /// # Safety
+    ///
+    /// `window` must be a valid `NSWindow *` that outlives the `DisplayLink`, and this must be
+    /// called on the main thread.
+    pub unsafe fn pause_while_occluded(&mut self, window: *mut Object) {
+        let runner = Arc::clone(&self.runner);
+        let on_occlusion =
+            Box::new(move |visible: bool| runner.suspend(Reason::Occluded, !visible));
+        self.occlusion = Some(OcclusionObserver::new(window, on_occlusion));
+    }
+
+    /// Pauses the link while the displays are asleep, resuming it once they wake up if it was
+    /// running, and calls `on_wake` on the main thread whenever they wake up.
+    pub fn pause_while_displays_sleep(&mut self, mut on_wake: Box<dyn FnMut() + Send>) {
+        let runner = Arc::clone(&self.runner);
+        self.sleep = Some(SessionObserver::for_display_sleep(Box::new(
+            move |asleep| {
+                runner.suspend(Reason::DisplaySleep, asleep);
+                if !asleep {
+                    on_wake()
+                }
+            },
+        )));
+    }
+
+    /// Pauses the link while the screen is locked, resuming it once it's unlocked if it was
+    /// running, and calls `on_lock` on the main thread with whether it's locked whenever that
+    /// changes.
+    pub fn pause_while_locked(&mut self, mut on_lock: Box<dyn FnMut(bool) + Send>) {
+        let runner = Arc::clone(&self.runner);
+        self.lock = Some(SessionObserver::for_screen_lock(Box::new(move |locked| {
+            runner.suspend(Reason::ScreenLocked, locked);
+            on_lock(locked)
+        })));
+    }
+
+    /// Calls `on_change` with whether the Mac is on battery power, right away and whenever its
+    /// power sources change.
+    pub fn on_power_source_changed(&mut self, on_change: power::OnChange) {
+        self.power = PowerObserver::new(on_change);
+    }
+
+    /// Calls `on_change` with the thermal state, right away and whenever it changes.
+    pub fn on_thermal_state_changed(&mut self, on_change: thermal::OnChange) {
+        self.thermal = Some(ThermalObserver::new(on_change));
+    }
+
+    pub fn pause(&mut self) -> Result<(), PauseError> {
+        if self.runner.pause() {
+            Ok(())
+        } else {
+            Err(PauseError::AlreadyPaused)
         }
     }
 
     pub fn resume(&mut self) -> Result<(), ResumeError> {
-        if !self.is_paused {
-            Err(ResumeError::AlreadyRunning)
+        if self.runner.resume() {
+            Ok(())
         } else {
-            unsafe {
-                self.display_link.start();
-                self.is_paused = false;
-                Ok(())
-            }
+            Err(ResumeError::AlreadyRunning)
         }
     }
 }
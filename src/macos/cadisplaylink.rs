@@ -0,0 +1,272 @@
+//! Apple docs: [CADisplayLink](https://developer.apple.com/documentation/quartzcore/cadisplaylink?language=objc)
+//!
+//! On macOS, a `CADisplayLink` is obtained from an `NSScreen`, and is only available on macOS 14
+//! and newer.
+
+use crate::{macos::dispatch, RefreshRateRange};
+use foreign_types::ForeignTypeRef;
+use objc::{
+    class, msg_send,
+    runtime::{Object, Sel, BOOL, NO},
+    sel, sel_impl,
+};
+use objc_foundation::{INSString, NSString};
+
+#[link(name = "AppKit", kind = "framework")]
+#[link(name = "Foundation", kind = "framework")]
+extern "C" {
+    #[allow(improper_ctypes)]
+    static NSRunLoopCommonModes: *mut NSString;
+    pub fn CACurrentMediaTime() -> f64;
+}
+
+pub enum CADisplayLink {}
+
+foreign_obj_type! {
+    type CType = CADisplayLink;
+    fn drop = invalidate;
+    pub struct DisplayLink;
+    pub struct DisplayLinkRef;
+}
+
+// `paused` is thread safe, and links are invalidated on the main thread, whichever thread drops
+// them.
+unsafe impl Send for DisplayLink {}
+
+/// Returns `true` if `NSScreen` can create a `CADisplayLink`, i.e. on macOS 14 and newer.
+pub fn is_available() -> bool {
+    let available: BOOL = unsafe {
+        msg_send![
+            class!(NSScreen),
+            instancesRespondToSelector: sel!(displayLinkWithTarget:selector:)
+        ]
+    };
+    available != NO
+}
+
+/// Returns the `NSScreen` whose `NSScreenNumber` is `display_id`, or `None` if no such screen is
+/// connected.
+///
+/// # Safety
+///
+/// `NSScreen` is only documented as safe to use on the main thread. Elsewhere, the screens may
+/// change while they're read.
+pub unsafe fn screen_for_display(display_id: u32) -> Option<*mut Object> {
+    let screens: *mut Object = msg_send![class!(NSScreen), screens];
+    let count: usize = msg_send![screens, count];
+    let key = NSString::from_str("NSScreenNumber");
+    (0..count)
+        .map(|i| -> *mut Object { msg_send![screens, objectAtIndex: i] })
+        .find(|&screen| display_for_screen(screen, &key) == Some(display_id))
+}
+
+/// Returns the `CGDirectDisplayID` of `[NSScreen mainScreen]`.
+///
+/// # Safety
+///
+/// `NSScreen` is only documented as safe to use on the main thread. Elsewhere, the screens may
+/// change while they're read.
+pub unsafe fn main_display() -> Option<u32> {
+    let screen: *mut Object = msg_send![class!(NSScreen), mainScreen];
+    if screen.is_null() {
+        None
+    } else {
+        display_for_ns_screen(screen)
+    }
+}
+
+/// Returns the `localizedName` of the `NSScreen` whose `NSScreenNumber` is `display_id`, which is
+/// only available on macOS 10.15 and newer.
+///
+/// # Safety
+///
+/// `NSScreen` is only documented as safe to use on the main thread. Elsewhere, the screens may
+/// change while they're read.
+pub unsafe fn screen_name(display_id: u32) -> Option<String> {
+    let screen = screen_for_display(display_id)?;
+    let responds: BOOL = msg_send![screen, respondsToSelector: sel!(localizedName)];
+    if responds == NO {
+        return None;
+    }
+    let name: *mut NSString = msg_send![screen, localizedName];
+    name.as_ref().map(|name| name.as_str().to_owned())
+}
+
+/// Returns the refresh rates the `NSScreen` whose `NSScreenNumber` is `display_id` can run at,
+/// from its `minimumRefreshInterval` and `maximumRefreshInterval`, which are only available on
+/// macOS 12 and newer.
+///
+/// # Safety
+///
+/// `NSScreen` is only documented as safe to use on the main thread. Elsewhere, the screens may
+/// change while they're read.
+pub unsafe fn refresh_rate_range(display_id: u32) -> Option<RefreshRateRange> {
+    let screen = screen_for_display(display_id)?;
+    let responds: BOOL = msg_send![screen, respondsToSelector: sel!(minimumRefreshInterval)];
+    if responds == NO {
+        return None;
+    }
+    let min_interval: f64 = msg_send![screen, minimumRefreshInterval];
+    let max_interval: f64 = msg_send![screen, maximumRefreshInterval];
+    // Fixed rate displays report the same interval for both.
+    if min_interval > 0.0 && max_interval > min_interval {
+        Some(RefreshRateRange {
+            min: 1.0 / max_interval,
+            max: 1.0 / min_interval,
+        })
+    } else {
+        None
+    }
+}
+
+/// Returns the `CGDirectDisplayID` of the screen `window` is on, or `None` if it's offscreen.
+///
+/// # Safety
+///
+/// `window` must be a valid `NSWindow *`.
+pub unsafe fn display_for_window(window: *mut Object) -> Option<u32> {
+    let screen: *mut Object = msg_send![window, screen];
+    if screen.is_null() {
+        None
+    } else {
+        display_for_ns_screen(screen)
+    }
+}
+
+/// Returns the `CGDirectDisplayID` of `screen`, an `NSScreen`, from its `NSScreenNumber`.
+///
+/// # Safety
+///
+/// `screen` must be a valid `NSScreen *`.
+pub unsafe fn display_for_ns_screen(screen: *mut Object) -> Option<u32> {
+    display_for_screen(screen, &NSString::from_str("NSScreenNumber"))
+}
+
+/// Returns the `CGDirectDisplayID` of `[NSScreen screens][index]`.
+///
+/// # Safety
+///
+/// `NSScreen` is only documented as safe to use on the main thread. Elsewhere, the screens may
+/// change while they're read.
+pub unsafe fn display_for_screen_index(index: usize) -> Option<u32> {
+    let screens: *mut Object = msg_send![class!(NSScreen), screens];
+    let count: usize = msg_send![screens, count];
+    if index < count {
+        display_for_ns_screen(msg_send![screens, objectAtIndex: index])
+    } else {
+        None
+    }
+}
+
+unsafe fn display_for_screen(screen: *mut Object, key: &NSString) -> Option<u32> {
+    let description: *mut Object = msg_send![screen, deviceDescription];
+    let number: *mut Object = msg_send![description, objectForKey: key];
+    if number.is_null() {
+        None
+    } else {
+        Some(msg_send![number, unsignedIntValue])
+    }
+}
+
+impl DisplayLink {
+    /// Apple docs: [displayLinkWithTarget:selector:](https://developer.apple.com/documentation/appkit/nsscreen/4190632-displaylinkwithtarget?language=objc)
+    ///
+    /// # Safety
+    ///
+    /// `screen` must be a valid `NSScreen *`, and `object` must respond to `selector`, taking the
+    /// link, for as long as the link isn't invalidated. The link retains `object` until then.
+    pub unsafe fn with_screen_target_selector(
+        screen: *mut Object,
+        object: *mut Object,
+        selector: Sel,
+    ) -> Option<Self> {
+        let display_link: *mut CADisplayLink =
+            msg_send![screen, displayLinkWithTarget:object selector:selector];
+        if display_link.is_null() {
+            None
+        } else {
+            // The returned link is autoreleased.
+            let display_link: *mut CADisplayLink = msg_send![display_link, retain];
+            Some(foreign_types::ForeignType::from_ptr(display_link))
+        }
+    }
+}
+
+impl DisplayLinkRef {
+    /// Apple docs: [addToRunLoop:forMode:](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1621323-addtorunloop?language=objc)
+    ///
+    /// # Safety
+    ///
+    /// `run_loop` must be a valid `NSRunLoop *` and `mode` a valid `NSString *`, and the link's
+    /// target must be safe to call on `run_loop`'s thread.
+    pub unsafe fn add_to_run_loop_for_mode(&mut self, run_loop: *mut Object, mode: *mut NSString) {
+        msg_send![
+            self,
+            addToRunLoop: run_loop
+            forMode: mode
+        ]
+    }
+
+    /// Calls `self.add_to_run_loop_for_mode([NSRunLoop mainRunLoop], NSRunLoopCommonModes)`
+    ///
+    /// # Safety
+    ///
+    /// The link's target must be safe to call on the main thread.
+    pub unsafe fn add_to_main(&mut self) {
+        self.add_to_run_loop_for_mode(
+            msg_send![class!(NSRunLoop), mainRunLoop],
+            NSRunLoopCommonModes,
+        )
+    }
+
+    /// Apple docs: [paused](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1621229-paused?language=objc)
+    ///
+    /// This is documented as being thread safe.
+    ///
+    /// # Safety
+    ///
+    /// This only messages the link, which is valid for as long as it's borrowed.
+    pub unsafe fn set_paused(&self, paused: BOOL) {
+        msg_send![self, setPaused: paused]
+    }
+
+    /// Apple docs: [paused](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1621229-paused?language=objc)
+    ///
+    /// This is documented as being thread safe.
+    ///
+    /// # Safety
+    ///
+    /// This only messages the link, which is valid for as long as it's borrowed.
+    pub unsafe fn is_paused(&self) -> BOOL {
+        msg_send![self, isPaused]
+    }
+
+    /// Apple docs: [invalidate](https://developer.apple.com/documentation/quartzcore/cadisplaylink/1621293-invalidate?language=objc)
+    ///
+    /// Links call back on the main run loop, so the link is invalidated there, without waiting
+    /// for the main thread. Its target may be called once more in the meantime.
+    pub fn invalidate(&self) {
+        let link = Invalidate(unsafe { msg_send![self, retain] });
+        dispatch::async_main(move || link.run())
+    }
+}
+
+/// A retained link to invalidate and release on the main thread.
+struct Invalidate(*mut CADisplayLink);
+
+// It's only messaged on the main thread.
+unsafe impl Send for Invalidate {}
+
+impl Invalidate {
+    fn run(self) {
+        unsafe {
+            let () = msg_send![self.0, invalidate];
+            let () = msg_send![self.0, release];
+        }
+    }
+}
+
+unsafe fn invalidate(p: *mut CADisplayLink) {
+    DisplayLinkRef::from_ptr(p).invalidate();
+    let () = msg_send![p, release];
+}
@@ -0,0 +1,93 @@
+//! Apple docs: [Quartz Display Services](https://developer.apple.com/documentation/coregraphics/quartz_display_services?language=objc)
+
+use std::ffi::c_void;
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CGPoint {
+    pub x: f64,
+    pub y: f64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CGSize {
+    pub width:  f64,
+    pub height: f64,
+}
+
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CGRect {
+    pub origin: CGPoint,
+    pub size:   CGSize,
+}
+
+pub type CGDisplayModeRef = *mut c_void;
+
+pub type CGDisplayReconfigurationCallBack =
+    extern "C" fn(display: u32, flags: u32, user_info: *mut c_void);
+
+/// Apple docs: [CGDisplayChangeSummaryFlags](https://developer.apple.com/documentation/coregraphics/cgdisplaychangesummaryflags?language=objc)
+pub const CG_DISPLAY_BEGIN_CONFIGURATION_FLAG: u32 = 1 << 0;
+pub const CG_DISPLAY_MOVED_FLAG: u32 = 1 << 1;
+pub const CG_DISPLAY_SET_MAIN_FLAG: u32 = 1 << 2;
+pub const CG_DISPLAY_SET_MODE_FLAG: u32 = 1 << 3;
+pub const CG_DISPLAY_ADD_FLAG: u32 = 1 << 4;
+pub const CG_DISPLAY_REMOVE_FLAG: u32 = 1 << 5;
+pub const CG_DISPLAY_ENABLED_FLAG: u32 = 1 << 8;
+pub const CG_DISPLAY_DISABLED_FLAG: u32 = 1 << 9;
+pub const CG_DISPLAY_MIRROR_FLAG: u32 = 1 << 10;
+pub const CG_DISPLAY_UNMIRROR_FLAG: u32 = 1 << 11;
+pub const CG_DISPLAY_DESKTOP_SHAPE_CHANGED_FLAG: u32 = 1 << 12;
+
+#[link(name = "CoreGraphics", kind = "framework")]
+extern "C" {
+    pub fn CGMainDisplayID() -> u32;
+    pub fn CGDisplayMirrorsDisplay(display: u32) -> u32;
+    /// Returns a retained `id<MTLDevice>`, or nil if there's none.
+    pub fn CGDirectDisplayCopyCurrentMetalDevice(display: u32) -> *mut c_void;
+    pub fn CGGetActiveDisplayList(max_displays: u32, displays: *mut u32, count: *mut u32) -> i32;
+    pub fn CGDisplayBounds(display: u32) -> CGRect;
+    pub fn CGDisplayCopyDisplayMode(display: u32) -> CGDisplayModeRef;
+    pub fn CGDisplayModeGetRefreshRate(mode: CGDisplayModeRef) -> f64;
+    pub fn CGDisplayModeRelease(mode: CGDisplayModeRef);
+    pub fn CGDisplayRegisterReconfigurationCallback(
+        callback: CGDisplayReconfigurationCallBack,
+        user_info: *mut c_void,
+    ) -> i32;
+    pub fn CGDisplayRemoveReconfigurationCallback(
+        callback: CGDisplayReconfigurationCallBack,
+        user_info: *mut c_void,
+    ) -> i32;
+}
+
+/// Returns the active displays, with the main display first.
+pub fn active_displays() -> Vec<u32> {
+    let mut count = 0;
+    if unsafe { CGGetActiveDisplayList(0, std::ptr::null_mut(), &mut count) } != 0 {
+        return Vec::new();
+    }
+    let mut displays = vec![0; count as usize];
+    if unsafe { CGGetActiveDisplayList(count, displays.as_mut_ptr(), &mut count) } != 0 {
+        return Vec::new();
+    }
+    displays.truncate(count as usize);
+    let main = unsafe { CGMainDisplayID() };
+    displays.sort_by_key(|&display| display != main);
+    displays
+}
+
+/// Returns the refresh rate of the current mode of `display`, or `None` if it isn't known, as is
+/// the case for some built-in displays.
+pub fn refresh_rate(display: u32) -> Option<f64> {
+    unsafe {
+        let mode = CGDisplayCopyDisplayMode(display);
+        if mode.is_null() {
+            return None;
+        }
+        let rate = CGDisplayModeGetRefreshRate(mode);
+        CGDisplayModeRelease(mode);
+        Some(rate).filter(|&rate| rate > 0.0)
+    }
+}
@@ -17,6 +17,9 @@ foreign_type! {
     pub struct DisplayLinkRef;
 }
 
+// CoreVideo functions can be called from any thread.
+unsafe impl Send for DisplayLink {}
+
 impl Debug for DisplayLink {
     fn fmt(&self, formatter: &mut Formatter) -> Result {
         formatter
@@ -26,10 +29,24 @@ impl Debug for DisplayLink {
     }
 }
 
-#[doc(hidden)]
-pub enum Unimplemented {}
+/// Apple docs: [CVSMPTETime](https://developer.apple.com/documentation/corevideo/cvsmptetime?language=objc)
+#[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CVSMPTETime {
+    pub subframes:        i16,
+    pub subframe_divisor: i16,
+    pub counter:          u32,
+    pub type_:            u32,
+    pub flags:            u32,
+    pub hours:            i16,
+    pub minutes:          i16,
+    pub seconds:          i16,
+    pub frames:           i16,
+}
 
+/// Apple docs: [CVTimeStamp](https://developer.apple.com/documentation/corevideo/cvtimestamp?language=objc)
 #[repr(C)]
+#[derive(Clone, Copy, Debug, Default)]
 pub struct CVTimeStamp {
     pub version:              u32,
     pub video_timescale:      i32,
@@ -37,11 +54,23 @@ pub struct CVTimeStamp {
     pub host_time:            u64,
     pub rate_scalar:          f64,
     pub video_refresh_period: i64,
-
-    #[doc(hidden)]
-    _unimplemented: Unimplemented,
+    pub smpte_time:           CVSMPTETime,
+    pub flags:                u64,
+    pub reserved:             u64,
 }
 
+/// Apple docs: [CVTimeStampFlags](https://developer.apple.com/documentation/corevideo/cvtimestampflags?language=objc)
+pub const CV_TIME_STAMP_VIDEO_TIME_VALID: u64 = 1 << 0;
+pub const CV_TIME_STAMP_HOST_TIME_VALID: u64 = 1 << 1;
+pub const CV_TIME_STAMP_SMPTE_TIME_VALID: u64 = 1 << 2;
+pub const CV_TIME_STAMP_VIDEO_REFRESH_PERIOD_VALID: u64 = 1 << 3;
+pub const CV_TIME_STAMP_RATE_SCALAR_VALID: u64 = 1 << 4;
+pub const CV_TIME_STAMP_TOP_FIELD: u64 = 1 << 16;
+pub const CV_TIME_STAMP_BOTTOM_FIELD: u64 = 1 << 17;
+pub const CV_TIME_STAMP_VIDEO_HOST_TIME_VALID: u64 =
+    CV_TIME_STAMP_VIDEO_TIME_VALID | CV_TIME_STAMP_HOST_TIME_VALID;
+pub const CV_TIME_STAMP_IS_INTERLACED: u64 = CV_TIME_STAMP_TOP_FIELD | CV_TIME_STAMP_BOTTOM_FIELD;
+
 pub type CVDisplayLinkOutputCallback = unsafe extern "C" fn(
     display_link_out: *mut CVDisplayLink,
     in_now_timestamp: *const CVTimeStamp,
@@ -71,14 +100,30 @@ extern "C" {
         display_link: &mut DisplayLinkRef,
         display_id: u32,
     ) -> i32;
+    pub fn CVDisplayLinkSetCurrentCGDisplayFromOpenGLContext(
+        display_link: &mut DisplayLinkRef,
+        cgl_context: *mut c_void,
+        cgl_pixel_format: *mut c_void,
+    ) -> i32;
+    pub fn CVDisplayLinkGetCurrentCGDisplay(display_link: &DisplayLinkRef) -> u32;
+    pub fn CVDisplayLinkTranslateTime(
+        display_link: &DisplayLinkRef,
+        in_time: *const CVTimeStamp,
+        out_time: *mut CVTimeStamp,
+    ) -> i32;
     pub fn CVDisplayLinkStart(display_link: &mut DisplayLinkRef) -> i32;
     pub fn CVDisplayLinkStop(display_link: &mut DisplayLinkRef) -> i32;
+    pub fn CVGetCurrentHostTime() -> u64;
+    pub fn CVGetHostClockFrequency() -> f64;
     pub fn CVDisplayLinkRelease(display_link: *mut CVDisplayLink);
     pub fn CVDisplayLinkRetain(display_link: *mut CVDisplayLink) -> *mut CVDisplayLink;
 }
 
 impl DisplayLink {
     /// Apple docs: [CVDisplayLinkCreateWithActiveCGDisplays](https://developer.apple.com/documentation/corevideo/1456863-cvdisplaylinkcreatewithactivecgd?language=objc)
+    ///
+    /// The link can be moved to any of the active displays with
+    /// [`DisplayLinkRef::set_current_display`].
     pub unsafe fn new() -> Option<Self> {
         let mut display_link: *mut CVDisplayLink = 0 as _;
         let code = CVDisplayLinkCreateWithActiveCGDisplays(&mut display_link);
@@ -112,8 +157,44 @@ impl DisplayLinkRef {
     }
 
     /// Apple docs: [CVDisplayLinkSetCurrentCGDisplay](https://developer.apple.com/documentation/corevideo/1456768-cvdisplaylinksetcurrentcgdisplay?language=objc)
-    pub unsafe fn set_current_display(&mut self, display_id: u32) {
-        assert_eq!(CVDisplayLinkSetCurrentCGDisplay(self, display_id), 0);
+    ///
+    /// Returns `false` if `display_id` isn't one of the displays the link was created with, e.g.
+    /// a display that was disconnected.
+    pub unsafe fn set_current_display(&mut self, display_id: u32) -> bool {
+        CVDisplayLinkSetCurrentCGDisplay(self, display_id) == 0
+    }
+
+    /// Apple docs: [CVDisplayLinkSetCurrentCGDisplayFromOpenGLContext](https://developer.apple.com/documentation/corevideo/1457090-cvdisplaylinksetcurrentcgdisplay?language=objc)
+    ///
+    /// Returns `false` if the context's display isn't one of the displays the link was created
+    /// with.
+    pub unsafe fn set_current_display_from_gl_context(
+        &mut self,
+        cgl_context: *mut c_void,
+        cgl_pixel_format: *mut c_void,
+    ) -> bool {
+        CVDisplayLinkSetCurrentCGDisplayFromOpenGLContext(self, cgl_context, cgl_pixel_format) == 0
+    }
+
+    /// Apple docs: [CVDisplayLinkGetCurrentCGDisplay](https://developer.apple.com/documentation/corevideo/1456702-cvdisplaylinkgetcurrentcgdisplay?language=objc)
+    pub unsafe fn current_display(&self) -> u32 {
+        CVDisplayLinkGetCurrentCGDisplay(self)
+    }
+
+    /// Apple docs: [CVDisplayLinkTranslateTime](https://developer.apple.com/documentation/corevideo/1456594-cvdisplaylinktranslatetime?language=objc)
+    ///
+    /// `flags` selects which fields of the returned `CVTimeStamp` should be filled in. Returns
+    /// `None` if the translation failed, which happens if the display link isn't running.
+    pub unsafe fn translate_time(&self, in_time: &CVTimeStamp, flags: u64) -> Option<CVTimeStamp> {
+        let mut out_time = CVTimeStamp {
+            flags,
+            ..Default::default()
+        };
+        if CVDisplayLinkTranslateTime(self, in_time, &mut out_time) == 0 {
+            Some(out_time)
+        } else {
+            None
+        }
     }
 
     /// Apple docs: [CVDisplayLinkStart](https://developer.apple.com/documentation/corevideo/1457193-cvdisplaylinkstart?language=objc)
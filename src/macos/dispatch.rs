@@ -0,0 +1,156 @@
+//! Apple docs: [Dispatch](https://developer.apple.com/documentation/dispatch?language=objc)
+
+use crate::{macos::main_thread::is_main_thread, FrameTime, FrameTimestamp};
+use std::{
+    ffi::c_void,
+    sync::{Arc, Mutex},
+};
+
+#[repr(C)]
+pub struct DispatchObject {
+    _private: [u8; 0],
+}
+
+// libdispatch is part of libSystem, which is always linked.
+extern "C" {
+    static _dispatch_main_q: DispatchObject;
+    fn dispatch_get_global_queue(identifier: isize, flags: usize) -> *const DispatchObject;
+    fn dispatch_retain(object: *const DispatchObject);
+    fn dispatch_release(object: *const DispatchObject);
+    fn dispatch_async_f(
+        queue: *const DispatchObject,
+        context: *mut c_void,
+        work: extern "C" fn(*mut c_void),
+    );
+}
+
+/// Runs `work` on the main thread: right away on the main thread, or else once the main queue gets
+/// to it, without waiting for it, as the main thread may be waiting for this one.
+pub(crate) fn async_main<F: 'static + FnOnce() + Send>(work: F) {
+    if is_main_thread() {
+        work()
+    } else {
+        DispatchQueue::main().run_async(work)
+    }
+}
+
+/// Apple docs: [dispatch_qos_class_t](https://developer.apple.com/documentation/dispatch/dispatch_qos_class_t?language=objc)
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum QosClass {
+    UserInteractive,
+    UserInitiated,
+    Default,
+    Utility,
+    Background,
+}
+
+impl QosClass {
+    fn raw(self) -> isize {
+        match self {
+            QosClass::UserInteractive => 0x21,
+            QosClass::UserInitiated => 0x19,
+            QosClass::Default => 0x15,
+            QosClass::Utility => 0x11,
+            QosClass::Background => 0x09,
+        }
+    }
+}
+
+/// A dispatch queue for a `DisplayLink` to deliver frames on, rather than the thread the frames
+/// arrive on. See
+/// [`DisplayLinkBuilder::dispatch_queue`](crate::DisplayLinkBuilder::dispatch_queue).
+#[derive(Debug, PartialEq, Eq)]
+pub struct DispatchQueue(*const DispatchObject);
+
+// Dispatch queues can be used from any thread.
+unsafe impl Send for DispatchQueue {}
+unsafe impl Sync for DispatchQueue {}
+
+impl DispatchQueue {
+    /// The main queue, which runs on the main thread.
+    pub fn main() -> Self {
+        DispatchQueue(unsafe { &_dispatch_main_q })
+    }
+
+    /// The global concurrent queue for `qos`.
+    pub fn global(qos: QosClass) -> Self {
+        DispatchQueue(unsafe { dispatch_get_global_queue(qos.raw(), 0) })
+    }
+
+    /// Adopts an app's own queue, retaining it.
+    ///
+    /// # Safety
+    ///
+    /// `queue` must be a valid `dispatch_queue_t`.
+    pub unsafe fn from_raw(queue: *mut c_void) -> Self {
+        let queue = queue as *const DispatchObject;
+        dispatch_retain(queue);
+        DispatchQueue(queue)
+    }
+
+    /// Runs `work` on this queue, without waiting for it.
+    pub(crate) fn run_async<F: 'static + FnOnce() + Send>(&self, work: F) {
+        extern "C" fn run<F: FnOnce()>(context: *mut c_void) {
+            let work = unsafe { Box::from_raw(context as *mut F) };
+            work()
+        }
+
+        let context = Box::into_raw(Box::new(work)) as *mut c_void;
+        unsafe { dispatch_async_f(self.0, context, run::<F>) }
+    }
+
+    /// Wraps `callback` so it's invoked on this queue.
+    ///
+    /// Frames are coalesced: if the queue falls behind, frames that arrive before the last one
+    /// was delivered replace it, so the callback only ever sees the latest frame.
+    pub(crate) fn deliver<T, F>(self, callback: F) -> impl FnMut(FrameTime<T>) + Send
+    where
+        T: FrameTimestamp + Send,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        let pending = Arc::new(Pending {
+            queue:    self,
+            frame:    Mutex::new(None),
+            callback: Mutex::new(callback),
+        });
+        move |frame| {
+            let scheduled = pending.frame.lock().unwrap().replace(frame).is_some();
+            if !scheduled {
+                let context = Arc::into_raw(Arc::clone(&pending)) as *mut c_void;
+                unsafe { dispatch_async_f(pending.queue.0, context, deliver::<T, F>) }
+            }
+        }
+    }
+}
+
+impl Clone for DispatchQueue {
+    fn clone(&self) -> Self {
+        unsafe { dispatch_retain(self.0) };
+        DispatchQueue(self.0)
+    }
+}
+
+impl Drop for DispatchQueue {
+    // Retaining and releasing the main and global queues has no effect.
+    fn drop(&mut self) {
+        unsafe { dispatch_release(self.0) }
+    }
+}
+
+struct Pending<T, F> {
+    queue:    DispatchQueue,
+    /// The latest frame that hasn't been delivered yet.
+    frame:    Mutex<Option<FrameTime<T>>>,
+    callback: Mutex<F>,
+}
+
+extern "C" fn deliver<T, F>(context: *mut c_void)
+where
+    F: FnMut(FrameTime<T>),
+{
+    let pending = unsafe { Arc::from_raw(context as *const Pending<T, F>) };
+    let frame = pending.frame.lock().unwrap().take();
+    if let Some(frame) = frame {
+        (pending.callback.lock().unwrap())(frame)
+    }
+}
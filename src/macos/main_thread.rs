@@ -0,0 +1,55 @@
+use crate::{DisplayLink, FrameTime};
+use objc::{
+    class, msg_send,
+    runtime::{BOOL, NO},
+    sel, sel_impl,
+};
+use std::marker::PhantomData;
+
+/// Lets a callback that isn't `Send` into a link whose callbacks are only ever invoked, and
+/// dropped, on the main thread.
+struct MainThreadOnly<F>(F);
+
+unsafe impl<F> Send for MainThreadOnly<F> {}
+
+pub(crate) fn is_main_thread() -> bool {
+    let is_main: BOOL = unsafe { msg_send![class!(NSThread), isMainThread] };
+    is_main != NO
+}
+
+/// A `DisplayLink` confined to the main thread, whose callback doesn't need to be `Send`, so it
+/// can capture `Rc`s and AppKit objects, like callbacks on iOS.
+///
+/// Frames are delivered like [`DisplayLink::on_main_thread`].
+#[derive(Debug)]
+pub struct MainThreadDisplayLink {
+    link:         DisplayLink,
+    _main_thread: PhantomData<*mut ()>,
+}
+
+impl MainThreadDisplayLink {
+    /// Creates a paused `MainThreadDisplayLink`, invoking `callback` on the main thread with the
+    /// `FrameTime` of each refresh.
+    ///
+    /// Returns `None` if this isn't called on the main thread, or if a `DisplayLink` could not be
+    /// created.
+    pub fn new<F>(callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime),
+    {
+        if !is_main_thread() {
+            return None;
+        }
+        let mut callback = MainThreadOnly(callback);
+        let link = DisplayLink::on_main_thread(move |frame| (callback.0)(frame))?;
+        Some(MainThreadDisplayLink {
+            link,
+            _main_thread: PhantomData,
+        })
+    }
+
+    /// Returns the underlying `DisplayLink`, e.g. to pause and resume it.
+    pub fn link(&self) -> &DisplayLink {
+        &self.link
+    }
+}
@@ -0,0 +1,164 @@
+//! Apple docs: [NSWindowDidChangeScreenNotification](https://developer.apple.com/documentation/appkit/nswindowdidchangescreennotification?language=objc),
+//! [NSWindowDidChangeOcclusionStateNotification](https://developer.apple.com/documentation/appkit/nswindowdidchangeocclusionstatenotification?language=objc)
+
+use crate::macos::cadisplaylink;
+use objc::{
+    class,
+    declare::ClassDecl,
+    msg_send,
+    runtime::{Object, Sel},
+    sel, sel_impl,
+};
+use objc_foundation::NSString;
+use std::{ffi::c_void, sync::Once};
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    #[allow(improper_ctypes)]
+    static NSWindowDidChangeScreenNotification: *mut NSString;
+    #[allow(improper_ctypes)]
+    static NSWindowDidChangeOcclusionStateNotification: *mut NSString;
+}
+
+/// Apple docs: [NSWindowOcclusionStateVisible](https://developer.apple.com/documentation/appkit/nswindowocclusionstate/nswindowocclusionstatevisible?language=objc)
+const NS_WINDOW_OCCLUSION_STATE_VISIBLE: usize = 1 << 1;
+
+type OnChange = Box<dyn FnMut(u32)>;
+
+extern "C" fn screen_did_change(this: &Object, _: Sel, notification: *mut Object) {
+    unsafe {
+        let on_change: *mut c_void = *this.get_ivar("_data");
+        let on_change = &mut *(on_change as *mut OnChange);
+        let window: *mut Object = msg_send![notification, object];
+        if let Some(display_id) = cadisplaylink::display_for_window(window) {
+            on_change(display_id)
+        }
+    }
+}
+
+/// Calls back with the new `CGDirectDisplayID` whenever a window moves to another screen.
+///
+/// Notifications are posted on the main thread.
+#[derive(Debug)]
+pub(crate) struct ScreenObserver {
+    observer:  *mut Object,
+    on_change: *mut OnChange,
+}
+
+impl ScreenObserver {
+    /// `window` must be a valid `NSWindow *`.
+    pub unsafe fn new(window: *mut Object, on_change: OnChange) -> Self {
+        static OBSERVER_CLASS_CREATOR: Once = Once::new();
+        OBSERVER_CLASS_CREATOR.call_once(|| {
+            let mut decl = ClassDecl::new("DisplayLinkScreenObserver", class!(NSObject)).unwrap();
+            decl.add_ivar::<*mut c_void>("_data");
+            decl.add_method(
+                sel!(screenDidChange:),
+                screen_did_change as extern "C" fn(&Object, Sel, *mut Object),
+            );
+            decl.register();
+        });
+
+        let on_change = Box::into_raw(Box::new(on_change));
+        let observer: *mut Object = msg_send![class!(DisplayLinkScreenObserver), alloc];
+        let observer: *mut Object = msg_send![observer, init];
+        (*observer).set_ivar::<*mut c_void>("_data", on_change as *mut c_void);
+        let center: *mut Object = msg_send![class!(NSNotificationCenter), defaultCenter];
+        let () = msg_send![
+            center,
+            addObserver: observer
+            selector: sel!(screenDidChange:)
+            name: NSWindowDidChangeScreenNotification
+            object: window
+        ];
+        ScreenObserver {
+            observer,
+            on_change,
+        }
+    }
+}
+
+impl Drop for ScreenObserver {
+    fn drop(&mut self) {
+        unsafe {
+            let center: *mut Object = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center, removeObserver: self.observer];
+            let () = msg_send![self.observer, release];
+            drop(Box::from_raw(self.on_change));
+        }
+    }
+}
+
+type OnOcclusion = Box<dyn FnMut(bool)>;
+
+/// Whether any part of `window` is visible.
+unsafe fn is_visible(window: *mut Object) -> bool {
+    let state: usize = msg_send![window, occlusionState];
+    state & NS_WINDOW_OCCLUSION_STATE_VISIBLE != 0
+}
+
+extern "C" fn occlusion_state_did_change(this: &Object, _: Sel, notification: *mut Object) {
+    unsafe {
+        let on_occlusion: *mut c_void = *this.get_ivar("_data");
+        let on_occlusion = &mut *(on_occlusion as *mut OnOcclusion);
+        let window: *mut Object = msg_send![notification, object];
+        on_occlusion(is_visible(window))
+    }
+}
+
+/// Calls back with whether any part of a window is visible whenever that changes, e.g. when it's
+/// covered by other windows, minimized, or on another space.
+///
+/// Notifications are posted on the main thread.
+#[derive(Debug)]
+pub(crate) struct OcclusionObserver {
+    observer:     *mut Object,
+    on_occlusion: *mut OnOcclusion,
+}
+
+impl OcclusionObserver {
+    /// `window` must be a valid `NSWindow *`. `on_occlusion` is also called right away, with
+    /// whether the window is visible now.
+    pub unsafe fn new(window: *mut Object, mut on_occlusion: OnOcclusion) -> Self {
+        static OBSERVER_CLASS_CREATOR: Once = Once::new();
+        OBSERVER_CLASS_CREATOR.call_once(|| {
+            let mut decl =
+                ClassDecl::new("DisplayLinkOcclusionObserver", class!(NSObject)).unwrap();
+            decl.add_ivar::<*mut c_void>("_data");
+            decl.add_method(
+                sel!(occlusionStateDidChange:),
+                occlusion_state_did_change as extern "C" fn(&Object, Sel, *mut Object),
+            );
+            decl.register();
+        });
+
+        on_occlusion(is_visible(window));
+        let on_occlusion = Box::into_raw(Box::new(on_occlusion));
+        let observer: *mut Object = msg_send![class!(DisplayLinkOcclusionObserver), alloc];
+        let observer: *mut Object = msg_send![observer, init];
+        (*observer).set_ivar::<*mut c_void>("_data", on_occlusion as *mut c_void);
+        let center: *mut Object = msg_send![class!(NSNotificationCenter), defaultCenter];
+        let () = msg_send![
+            center,
+            addObserver: observer
+            selector: sel!(occlusionStateDidChange:)
+            name: NSWindowDidChangeOcclusionStateNotification
+            object: window
+        ];
+        OcclusionObserver {
+            observer,
+            on_occlusion,
+        }
+    }
+}
+
+impl Drop for OcclusionObserver {
+    fn drop(&mut self) {
+        unsafe {
+            let center: *mut Object = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center, removeObserver: self.observer];
+            let () = msg_send![self.observer, release];
+            drop(Box::from_raw(self.on_occlusion));
+        }
+    }
+}
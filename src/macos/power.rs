@@ -0,0 +1,97 @@
+//! Apple docs: [IOPSNotificationCreateRunLoopSource](https://developer.apple.com/documentation/iokit/1523868-iopsnotificationcreaterunloopsou?language=objc),
+//! [IOPSGetProvidingPowerSourceType](https://developer.apple.com/documentation/iokit/1523834-iopsgetprovidingpowersourcetype?language=objc)
+
+use objc::{
+    msg_send,
+    runtime::{Object, BOOL, NO},
+    sel, sel_impl,
+};
+use objc_foundation::{INSString, NSString};
+use std::ffi::c_void;
+
+type IOPowerSourceCallbackType = extern "C" fn(context: *mut c_void);
+
+#[link(name = "IOKit", kind = "framework")]
+extern "C" {
+    fn IOPSNotificationCreateRunLoopSource(
+        callback: IOPowerSourceCallbackType,
+        context: *mut c_void,
+    ) -> *mut c_void;
+    fn IOPSCopyPowerSourcesInfo() -> *mut c_void;
+    fn IOPSGetProvidingPowerSourceType(snapshot: *mut c_void) -> *mut Object;
+}
+
+#[link(name = "CoreFoundation", kind = "framework")]
+extern "C" {
+    static kCFRunLoopCommonModes: *const c_void;
+    fn CFRunLoopGetMain() -> *mut c_void;
+    fn CFRunLoopAddSource(run_loop: *mut c_void, source: *mut c_void, mode: *const c_void);
+    fn CFRunLoopSourceInvalidate(source: *mut c_void);
+    fn CFRelease(cf: *mut c_void);
+}
+
+/// Returns `true` if the Mac is running on battery power, rather than AC power or a UPS.
+pub fn is_on_battery() -> bool {
+    unsafe {
+        let snapshot = IOPSCopyPowerSourcesInfo();
+        if snapshot.is_null() {
+            return false;
+        }
+        // The `CFStringRef` is toll-free bridged to `NSString`, and owned by the snapshot.
+        let source = IOPSGetProvidingPowerSourceType(snapshot);
+        let on_battery = !source.is_null() && {
+            let battery = NSString::from_str("Battery Power");
+            let equal: BOOL = msg_send![source, isEqualToString: &*battery];
+            equal != NO
+        };
+        CFRelease(snapshot);
+        on_battery
+    }
+}
+
+pub(crate) type OnChange = Box<dyn FnMut(bool)>;
+
+extern "C" fn power_sources_did_change(context: *mut c_void) {
+    unsafe {
+        let on_change = &mut *(context as *mut OnChange);
+        on_change(is_on_battery())
+    }
+}
+
+/// Calls back with whether the Mac is on battery power whenever its power sources change, which
+/// includes changes to the battery's charge.
+///
+/// Callbacks are made on the main run loop.
+#[derive(Debug)]
+pub(crate) struct PowerObserver {
+    source:    *mut c_void,
+    on_change: *mut OnChange,
+}
+
+impl PowerObserver {
+    /// `on_change` is also called right away, with whether the Mac is on battery power now.
+    pub fn new(mut on_change: OnChange) -> Option<Self> {
+        on_change(is_on_battery());
+        unsafe {
+            let on_change = Box::into_raw(Box::new(on_change));
+            let source =
+                IOPSNotificationCreateRunLoopSource(power_sources_did_change, on_change.cast());
+            if source.is_null() {
+                drop(Box::from_raw(on_change));
+                return None;
+            }
+            CFRunLoopAddSource(CFRunLoopGetMain(), source, kCFRunLoopCommonModes);
+            Some(PowerObserver { source, on_change })
+        }
+    }
+}
+
+impl Drop for PowerObserver {
+    fn drop(&mut self) {
+        unsafe {
+            CFRunLoopSourceInvalidate(self.source);
+            CFRelease(self.source);
+            drop(Box::from_raw(self.on_change));
+        }
+    }
+}
@@ -0,0 +1,124 @@
+use crate::macos::{
+    cadisplaylink::DisplayLink as ScreenDisplayLink, cvdisplaylink::DisplayLink as RawDisplayLink,
+};
+use objc::runtime::{NO, YES};
+use std::sync::{Arc, Mutex};
+
+/// Why a link was paused on its own, rather than by hand.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum Reason {
+    /// The window it renders to is fully hidden.
+    Occluded = 1 << 0,
+    /// The displays are asleep.
+    DisplaySleep = 1 << 1,
+    /// The screen is locked.
+    ScreenLocked = 1 << 2,
+}
+
+/// Starts and stops the link behind a `DisplayLink`.
+#[derive(Debug)]
+pub(crate) enum Switch {
+    CoreVideo(RawDisplayLink),
+    /// Shared with the `DisplayLink`, which replaces the link when it's moved to another screen.
+    Screen(Arc<Mutex<ScreenDisplayLink>>),
+}
+
+impl Switch {
+    unsafe fn set_running(&mut self, running: bool) {
+        match self {
+            Switch::CoreVideo(display_link) if running => display_link.start(),
+            Switch::CoreVideo(display_link) => display_link.stop(),
+            Switch::Screen(display_link) => {
+                display_link
+                    .lock()
+                    .unwrap()
+                    .set_paused(if running { NO } else { YES })
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+struct State {
+    switch:  Switch,
+    running: bool,
+    /// The `Reason`s the link is suspended for, as bits. Only set while the link was running
+    /// before it was suspended, so it's resumed once they're all cleared.
+    reasons: u8,
+}
+
+/// Whether a link is running, shared with the observers that pause it on their own.
+///
+/// Like on iOS, pausing or resuming by hand overrides any suspension.
+#[derive(Debug)]
+pub(crate) struct Runner(Mutex<State>);
+
+impl Runner {
+    /// Creates a runner for a stopped link.
+    pub fn new(switch: Switch) -> Self {
+        Runner(Mutex::new(State {
+            switch,
+            running: false,
+            reasons: 0,
+        }))
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.0.lock().unwrap().running
+    }
+
+    /// Stops the link, returning `false` if it wasn't running.
+    pub fn pause(&self) -> bool {
+        self.set_running(false)
+    }
+
+    /// Starts the link, returning `false` if it was already running.
+    pub fn resume(&self) -> bool {
+        self.set_running(true)
+    }
+
+    /// Stops the link if it's running and `closed` returns `true`, for stopping it once its
+    /// callback closed the gate, unless it was resumed since.
+    pub fn pause_if(&self, closed: impl FnOnce() -> bool) {
+        let mut state = self.0.lock().unwrap();
+        if state.running && closed() {
+            unsafe { state.switch.set_running(false) };
+            state.running = false;
+            state.reasons = 0;
+        }
+    }
+
+    fn set_running(&self, running: bool) -> bool {
+        let mut state = self.0.lock().unwrap();
+        state.reasons = 0;
+        if state.running == running {
+            false
+        } else {
+            unsafe { state.switch.set_running(running) };
+            state.running = running;
+            true
+        }
+    }
+
+    /// Suspends the link for `reason` if it's running, or lifts that suspension, resuming the link
+    /// if nothing else suspended it.
+    pub fn suspend(&self, reason: Reason, suspended: bool) {
+        let mut state = self.0.lock().unwrap();
+        let bit = reason as u8;
+        if suspended {
+            if state.running {
+                unsafe { state.switch.set_running(false) };
+                state.running = false;
+                state.reasons = bit;
+            } else if state.reasons != 0 {
+                state.reasons |= bit;
+            }
+        } else if state.reasons & bit != 0 {
+            state.reasons &= !bit;
+            if state.reasons == 0 {
+                unsafe { state.switch.set_running(true) };
+                state.running = true;
+            }
+        }
+    }
+}
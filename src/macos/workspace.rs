@@ -0,0 +1,137 @@
+//! Apple docs: [NSWorkspaceScreensDidSleepNotification](https://developer.apple.com/documentation/appkit/nsworkspace/1534022-screensdidsleepnotification?language=objc),
+//! [NSWorkspaceScreensDidWakeNotification](https://developer.apple.com/documentation/appkit/nsworkspace/1530708-screensdidwakenotification?language=objc),
+//! [NSDistributedNotificationCenter](https://developer.apple.com/documentation/foundation/nsdistributednotificationcenter?language=objc)
+
+use objc::{
+    class,
+    declare::ClassDecl,
+    msg_send,
+    runtime::{Object, Sel},
+    sel, sel_impl,
+};
+use objc_foundation::{INSString, NSString};
+use std::{ffi::c_void, ptr, sync::Once};
+
+#[link(name = "AppKit", kind = "framework")]
+extern "C" {
+    #[allow(improper_ctypes)]
+    static NSWorkspaceScreensDidSleepNotification: *mut NSString;
+    #[allow(improper_ctypes)]
+    static NSWorkspaceScreensDidWakeNotification: *mut NSString;
+}
+
+type OnChange = Box<dyn FnMut(bool)>;
+
+unsafe fn notify(this: &Object, active: bool) {
+    let on_change: *mut c_void = *this.get_ivar("_data");
+    let on_change = &mut *(on_change as *mut OnChange);
+    on_change(active)
+}
+
+extern "C" fn did_begin(this: &Object, _: Sel, _notification: *mut Object) {
+    unsafe { notify(this, true) }
+}
+
+extern "C" fn did_end(this: &Object, _: Sel, _notification: *mut Object) {
+    unsafe { notify(this, false) }
+}
+
+/// Calls back with `true` when the session enters a state where nothing on screen is seen, e.g.
+/// the displays going to sleep, and with `false` when it leaves it.
+///
+/// Notifications are posted on the main thread.
+#[derive(Debug)]
+pub(crate) struct SessionObserver {
+    center:    *mut Object,
+    observer:  *mut Object,
+    on_change: *mut OnChange,
+}
+
+impl SessionObserver {
+    /// Observes the displays going to sleep and waking up.
+    pub fn for_display_sleep(on_change: OnChange) -> Self {
+        unsafe {
+            // Workspace notifications are posted to the workspace's own center.
+            let workspace: *mut Object = msg_send![class!(NSWorkspace), sharedWorkspace];
+            Self::new(
+                msg_send![workspace, notificationCenter],
+                NSWorkspaceScreensDidSleepNotification,
+                NSWorkspaceScreensDidWakeNotification,
+                on_change,
+            )
+        }
+    }
+
+    /// Observes the screen being locked and unlocked. These notifications aren't documented, but
+    /// are what the system has posted since Mac OS X.
+    pub fn for_screen_lock(on_change: OnChange) -> Self {
+        let locked = NSString::from_str("com.apple.screenIsLocked");
+        let unlocked = NSString::from_str("com.apple.screenIsUnlocked");
+        unsafe {
+            Self::new(
+                msg_send![class!(NSDistributedNotificationCenter), defaultCenter],
+                &*locked as *const NSString as *mut NSString,
+                &*unlocked as *const NSString as *mut NSString,
+                on_change,
+            )
+        }
+    }
+
+    /// Observes the `begin` and `end` notifications posted to `center`.
+    unsafe fn new(
+        center: *mut Object,
+        begin: *mut NSString,
+        end: *mut NSString,
+        on_change: OnChange,
+    ) -> Self {
+        static OBSERVER_CLASS_CREATOR: Once = Once::new();
+        OBSERVER_CLASS_CREATOR.call_once(|| {
+            let mut decl =
+                ClassDecl::new("DisplayLinkSessionObserver", class!(NSObject)).unwrap();
+            decl.add_ivar::<*mut c_void>("_data");
+            decl.add_method(
+                sel!(didBegin:),
+                did_begin as extern "C" fn(&Object, Sel, *mut Object),
+            );
+            decl.add_method(
+                sel!(didEnd:),
+                did_end as extern "C" fn(&Object, Sel, *mut Object),
+            );
+            decl.register();
+        });
+
+        let on_change = Box::into_raw(Box::new(on_change));
+        let observer: *mut Object = msg_send![class!(DisplayLinkSessionObserver), alloc];
+        let observer: *mut Object = msg_send![observer, init];
+        (*observer).set_ivar::<*mut c_void>("_data", on_change as *mut c_void);
+        let () = msg_send![
+            center,
+            addObserver: observer
+            selector: sel!(didBegin:)
+            name: begin
+            object: ptr::null_mut::<Object>()
+        ];
+        let () = msg_send![
+            center,
+            addObserver: observer
+            selector: sel!(didEnd:)
+            name: end
+            object: ptr::null_mut::<Object>()
+        ];
+        SessionObserver {
+            center,
+            observer,
+            on_change,
+        }
+    }
+}
+
+impl Drop for SessionObserver {
+    fn drop(&mut self) {
+        unsafe {
+            let () = msg_send![self.center, removeObserver: self.observer];
+            let () = msg_send![self.observer, release];
+            drop(Box::from_raw(self.on_change));
+        }
+    }
+}
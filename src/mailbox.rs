@@ -0,0 +1,142 @@
+use crate::{DisplayLink, FrameTime, WaitError};
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    time::Duration,
+};
+
+#[derive(Debug, Default)]
+struct State {
+    frame:  Option<FrameTime>,
+    /// Set once the `DisplayLink` filling the mailbox is dropped.
+    closed: bool,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    state:   Mutex<State>,
+    condvar: Condvar,
+}
+
+/// Creates a link that calls back on a thread of its own, for links whose frames are waited for by
+/// blocking the caller, which may be the thread whose run loop would otherwise deliver the frames:
+/// iOS spins a run loop on a dedicated thread.
+pub(crate) fn off_run_loop<F>(callback: F) -> Option<DisplayLink>
+where
+    F: 'static + FnMut(FrameTime) + Send,
+{
+    #[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+    {
+        DisplayLink::on_dedicated_thread(callback)
+    }
+    #[cfg(not(any(target_os = "ios", target_os = "tvos", target_os = "visionos")))]
+    {
+        DisplayLink::new(callback)
+    }
+}
+
+/// Held by the `DisplayLink` callback, closing the mailbox when the link drops it.
+struct Sender(Arc<Shared>);
+
+impl Sender {
+    fn send(&self, frame: FrameTime) {
+        self.0.state.lock().unwrap().frame = Some(frame);
+        self.0.condvar.notify_all();
+    }
+}
+
+impl Drop for Sender {
+    fn drop(&mut self) {
+        self.0.state.lock().unwrap().closed = true;
+        self.0.condvar.notify_all();
+    }
+}
+
+/// Holds the latest frame of a `DisplayLink`, created with [`DisplayLink::mailbox`].
+///
+/// The mailbox never holds more than one frame: each frame overwrites the last if it hasn't been
+/// received yet, so a slow consumer always gets the most recent frame instead of falling further
+/// and further behind a queue.
+#[derive(Debug)]
+pub struct FrameMailbox {
+    shared: Arc<Shared>,
+}
+
+impl FrameMailbox {
+    pub(crate) fn new() -> Option<(DisplayLink, Self)> {
+        let shared = Arc::new(Shared::default());
+        let sender = Sender(Arc::clone(&shared));
+        let link = off_run_loop(move |frame| sender.send(frame))?;
+        link.resume().ok()?;
+        Some((link, FrameMailbox { shared }))
+    }
+
+    /// Takes the latest frame, if one arrived since the last was received, without blocking.
+    pub fn try_recv(&self) -> Option<FrameTime> {
+        self.shared.state.lock().unwrap().frame.take()
+    }
+
+    /// Blocks until a frame arrives, unless one already has, and takes it.
+    ///
+    /// Returns `None` once the `DisplayLink` has been dropped.
+    pub fn recv(&self) -> Option<FrameTime> {
+        let mut state = self.shared.state.lock().unwrap();
+        loop {
+            if let Some(frame) = state.frame.take() {
+                return Some(frame);
+            }
+            if state.closed {
+                return None;
+            }
+            state = self.shared.condvar.wait(state).unwrap();
+        }
+    }
+
+    /// Blocks until a frame arrives, like [`FrameMailbox::recv`], but for at most `timeout`, so
+    /// callers can't hang forever on a paused link or a sleeping display.
+    pub fn recv_timeout(&self, timeout: Duration) -> Result<FrameTime, WaitError> {
+        let state = self.shared.state.lock().unwrap();
+        let (mut state, _) = self
+            .shared
+            .condvar
+            .wait_timeout_while(state, timeout, |state| {
+                state.frame.is_none() && !state.closed
+            })
+            .unwrap();
+        match state.frame.take() {
+            Some(frame) => Ok(frame),
+            None if state.closed => Err(WaitError::Unavailable),
+            None => Err(WaitError::Timeout),
+        }
+    }
+}
+
+/// A blocking `Iterator` over the frames of a `DisplayLink`, created with [`DisplayLink::iter`].
+///
+/// Like a [`FrameMailbox`], frames the loop body was too slow for are skipped. Iteration ends
+/// when the display link stops for good, e.g. when the DRM device or X11 connection driving it
+/// goes away.
+#[derive(Debug)]
+pub struct Frames {
+    mailbox: FrameMailbox,
+    link:    DisplayLink,
+}
+
+impl Frames {
+    pub(crate) fn new() -> Option<Self> {
+        let (link, mailbox) = FrameMailbox::new()?;
+        Some(Frames { mailbox, link })
+    }
+
+    /// Returns the `DisplayLink` driving the iterator.
+    pub fn link(&mut self) -> &mut DisplayLink {
+        &mut self.link
+    }
+}
+
+impl Iterator for Frames {
+    type Item = FrameTime;
+
+    fn next(&mut self) -> Option<FrameTime> {
+        self.mailbox.recv()
+    }
+}
@@ -0,0 +1,34 @@
+use crate::{Display, DisplayId};
+
+/// Which display drives a `DisplayLink` when a window spans, or moves between, displays with
+/// different refresh rates, e.g. a 60 Hz and a 120 Hz monitor.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum PacingPolicy {
+    /// Follow the slowest display, so every frame rendered is shown on every display, e.g. so
+    /// video doesn't judder on the slower display.
+    Slowest,
+    /// Follow the fastest display, so it never misses a refresh, at the cost of rendering frames
+    /// the slower displays won't show.
+    Fastest,
+}
+
+impl PacingPolicy {
+    /// Returns the display among `displays` this policy follows, along with its refresh rate in
+    /// Hz, which is the cadence frames are delivered at once a link is synced to it.
+    ///
+    /// Displays that aren't connected, or whose refresh rate isn't known, are skipped. Returns
+    /// `None` if that leaves no displays.
+    pub fn choose(self, displays: &[DisplayId]) -> Option<(DisplayId, f64)> {
+        let rates = Display::all().into_iter().filter_map(|display| {
+            if displays.contains(&display.id()) {
+                display.refresh_rate().map(|rate| (display.id(), rate))
+            } else {
+                None
+            }
+        });
+        match self {
+            PacingPolicy::Slowest => rates.min_by(|(_, a), (_, b)| a.total_cmp(b)),
+            PacingPolicy::Fastest => rates.max_by(|(_, a), (_, b)| a.total_cmp(b)),
+        }
+    }
+}
@@ -0,0 +1,58 @@
+use crate::{callback::Gate, ControlFlow, FrameTime};
+use std::sync::Arc;
+
+/// Controls a `DisplayLink` from within its callback, passed to callbacks created with
+/// [`DisplayLink::with_proxy`](crate::DisplayLink::with_proxy).
+///
+/// Requests take effect once the callback returns.
+#[derive(Debug, Default)]
+pub struct LinkProxy {
+    frame_index: u64,
+    flow:        ControlFlow,
+    gate:        Arc<Gate>,
+}
+
+impl LinkProxy {
+    pub(crate) fn new(gate: Arc<Gate>) -> Self {
+        LinkProxy {
+            frame_index: 0,
+            flow: ControlFlow::Continue,
+            gate,
+        }
+    }
+
+    /// Invokes `callback` with the proxy and `frame`, returning what the callback asked for.
+    pub(crate) fn frame<F>(&mut self, frame: FrameTime, callback: &mut F) -> ControlFlow
+    where
+        F: FnMut(&mut LinkProxy, FrameTime),
+    {
+        self.flow = ControlFlow::Continue;
+        callback(self, frame);
+        self.frame_index += 1;
+        self.flow
+    }
+
+    /// Returns the number of frames delivered to the callback before this one.
+    pub fn frame_index(&self) -> u64 {
+        self.frame_index
+    }
+
+    /// Pauses the link, like returning `ControlFlow::Pause`.
+    pub fn pause(&mut self) {
+        if self.flow == ControlFlow::Continue {
+            self.flow = ControlFlow::Pause;
+        }
+    }
+
+    /// Stops the link for good, like returning `ControlFlow::Stop`.
+    pub fn stop(&mut self) {
+        self.flow = ControlFlow::Stop;
+    }
+
+    /// Invokes the callback at about `fps` frames per second from the next frame, like
+    /// [`DisplayLink::set_target_frame_rate`](crate::DisplayLink::set_target_frame_rate). 0
+    /// removes the target.
+    pub fn set_preferred_fps(&mut self, fps: u32) {
+        self.gate.throttle.set_target(fps)
+    }
+}
@@ -0,0 +1,64 @@
+//! Integration with winit event loops.
+
+use crate::{DisplayLink, FrameTime};
+use winit::{event::WindowEvent, event_loop::EventLoopProxy, window::WindowId};
+
+/// The user event a [`RedrawDriver`] sends on each refresh.
+///
+/// Handle it in `ApplicationHandler::user_event`, either by drawing right away or by calling
+/// `Window::request_redraw` on the window with `window_id`.
+#[derive(Clone, Copy, Debug)]
+pub struct VsyncEvent {
+    pub window_id: WindowId,
+    pub frame:     FrameTime,
+}
+
+/// Drives the redraws of a winit window from a `DisplayLink`, sending a [`VsyncEvent`] through an
+/// `EventLoopProxy` on each refresh.
+///
+/// Pass window events to [`RedrawDriver::handle_window_event`] so the link pauses while the
+/// window is occluded, instead of waking the event loop for frames nobody will see.
+#[derive(Debug)]
+pub struct RedrawDriver {
+    link:      DisplayLink,
+    window_id: WindowId,
+}
+
+impl RedrawDriver {
+    /// Creates a running `DisplayLink` sending a [`VsyncEvent`] for `window_id` to `proxy` on each
+    /// refresh.
+    ///
+    /// Returns `None` if a `DisplayLink` could not be created, like [`DisplayLink::new`].
+    pub fn new<E>(proxy: EventLoopProxy<E>, window_id: WindowId) -> Option<Self>
+    where
+        E: 'static + From<VsyncEvent> + Send,
+    {
+        let link = DisplayLink::new(move |frame| {
+            // The event loop exiting drops the driver soon after, so there's nothing to do here.
+            let _ = proxy.send_event(VsyncEvent { window_id, frame }.into());
+        })?;
+        link.resume().ok()?;
+        Some(RedrawDriver { link, window_id })
+    }
+
+    /// Pauses the link when the window becomes occluded, and resumes it when it's visible again.
+    /// Events for other windows are ignored.
+    pub fn handle_window_event(&mut self, window_id: WindowId, event: &WindowEvent) {
+        if window_id != self.window_id {
+            return;
+        }
+        if let WindowEvent::Occluded(occluded) = event {
+            self.link.set_paused(*occluded);
+        }
+    }
+
+    pub fn window_id(&self) -> WindowId {
+        self.window_id
+    }
+
+    /// Returns the `DisplayLink` driving the window, e.g. to pause it while nothing needs
+    /// drawing.
+    pub fn link(&mut self) -> &mut DisplayLink {
+        &mut self.link
+    }
+}
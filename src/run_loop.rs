@@ -0,0 +1,17 @@
+/// The run loop mode a `CADisplayLink` fires in, on iOS, tvOS and visionOS.
+///
+/// Like [`Backend`](crate::Backend), it exists on every platform so it can be passed to
+/// [`DisplayLinkBuilder::run_loop_mode`](crate::DisplayLinkBuilder::run_loop_mode) without `cfg`s,
+/// but it's ignored where links don't fire in a run loop of the app's.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub enum RunLoopMode {
+    /// `NSRunLoopCommonModes`, which includes the tracking mode UIKit runs in during scrolls and
+    /// other gestures, so frames keep coming through them. This is the default.
+    #[default]
+    Common,
+    /// `NSDefaultRunLoopMode`, so frames stop while UIKit tracks a gesture, leaving the run loop
+    /// to it.
+    Default,
+    /// A custom mode, by name, for apps running the run loop in modes of their own.
+    Custom(String),
+}
@@ -0,0 +1,36 @@
+//! Integration with the tokio runtime.
+
+use crate::{DisplayLink, FrameTime};
+use std::future::Future;
+use tokio::{sync::watch, task::JoinHandle};
+
+/// Creates a running `DisplayLink` publishing each frame to a `watch` channel.
+///
+/// The channel holds only the latest frame, so a task that falls behind skips the frames it
+/// missed, and sending never blocks the display link's thread or needs a runtime.
+pub(crate) fn frames() -> Option<(DisplayLink, watch::Receiver<Option<FrameTime>>)> {
+    let (sender, receiver) = watch::channel(None);
+    let link = DisplayLink::new(move |frame| {
+        let _ = sender.send(Some(frame));
+    })?;
+    link.resume().ok()?;
+    Some((link, receiver))
+}
+
+pub(crate) fn spawn_on_vsync<F, Fut>(mut f: F) -> Option<(DisplayLink, JoinHandle<()>)>
+where
+    F: 'static + FnMut(FrameTime) -> Fut + Send,
+    Fut: Future<Output = ()> + Send,
+{
+    let (link, mut receiver) = frames()?;
+    let task = tokio::spawn(async move {
+        // Fails once the `DisplayLink`, and with it the sender, is dropped.
+        while receiver.changed().await.is_ok() {
+            let frame = *receiver.borrow_and_update();
+            if let Some(frame) = frame {
+                f(frame).await
+            }
+        }
+    });
+    Some((link, task))
+}
@@ -0,0 +1,79 @@
+use crate::{CreateError, DisplayLink, FrameTime};
+use std::{
+    cell::RefCell,
+    fmt::{self, Debug, Formatter},
+    marker::PhantomData,
+    mem,
+    sync::{Arc, Mutex},
+};
+
+type Callback = Box<dyn FnMut(FrameTime) + Send>;
+
+/// The callback of a scoped link, which is cleared before the scope returns.
+type Slot = Arc<Mutex<Option<Callback>>>;
+
+/// Spawns `DisplayLink`s whose callbacks can borrow from the stack, created by
+/// [`DisplayLink::scope`].
+pub struct LinkScope<'env> {
+    links: RefCell<Vec<(DisplayLink, Slot)>>,
+    /// Invariant over `'env`, like `std::thread::Scope`.
+    env:   PhantomData<&'env mut &'env ()>,
+}
+
+impl Debug for LinkScope<'_> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let links = self.links.borrow();
+        f.debug_struct("LinkScope")
+            .field(
+                "links",
+                &links.iter().map(|(link, _)| link).collect::<Vec<_>>(),
+            )
+            .finish()
+    }
+}
+
+impl<'env> LinkScope<'env> {
+    pub(crate) fn new() -> Self {
+        LinkScope {
+            links: RefCell::new(Vec::new()),
+            env:   PhantomData,
+        }
+    }
+
+    /// Creates a running `DisplayLink` that invokes `callback` with each frame until the scope
+    /// ends.
+    ///
+    /// Returns `CreateError::Unavailable` if a `DisplayLink` could not be created, like
+    /// [`DisplayLink::new`].
+    pub fn spawn<F>(&self, callback: F) -> Result<(), CreateError>
+    where
+        F: 'env + FnMut(FrameTime) + Send,
+    {
+        let callback: Box<dyn FnMut(FrameTime) + Send + 'env> = Box::new(callback);
+        // Safe since the scope clears the slot, waiting for any call in progress, before `'env`
+        // ends, and the link never calls the callback once it's gone.
+        let callback: Callback = unsafe { mem::transmute(callback) };
+        let slot = Arc::new(Mutex::new(Some(callback)));
+        let link = {
+            let slot = Arc::clone(&slot);
+            DisplayLink::new(move |frame| {
+                if let Some(callback) = slot.lock().unwrap().as_mut() {
+                    callback(frame)
+                }
+            })
+            .ok_or(CreateError::Unavailable)?
+        };
+        link.resume().map_err(|_| CreateError::Unavailable)?;
+        self.links.borrow_mut().push((link, slot));
+        Ok(())
+    }
+}
+
+impl Drop for LinkScope<'_> {
+    fn drop(&mut self) {
+        for (_, slot) in self.links.get_mut().iter() {
+            slot.lock().unwrap().take();
+        }
+        self.links.get_mut().clear();
+    }
+}
@@ -0,0 +1,155 @@
+#[cfg(any(target_os = "ios", target_os = "tvos", target_os = "visionos"))]
+use crate::ios::is_main_thread;
+#[cfg(target_os = "macos")]
+use crate::macos::is_main_thread;
+use crate::{
+    Display, DisplayEvent, DisplayId, DisplayLink, DisplayWatcher, FrameTime, PauseError,
+    ResumeError,
+};
+use std::{
+    marker::PhantomData,
+    sync::{Arc, Mutex},
+};
+
+type SharedCallback = Arc<Mutex<dyn FnMut(FrameTime) + Send>>;
+
+#[derive(Debug)]
+struct Links {
+    links:  Vec<(DisplayId, DisplayLink)>,
+    paused: bool,
+}
+
+impl Links {
+    fn add(&mut self, id: DisplayId, callback: &SharedCallback) {
+        if self.links.iter().any(|(link_id, _)| *link_id == id) {
+            return;
+        }
+        let callback = Arc::clone(callback);
+        if let Some(link) =
+            DisplayLink::on_display(id, move |frame| (callback.lock().unwrap())(frame))
+        {
+            if !self.paused {
+                let _ = link.resume();
+            }
+            self.links.push((id, link));
+        }
+    }
+
+    fn remove(&mut self, id: DisplayId) {
+        self.links.retain(|(link_id, _)| *link_id != id);
+    }
+}
+
+// On Apple platforms, `DisplayLink`s aren't `Send`, but the watcher calls back on the main thread,
+// which is where the `DisplayLinkSet` is created, and can't leave. Elsewhere, `DisplayLink`s are
+// `Send` anyway.
+unsafe impl Send for Links {}
+
+/// Maintains one `DisplayLink` per connected display, creating and dropping links as displays are
+/// connected and disconnected, and calls a single callback with the frames of all of them.
+///
+/// Each `FrameTime` says which display it's from, e.g. for apps with one window per display.
+#[derive(Debug)]
+pub struct DisplayLinkSet {
+    // Dropped first, so links aren't added while the others are dropped.
+    _watcher: Option<DisplayWatcher>,
+    links:    Arc<Mutex<Links>>,
+    /// Keeps the set on the main thread on Apple platforms, like its links.
+    _links:   PhantomData<DisplayLink>,
+}
+
+impl DisplayLinkSet {
+    /// Creates a link on each connected display, plus one whenever a display is connected later.
+    ///
+    /// Like a `DisplayLink`, the set starts paused. On platforms that can't list displays, the set
+    /// holds a single link on the default display. Returns `None` if no link could be created.
+    ///
+    /// On macOS and iOS, this must be called on the main thread, whose run loop delivers display
+    /// changes, and returns `None` elsewhere.
+    pub fn new<F>(callback: F) -> Option<Self>
+    where
+        F: 'static + FnMut(FrameTime) + Send,
+    {
+        #[cfg(any(
+            target_os = "macos",
+            target_os = "ios",
+            target_os = "tvos",
+            target_os = "visionos"
+        ))]
+        if !is_main_thread() {
+            return None;
+        }
+        let callback: SharedCallback = Arc::new(Mutex::new(callback));
+        let mut links = Links {
+            links:  Vec::new(),
+            paused: true,
+        };
+        let displays = Display::all();
+        if displays.is_empty() {
+            let shared = Arc::clone(&callback);
+            let link = DisplayLink::new(move |frame| (shared.lock().unwrap())(frame))?;
+            links.links.push((DisplayId::UNKNOWN, link));
+        } else {
+            for display in displays {
+                links.add(display.id(), &callback);
+            }
+            if links.links.is_empty() {
+                return None;
+            }
+        }
+        let links = Arc::new(Mutex::new(links));
+        let watcher = {
+            let links = Arc::clone(&links);
+            Display::watch(move |event| {
+                let mut links = links.lock().unwrap();
+                match event {
+                    DisplayEvent::Connected(id) => links.add(id, &callback),
+                    DisplayEvent::Disconnected(id) => links.remove(id),
+                    DisplayEvent::Reconfigured { .. } | DisplayEvent::MirroringChanged { .. } => (),
+                }
+            })
+        };
+        Some(DisplayLinkSet {
+            _watcher: watcher,
+            links,
+            _links: PhantomData,
+        })
+    }
+
+    /// Returns the displays that currently have a link.
+    pub fn displays(&self) -> Vec<DisplayId> {
+        let links = self.links.lock().unwrap();
+        links.links.iter().map(|(id, _)| *id).collect()
+    }
+
+    /// Returns `true` if the set is paused.
+    pub fn is_paused(&self) -> bool {
+        self.links.lock().unwrap().paused
+    }
+
+    /// Pauses every link in the set, as well as links created for displays connected later.
+    pub fn pause(&mut self) -> Result<(), PauseError> {
+        let mut links = self.links.lock().unwrap();
+        if links.paused {
+            return Err(PauseError::AlreadyPaused);
+        }
+        links.paused = true;
+        for (_, link) in &mut links.links {
+            let _ = link.pause();
+        }
+        Ok(())
+    }
+
+    /// Resumes every link in the set.
+    pub fn resume(&mut self) -> Result<(), ResumeError> {
+        let mut links = self.links.lock().unwrap();
+        if !links.paused {
+            return Err(ResumeError::AlreadyRunning);
+        }
+        links.paused = false;
+        for (_, link) in &mut links.links {
+            let _ = link.resume();
+        }
+        Ok(())
+    }
+}
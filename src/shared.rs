@@ -0,0 +1,34 @@
+//! The per-thread broadcasts handed out by [`DisplayLink::shared`](crate::DisplayLink::shared).
+
+use crate::{Broadcast, DisplayId};
+use std::{
+    cell::RefCell,
+    rc::{Rc, Weak},
+};
+
+/// The live shared broadcasts, by display. Entries are only weak, so a broadcast is dropped with
+/// its last handle.
+type Registry = Vec<(Option<DisplayId>, Weak<Broadcast>)>;
+
+thread_local! {
+    // Apple and web display links aren't `Send`, so they can't be shared between threads.
+    static REGISTRY: RefCell<Registry> = const { RefCell::new(Vec::new()) };
+}
+
+/// Returns the current thread's live broadcast for `display`, or creates and registers a new one.
+pub(crate) fn get(display: Option<DisplayId>) -> Option<Rc<Broadcast>> {
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        registry.retain(|(_, broadcast)| broadcast.strong_count() > 0);
+        let existing = registry
+            .iter()
+            .find(|(id, _)| *id == display)
+            .and_then(|(_, broadcast)| broadcast.upgrade());
+        if existing.is_some() {
+            return existing;
+        }
+        let broadcast = Rc::new(Broadcast::new(display)?);
+        registry.push((display, Rc::downgrade(&broadcast)));
+        Some(broadcast)
+    })
+}
@@ -0,0 +1,34 @@
+use crate::FrameTime;
+use std::sync::{mpsc, Arc};
+
+/// Something a `DisplayLink` can deliver frames into, e.g. an event loop's proxy, created with
+/// [`DisplayLink::with_sink`](crate::DisplayLink::with_sink).
+///
+/// `on_frame` is called from the thread driving the link, which is a dedicated thread on most
+/// platforms, but the main thread's run loop for `CADisplayLink` and `NSScreen` links. It should
+/// hand the frame over and wake the event loop if it's waiting, e.g. by posting an event, and
+/// must never block on that event loop: a sink that does can deadlock the thread the event loop
+/// runs on, or drag every later frame behind it.
+pub trait FrameSink: 'static + Send {
+    fn on_frame(&self, frame: FrameTime);
+}
+
+impl<S: FrameSink + Sync> FrameSink for Arc<S> {
+    fn on_frame(&self, frame: FrameTime) {
+        S::on_frame(self, frame)
+    }
+}
+
+/// Frames are sent until the receiver is dropped.
+impl FrameSink for mpsc::Sender<FrameTime> {
+    fn on_frame(&self, frame: FrameTime) {
+        let _ = self.send(frame);
+    }
+}
+
+/// Frames are dropped while the channel is full, rather than blocking the link.
+impl FrameSink for mpsc::SyncSender<FrameTime> {
+    fn on_frame(&self, frame: FrameTime) {
+        let _ = self.try_send(frame);
+    }
+}
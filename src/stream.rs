@@ -0,0 +1,72 @@
+use crate::{future::FrameSlot, DisplayLink, FrameTime};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// The frames of a `DisplayLink`, created with [`DisplayLink::frames`], for async code.
+///
+/// Frames are handed over with nothing but `std::task::Waker`, so this works under any executor,
+/// e.g. smol, async-std, tokio or a custom one. With the `futures` feature, this is also a
+/// `futures_core::Stream`.
+///
+/// Only the latest frame is kept, so a consumer that falls behind skips the frames it missed
+/// rather than working through a backlog of stale ones. Dropping the stream drops its
+/// `DisplayLink`.
+#[derive(Debug)]
+pub struct FrameStream {
+    link: DisplayLink,
+    slot: FrameSlot,
+}
+
+impl FrameStream {
+    pub(crate) fn new() -> Option<Self> {
+        let slot = FrameSlot::default();
+        Some(FrameStream {
+            link: slot.link()?,
+            slot,
+        })
+    }
+
+    /// Returns the `DisplayLink` driving the stream, e.g. to pause it while the app is in the
+    /// background.
+    pub fn link(&mut self) -> &mut DisplayLink {
+        &mut self.link
+    }
+
+    /// Polls for the next frame, registering the current task to be woken when it arrives.
+    pub fn poll_frame(&mut self, cx: &mut Context<'_>) -> Poll<FrameTime> {
+        self.slot.poll_take(cx)
+    }
+
+    /// Returns a future resolving to the next frame, so frames can be awaited in a loop without
+    /// the `futures` feature.
+    pub fn next_frame(&mut self) -> StreamFrame<'_> {
+        StreamFrame { stream: self }
+    }
+}
+
+/// A `Future` resolving to the next frame of a [`FrameStream`], created with
+/// [`FrameStream::next_frame`].
+#[derive(Debug)]
+pub struct StreamFrame<'a> {
+    stream: &'a mut FrameStream,
+}
+
+impl Future for StreamFrame<'_> {
+    type Output = FrameTime;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<FrameTime> {
+        self.stream.poll_frame(cx)
+    }
+}
+
+#[cfg(feature = "futures")]
+impl futures_core::Stream for FrameStream {
+    type Item = FrameTime;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<FrameTime>> {
+        self.poll_frame(cx).map(Some)
+    }
+}
@@ -0,0 +1,135 @@
+//! Apple docs: [thermalState](https://developer.apple.com/documentation/foundation/nsprocessinfo/1417480-thermalstate?language=objc),
+//! [NSProcessInfoThermalStateDidChangeNotification](https://developer.apple.com/documentation/foundation/nsprocessinfothermalstatedidchangenotification?language=objc)
+#![cfg(any(
+    target_os = "macos",
+    target_os = "ios",
+    target_os = "tvos",
+    target_os = "visionos"
+))]
+
+use objc::{
+    class,
+    declare::ClassDecl,
+    msg_send,
+    runtime::{Object, Sel},
+    sel, sel_impl,
+};
+use objc_foundation::NSString;
+use std::{ffi::c_void, ptr, sync::Once};
+
+#[link(name = "Foundation", kind = "framework")]
+extern "C" {
+    #[allow(improper_ctypes)]
+    static NSProcessInfoThermalStateDidChangeNotification: *mut NSString;
+}
+
+/// How hard the system is working to keep the device cool, from `NSProcessInfo`'s
+/// `thermalState`, see [`DisplayLink::throttle_under_thermal_pressure`].
+///
+/// [`DisplayLink::throttle_under_thermal_pressure`]: crate::DisplayLink::throttle_under_thermal_pressure
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ThermalState {
+    Nominal,
+    Fair,
+    /// Performance is being throttled, and the fans are likely at full speed.
+    Serious,
+    /// The system is doing all it can to cool down, and the app should do as little as it can.
+    Critical,
+}
+
+impl ThermalState {
+    /// Returns the thermal state of the device.
+    pub fn current() -> Self {
+        let state: isize = unsafe {
+            let process_info: *mut Object = msg_send![class!(NSProcessInfo), processInfo];
+            msg_send![process_info, thermalState]
+        };
+        match state {
+            0 => ThermalState::Nominal,
+            1 => ThermalState::Fair,
+            2 => ThermalState::Serious,
+            _ => ThermalState::Critical,
+        }
+    }
+
+    /// Only every `divisor`th frame is delivered in this state, when throttling.
+    pub(crate) fn divisor(self) -> u32 {
+        match self {
+            ThermalState::Nominal | ThermalState::Fair => 1,
+            ThermalState::Serious => 2,
+            ThermalState::Critical => 4,
+        }
+    }
+}
+
+pub(crate) type OnChange = Box<dyn FnMut(ThermalState) + Send>;
+
+extern "C" fn thermal_state_did_change(this: &Object, _: Sel, _notification: *mut Object) {
+    unsafe {
+        let on_change: *mut c_void = *this.get_ivar("_data");
+        let on_change = &mut *(on_change as *mut OnChange);
+        on_change(ThermalState::current())
+    }
+}
+
+/// Calls back with the new thermal state whenever it changes.
+///
+/// Notifications are posted on whichever thread the system posts them from, which needn't be the
+/// main thread.
+#[derive(Debug)]
+pub(crate) struct ThermalObserver {
+    observer:  *mut Object,
+    on_change: *mut OnChange,
+}
+
+// The observer is only touched on creation and drop, and `on_change` is `Send`.
+unsafe impl Send for ThermalObserver {}
+
+impl ThermalObserver {
+    /// `on_change` is also called right away, with the current thermal state.
+    pub fn new(mut on_change: OnChange) -> Self {
+        static OBSERVER_CLASS_CREATOR: Once = Once::new();
+        OBSERVER_CLASS_CREATOR.call_once(|| {
+            let mut decl = ClassDecl::new("DisplayLinkThermalObserver", class!(NSObject)).unwrap();
+            decl.add_ivar::<*mut c_void>("_data");
+            unsafe {
+                decl.add_method(
+                    sel!(thermalStateDidChange:),
+                    thermal_state_did_change as extern "C" fn(&Object, Sel, *mut Object),
+                );
+            }
+            decl.register();
+        });
+
+        on_change(ThermalState::current());
+        unsafe {
+            let on_change = Box::into_raw(Box::new(on_change));
+            let observer: *mut Object = msg_send![class!(DisplayLinkThermalObserver), alloc];
+            let observer: *mut Object = msg_send![observer, init];
+            (*observer).set_ivar::<*mut c_void>("_data", on_change as *mut c_void);
+            let center: *mut Object = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![
+                center,
+                addObserver: observer
+                selector: sel!(thermalStateDidChange:)
+                name: NSProcessInfoThermalStateDidChangeNotification
+                object: ptr::null_mut::<Object>()
+            ];
+            ThermalObserver {
+                observer,
+                on_change,
+            }
+        }
+    }
+}
+
+impl Drop for ThermalObserver {
+    fn drop(&mut self) {
+        unsafe {
+            let center: *mut Object = msg_send![class!(NSNotificationCenter), defaultCenter];
+            let () = msg_send![center, removeObserver: self.observer];
+            let () = msg_send![self.observer, release];
+            drop(Box::from_raw(self.on_change));
+        }
+    }
+}
@@ -0,0 +1,39 @@
+use crate::callback::{Gate, Slot};
+use std::sync::Arc;
+
+#[derive(Debug)]
+struct Invalidator {
+    gate:     Arc<Gate>,
+    callback: Arc<dyn Slot>,
+}
+
+impl Drop for Invalidator {
+    fn drop(&mut self) {
+        self.gate.close();
+        self.callback.clear();
+    }
+}
+
+/// Ties the lifetime of a `DisplayLink`'s callback to the token, created with
+/// [`DisplayLink::with_token`](crate::DisplayLink::with_token).
+///
+/// Once every clone of the token is dropped, the link is invalidated like
+/// [`DisplayLink::invalidate`](crate::DisplayLink::invalidate): its callback is never invoked
+/// again, and is dropped along with everything it captured, even if the `DisplayLink` itself is
+/// leaked, or the OS keeps the underlying link alive. Tokens must not be owned by the callback
+/// itself.
+#[derive(Clone, Debug)]
+pub struct LinkToken(Arc<Invalidator>);
+
+impl LinkToken {
+    pub(crate) fn new(gate: Arc<Gate>, callback: Arc<dyn Slot>) -> Self {
+        LinkToken(Arc::new(Invalidator { gate, callback }))
+    }
+
+    /// Returns `true` once the link is stopped for good, by
+    /// [`DisplayLink::invalidate`](crate::DisplayLink::invalidate) or by its callback returning
+    /// `ControlFlow::Stop`.
+    pub fn is_stopped(&self) -> bool {
+        self.0.gate.is_stopped()
+    }
+}
@@ -0,0 +1,279 @@
+#![cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+
+use crate::{
+    callback::Park,
+    frame::{Clock, DiscontinuityCallback, FrameObservers, RateCallback},
+    Backend, Bounds, Display, FrameTime, FrameTimestamp, PauseError, ResumeError, SetDisplayError,
+};
+use std::{
+    cell::RefCell,
+    fmt::{self, Debug, Formatter},
+    rc::{Rc, Weak},
+    sync::Arc,
+    task::Waker,
+};
+use time_point::TimePoint;
+use wasm_bindgen::{closure::Closure, JsCast};
+
+/// The browser doesn't expose which display a page is on, so this is always
+/// `DisplayId::UNKNOWN`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DisplayId(pub u32);
+
+impl DisplayId {
+    /// The id reported when the display driving a link is unknown, e.g. for the timer fallback.
+    pub const UNKNOWN: DisplayId = DisplayId(0);
+}
+
+/// Returns the screen the page is on, or nothing outside of a browser window.
+pub(crate) fn displays() -> Vec<Display> {
+    let screen = match web_sys::window().and_then(|window| window.screen().ok()) {
+        Some(screen) => screen,
+        None => return Vec::new(),
+    };
+    vec![Display {
+        id:                 DisplayId::UNKNOWN,
+        name:               None,
+        bounds:             Bounds {
+            x:      0.0,
+            y:      0.0,
+            width:  screen.width().map_or(0.0, f64::from),
+            height: screen.height().map_or(0.0, f64::from),
+        },
+        refresh_rate:       None,
+        refresh_rate_range: None,
+        mirrors:            None,
+    }]
+}
+
+/// A `DOMHighResTimeStamp`, in milliseconds since the time origin of the page.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct PlatformTimestamp(pub f64);
+
+impl PlatformTimestamp {
+    pub(crate) fn now() -> Self {
+        let now = web_sys::window()
+            .and_then(|window| window.performance())
+            .map_or(0.0, |performance| performance.now());
+        PlatformTimestamp(now)
+    }
+
+    pub(crate) fn seconds_since(&self, earlier: Self) -> f64 {
+        (self.0 - earlier.0) / 1000.0
+    }
+
+    /// `Instant` isn't available on `wasm32-unknown-unknown`, so the `TimePoint` is just the
+    /// timestamp in nanoseconds.
+    pub(crate) fn to_time_point(self, _epoch: &crate::Epoch) -> TimePoint {
+        TimePoint::new((self.0 * 1e6) as u64)
+    }
+}
+
+#[derive(Debug, Default)]
+struct State {
+    paused:  bool,
+    request: Option<i32>,
+    /// The time of the last frame, used to estimate the frame interval.
+    last:    Option<f64>,
+}
+
+struct Shared {
+    state:    RefCell<State>,
+    callback: RefCell<Box<dyn FnMut(PlatformTimestamp)>>,
+    closure:  RefCell<Option<Closure<dyn FnMut(f64)>>>,
+}
+
+impl Shared {
+    fn request_frame(&self) {
+        let closure = self.closure.borrow();
+        let request = web_sys::window()
+            .zip(closure.as_ref())
+            .and_then(|(window, closure)| {
+                window
+                    .request_animation_frame(closure.as_ref().unchecked_ref())
+                    .ok()
+            });
+        self.state.borrow_mut().request = request;
+    }
+
+    fn cancel_frame(&self) {
+        if let Some(request) = self.state.borrow_mut().request.take() {
+            if let Some(window) = web_sys::window() {
+                let _ = window.cancel_animation_frame(request);
+            }
+        }
+    }
+
+    fn on_frame(&self, time: f64) {
+        let next = {
+            let mut state = self.state.borrow_mut();
+            state.request = None;
+            // The timestamp is the start of the current frame, so estimate when it'll be shown
+            // from the previous interval.
+            match state.last.replace(time) {
+                Some(last) => 2.0 * time - last,
+                None => time,
+            }
+        };
+        (self.callback.borrow_mut())(PlatformTimestamp(next));
+        if !self.state.borrow().paused {
+            self.request_frame();
+        }
+    }
+}
+
+/// The link a `Park` pauses. The park only runs in the link's callback, and wasm is
+/// single-threaded, so the `Weak` never leaves the thread that created it.
+struct Parked(Weak<Shared>);
+
+unsafe impl Send for Parked {}
+
+impl Parked {
+    /// Keeps `on_frame` from requesting another frame.
+    fn pause(&self) {
+        if let Some(shared) = self.0.upgrade() {
+            shared.state.borrow_mut().paused = true;
+        }
+    }
+}
+
+/// A `DisplayLink` driven by `requestAnimationFrame`.
+pub struct DisplayLink {
+    shared:    Rc<Shared>,
+    observers: Arc<FrameObservers>,
+}
+
+impl Debug for DisplayLink {
+    fn fmt(&self, formatter: &mut Formatter) -> fmt::Result {
+        formatter
+            .debug_struct("DisplayLink")
+            .field("state", &self.shared.state)
+            .finish()
+    }
+}
+
+impl Drop for DisplayLink {
+    fn drop(&mut self) {
+        self.shared.cancel_frame();
+        self.shared.closure.borrow_mut().take();
+    }
+}
+
+impl DisplayLink {
+    /// Creates a new web `DisplayLink` instance.
+    ///
+    /// Returns `None` outside of a browser window, e.g. in a web worker.
+    pub fn new<T, F>(mut callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>),
+    {
+        web_sys::window()?;
+        let mut clock = Clock::default();
+        let observers = clock.observers();
+        let shared = Rc::new(Shared {
+            state:    RefCell::new(State {
+                paused: true,
+                ..Default::default()
+            }),
+            callback: RefCell::new(Box::new(move |timestamp| {
+                callback(clock.frame(timestamp, DisplayId::UNKNOWN))
+            })),
+            closure:  RefCell::new(None),
+        });
+        let weak: Weak<Shared> = Rc::downgrade(&shared);
+        let closure = Closure::wrap(Box::new(move |time: f64| {
+            if let Some(shared) = weak.upgrade() {
+                shared.on_frame(time);
+            }
+        }) as Box<dyn FnMut(f64)>);
+        *shared.closure.borrow_mut() = Some(closure);
+        Some(DisplayLink { shared, observers })
+    }
+
+    pub fn on_display<T, F>(_display_id: DisplayId, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>),
+    {
+        Self::new(callback)
+    }
+
+    /// `Backend::AnimationFrame` is the only backend on the web.
+    pub fn with_backend<T, F>(backend: Backend, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>),
+    {
+        match backend {
+            Backend::AnimationFrame => Self::new(callback),
+            _ => None,
+        }
+    }
+
+    pub fn set_display(&mut self, _display_id: DisplayId) -> Result<(), SetDisplayError> {
+        Err(SetDisplayError::Unsupported)
+    }
+
+    pub fn display(&self) -> DisplayId {
+        DisplayId::UNKNOWN
+    }
+
+    pub fn on_refresh_rate_changed(&mut self, callback: RateCallback) {
+        self.observers.rate.set_callback(callback)
+    }
+
+    pub fn on_clock_discontinuity(&mut self, callback: DiscontinuityCallback) {
+        self.observers.set_discontinuity_callback(callback)
+    }
+
+    pub fn register_waker(&mut self, waker: Waker) {
+        self.observers.set_waker(waker)
+    }
+
+    pub fn backend(&self) -> Backend {
+        Backend::AnimationFrame
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.shared.state.borrow().paused
+    }
+
+    pub(crate) fn park(&self) -> Park {
+        let shared = Parked(Rc::downgrade(&self.shared));
+        Park::new(move |gate| {
+            if !gate.is_open() {
+                shared.pause()
+            }
+        })
+    }
+
+    /// Animation frames keep coming until the link is dropped.
+    pub fn is_stopped(&self) -> bool {
+        false
+    }
+
+    pub fn pause(&mut self) -> Result<(), PauseError> {
+        if self.is_paused() {
+            Err(PauseError::AlreadyPaused)
+        } else {
+            self.shared.state.borrow_mut().paused = true;
+            self.shared.cancel_frame();
+            Ok(())
+        }
+    }
+
+    pub fn resume(&mut self) -> Result<(), ResumeError> {
+        if !self.is_paused() {
+            Err(ResumeError::AlreadyRunning)
+        } else {
+            {
+                let mut state = self.shared.state.borrow_mut();
+                state.paused = false;
+                state.last = None;
+            }
+            self.shared.request_frame();
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{DisplayLink, FrameTime};
+use std::{
+    future::Future,
+    mem,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+#[derive(Debug, Default)]
+struct State {
+    frame:   Option<FrameTime>,
+    /// Counts the frames sent, so each receiver can tell whether it has seen the latest.
+    version: u64,
+    wakers:  Vec<Waker>,
+    /// Set once the `DisplayLink` sending frames is dropped.
+    closed:  bool,
+}
+
+/// Held by the `DisplayLink` callback, closing the watch when the link drops it.
+struct Sender(Arc<Mutex<State>>);
+
+impl Sender {
+    fn send(&self, frame: FrameTime) {
+        let wakers = {
+            let mut state = self.0.lock().unwrap();
+            state.frame = Some(frame);
+            state.version += 1;
+            mem::take(&mut state.wakers)
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+impl Drop for Sender {
+    fn drop(&mut self) {
+        let wakers = {
+            let mut state = self.0.lock().unwrap();
+            state.closed = true;
+            mem::take(&mut state.wakers)
+        };
+        wakers.into_iter().for_each(Waker::wake);
+    }
+}
+
+/// Watches the latest frame of a `DisplayLink`, created with [`DisplayLink::watch`].
+///
+/// Frames coalesce: reading with [`FrameWatch::borrow_latest`] never waits, and
+/// [`FrameWatch::changed`] resolves once for any number of frames sent since the last change was
+/// seen, so UI layers that only sometimes care about frames never fall behind. Clones watch the
+/// same link, each keeping track of the changes it has seen.
+#[derive(Clone, Debug)]
+pub struct FrameWatch {
+    state: Arc<Mutex<State>>,
+    seen:  u64,
+}
+
+impl FrameWatch {
+    pub(crate) fn new() -> Option<(DisplayLink, Self)> {
+        let state = Arc::new(Mutex::new(State::default()));
+        let sender = Sender(Arc::clone(&state));
+        let link = DisplayLink::new(move |frame| sender.send(frame))?;
+        link.resume().ok()?;
+        Some((link, FrameWatch { state, seen: 0 }))
+    }
+
+    /// Returns the latest frame, or `None` before the first one, without marking it as seen.
+    pub fn borrow_latest(&self) -> Option<FrameTime> {
+        self.state.lock().unwrap().frame
+    }
+
+    /// Returns a future resolving to the latest frame once one arrives that this watch hasn't
+    /// seen, marking it as seen.
+    ///
+    /// Resolves to `None` once the `DisplayLink` is dropped.
+    pub fn changed(&mut self) -> Changed<'_> {
+        Changed { watch: self }
+    }
+
+    fn poll_changed(&mut self, cx: &mut Context<'_>) -> Poll<Option<FrameTime>> {
+        let mut state = self.state.lock().unwrap();
+        if state.version != self.seen {
+            self.seen = state.version;
+            Poll::Ready(state.frame)
+        } else if state.closed {
+            Poll::Ready(None)
+        } else {
+            if !state.wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                state.wakers.push(cx.waker().clone());
+            }
+            Poll::Pending
+        }
+    }
+}
+
+/// A `Future` resolving to the next frame a [`FrameWatch`] hasn't seen, created with
+/// [`FrameWatch::changed`].
+#[derive(Debug)]
+pub struct Changed<'a> {
+    watch: &'a mut FrameWatch,
+}
+
+impl Future for Changed<'_> {
+    type Output = Option<FrameTime>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<FrameTime>> {
+        self.watch.poll_changed(cx)
+    }
+}
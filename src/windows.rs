@@ -0,0 +1,390 @@
+#![cfg(target_os = "windows")]
+
+use crate::{
+    callback::Park,
+    frame::{DiscontinuityCallback, Epoch, RateCallback},
+    worker::Worker,
+    Backend, Bounds, Display, FrameTime, FrameTimestamp, PauseError, ResumeError, SetDisplayError,
+};
+use ::windows::{
+    core::PCWSTR,
+    Win32::{
+        Foundation::{BOOL, HWND, LPARAM, RECT, TRUE},
+        Graphics::{
+            Dwm::{
+                DwmFlush, DwmGetCompositionTimingInfo, DwmIsCompositionEnabled, DWM_TIMING_INFO,
+            },
+            Dxgi::{CreateDXGIFactory1, IDXGIFactory1, IDXGIOutput},
+            Gdi::{
+                EnumDisplayDevicesW, EnumDisplayMonitors, EnumDisplaySettingsW, GetMonitorInfoW,
+                MonitorFromWindow, DEVMODEW, DISPLAY_DEVICEW, ENUM_CURRENT_SETTINGS, HDC, HMONITOR,
+                MONITORINFO, MONITORINFOEXW, MONITOR_DEFAULTTONEAREST, MONITOR_DEFAULTTOPRIMARY,
+            },
+        },
+        System::Performance::{QueryPerformanceCounter, QueryPerformanceFrequency},
+    },
+};
+use std::{
+    ffi::c_void,
+    mem,
+    sync::{Arc, Mutex},
+    task::Waker,
+};
+use time_point::TimePoint;
+
+/// An `HMONITOR` identifying a display.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct DisplayId(pub isize);
+
+impl DisplayId {
+    /// The id reported when the display driving a link is unknown, e.g. for the timer fallback.
+    pub const UNKNOWN: DisplayId = DisplayId(0);
+}
+
+/// A `QueryPerformanceCounter` value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PlatformTimestamp(pub i64);
+
+impl PlatformTimestamp {
+    pub(crate) fn now() -> Self {
+        let mut count = 0;
+        unsafe {
+            let _ = QueryPerformanceCounter(&mut count);
+        }
+        PlatformTimestamp(count)
+    }
+
+    pub(crate) fn seconds_since(&self, earlier: Self) -> f64 {
+        let mut frequency = 0;
+        unsafe {
+            let _ = QueryPerformanceFrequency(&mut frequency);
+        }
+        (self.0 - earlier.0) as f64 / frequency as f64
+    }
+
+    pub(crate) fn to_time_point(self, epoch: &Epoch) -> TimePoint {
+        TimePoint::from_std_instant(epoch.to_instant(self))
+    }
+}
+
+/// Set in `MONITORINFO::dwFlags` for the primary monitor. `windows` only defines it under
+/// `Win32_UI_WindowsAndMessaging`, which is a lot to build for one flag.
+const MONITORINFOF_PRIMARY: u32 = 1;
+
+/// Returns the part of a UTF-16 buffer before the first nul.
+fn from_wide(wide: &[u16]) -> String {
+    let len = wide.iter().position(|&c| c == 0).unwrap_or(wide.len());
+    String::from_utf16_lossy(&wide[..len])
+}
+
+fn display(monitor: HMONITOR) -> Option<(Display, bool)> {
+    let mut info = MONITORINFOEXW::default();
+    info.monitorInfo.cbSize = mem::size_of::<MONITORINFOEXW>() as u32;
+    if !unsafe { GetMonitorInfoW(monitor, &mut info as *mut _ as *mut MONITORINFO) }.as_bool() {
+        return None;
+    }
+    let device = PCWSTR(info.szDevice.as_ptr());
+
+    let mut mode = DEVMODEW {
+        dmSize: mem::size_of::<DEVMODEW>() as u16,
+        ..Default::default()
+    };
+    // Frequencies of 0 and 1 mean the hardware's default rate.
+    let refresh_rate = unsafe { EnumDisplaySettingsW(device, ENUM_CURRENT_SETTINGS, &mut mode) }
+        .as_bool()
+        .then_some(mode.dmDisplayFrequency)
+        .filter(|&frequency| frequency > 1)
+        .map(f64::from);
+
+    // The first device of an adapter's display is its monitor.
+    let mut device_info = DISPLAY_DEVICEW {
+        cb: mem::size_of::<DISPLAY_DEVICEW>() as u32,
+        ..Default::default()
+    };
+    let name = unsafe { EnumDisplayDevicesW(device, 0, &mut device_info, 0) }
+        .as_bool()
+        .then(|| from_wide(&device_info.DeviceString));
+
+    let RECT {
+        left,
+        top,
+        right,
+        bottom,
+    } = info.monitorInfo.rcMonitor;
+    let is_primary = info.monitorInfo.dwFlags & MONITORINFOF_PRIMARY != 0;
+    Some((
+        Display {
+            id: DisplayId(monitor.0 as isize),
+            name,
+            bounds: Bounds {
+                x:      f64::from(left),
+                y:      f64::from(top),
+                width:  f64::from(right - left),
+                height: f64::from(bottom - top),
+            },
+            refresh_rate,
+            refresh_rate_range: None,
+            mirrors: None,
+        },
+        is_primary,
+    ))
+}
+
+pub(crate) fn displays() -> Vec<Display> {
+    unsafe extern "system" fn push(
+        monitor: HMONITOR,
+        _: HDC,
+        _: *mut RECT,
+        displays: LPARAM,
+    ) -> BOOL {
+        let displays = &mut *(displays.0 as *mut Vec<(Display, bool)>);
+        displays.extend(display(monitor));
+        TRUE
+    }
+
+    let mut displays: Vec<(Display, bool)> = Vec::new();
+    unsafe {
+        let _ = EnumDisplayMonitors(
+            HDC::default(),
+            None,
+            Some(push),
+            LPARAM(&mut displays as *mut _ as isize),
+        );
+    }
+    displays.sort_by_key(|&(_, is_primary)| !is_primary);
+    displays.into_iter().map(|(display, _)| display).collect()
+}
+
+/// What the worker thread waits on between frames.
+#[derive(Debug)]
+enum Source {
+    /// `IDXGIOutput::WaitForVBlank` on a specific output.
+    Dxgi {
+        output:      IDXGIOutput,
+        display:     DisplayId,
+        last_vblank: Option<PlatformTimestamp>,
+    },
+    /// `DwmFlush`, timed with `DwmGetCompositionTimingInfo`.
+    Dwm { display: DisplayId },
+}
+
+// DXGI objects are free-threaded.
+unsafe impl Send for Source {}
+
+impl Source {
+    /// Returns the output showing `display`, or the primary display's output if `display` is
+    /// `None`.
+    fn dxgi(display: Option<DisplayId>) -> Option<Self> {
+        let factory: IDXGIFactory1 = unsafe { CreateDXGIFactory1() }.ok()?;
+        let mut adapter_index = 0;
+        while let Ok(adapter) = unsafe { factory.EnumAdapters1(adapter_index) } {
+            let mut output_index = 0;
+            while let Ok(output) = unsafe { adapter.EnumOutputs(output_index) } {
+                let desc = unsafe { output.GetDesc() }.ok()?;
+                let monitor = DisplayId(desc.Monitor.0 as isize);
+                // The first output of the first adapter is the primary display.
+                if display.is_none() || display == Some(monitor) {
+                    return Some(Source::Dxgi {
+                        output,
+                        display: monitor,
+                        last_vblank: None,
+                    });
+                }
+                output_index += 1;
+            }
+            adapter_index += 1;
+        }
+        None
+    }
+
+    /// Returns `None` if desktop composition is disabled.
+    fn dwm() -> Option<Self> {
+        if !unsafe { DwmIsCompositionEnabled() }.ok()?.as_bool() {
+            return None;
+        }
+        let monitor = unsafe { MonitorFromWindow(HWND::default(), MONITOR_DEFAULTTOPRIMARY) };
+        Some(Source::Dwm {
+            display: DisplayId(monitor.0 as isize),
+        })
+    }
+
+    /// Blocks until the next frame, returning when it will be presented.
+    fn wait(&mut self) -> Option<(PlatformTimestamp, DisplayId)> {
+        match self {
+            Source::Dxgi {
+                output,
+                display,
+                last_vblank,
+            } => {
+                unsafe { output.WaitForVBlank() }.ok()?;
+                let vblank = PlatformTimestamp::now();
+                // DXGI only tells us about the vblank that just happened, so estimate the next
+                // one from the previous interval.
+                let next = match last_vblank.replace(vblank) {
+                    Some(PlatformTimestamp(last)) => PlatformTimestamp(2 * vblank.0 - last),
+                    None => vblank,
+                };
+                Some((next, *display))
+            }
+            Source::Dwm { display } => {
+                unsafe { DwmFlush() }.ok()?;
+                let mut info = DWM_TIMING_INFO {
+                    cbSize: mem::size_of::<DWM_TIMING_INFO>() as u32,
+                    ..Default::default()
+                };
+                unsafe { DwmGetCompositionTimingInfo(HWND::default(), &mut info) }.ok()?;
+                let next = info.qpcVBlank + info.qpcRefreshPeriod;
+                Some((PlatformTimestamp(next as i64), *display))
+            }
+        }
+    }
+}
+
+/// A `DisplayLink` driven from a dedicated thread, either by `IDXGIOutput::WaitForVBlank` or by
+/// the DWM compositor.
+#[derive(Debug)]
+pub struct DisplayLink {
+    source: Arc<Mutex<Source>>,
+    worker: Worker,
+}
+
+impl DisplayLink {
+    fn new_impl<T, F>(source: Source, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        let source = Arc::new(Mutex::new(source));
+        let worker = {
+            let source = Arc::clone(&source);
+            Worker::spawn_link(move || source.lock().unwrap().wait(), callback)?
+        };
+        Some(DisplayLink { source, worker })
+    }
+
+    /// Creates a new Windows `DisplayLink` instance, synced to the primary display.
+    ///
+    /// Windows _does_ require the callback to be `Send`.
+    pub fn new<T, F>(callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::new_impl(Source::dxgi(None)?, callback)
+    }
+
+    /// Creates a new Windows `DisplayLink` instance paced by the DWM compositor, using
+    /// `DwmFlush` and `DwmGetCompositionTimingInfo`.
+    ///
+    /// This matches what windowed (non-exclusive fullscreen) apps actually present against.
+    /// Returns `None` if desktop composition is disabled.
+    pub fn new_dwm<T, F>(callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::new_impl(Source::dwm()?, callback)
+    }
+
+    pub fn on_display<T, F>(display_id: DisplayId, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        Self::new_impl(Source::dxgi(Some(display_id))?, callback)
+    }
+
+    /// Creates a `DisplayLink` on the monitor `hwnd` is on, or the nearest one if it's offscreen.
+    ///
+    /// The link stays on that monitor, so call `set_display` when the window moves to another.
+    pub fn for_hwnd<T, F>(hwnd: *mut c_void, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        let monitor = unsafe { MonitorFromWindow(HWND(hwnd as _), MONITOR_DEFAULTTONEAREST) };
+        Self::on_display(DisplayId(monitor.0 as isize), callback)
+    }
+
+    /// Creates a `DisplayLink` on the primary display using `backend`, which must be
+    /// `Backend::Dxgi` or `Backend::Dwm`.
+    pub fn with_backend<T, F>(backend: Backend, callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+    {
+        match backend {
+            Backend::Dxgi => Self::new(callback),
+            Backend::Dwm => Self::new_dwm(callback),
+            _ => None,
+        }
+    }
+
+    /// Swaps the DXGI output the worker waits on. A `DisplayLink` paced by DWM can't be moved, as
+    /// the compositor drives all displays.
+    pub fn set_display(&mut self, display_id: DisplayId) -> Result<(), SetDisplayError> {
+        let mut source = self.source.lock().unwrap();
+        match *source {
+            Source::Dxgi { .. } => {
+                *source = Source::dxgi(Some(display_id)).ok_or(SetDisplayError::NotConnected)?;
+                Ok(())
+            }
+            Source::Dwm { .. } => Err(SetDisplayError::Unsupported),
+        }
+    }
+
+    pub fn display(&self) -> DisplayId {
+        match *self.source.lock().unwrap() {
+            Source::Dxgi { display, .. } | Source::Dwm { display } => display,
+        }
+    }
+
+    pub fn on_refresh_rate_changed(&mut self, callback: RateCallback) {
+        self.worker.on_refresh_rate_changed(callback)
+    }
+
+    pub fn on_clock_discontinuity(&mut self, callback: DiscontinuityCallback) {
+        self.worker.on_clock_discontinuity(callback)
+    }
+
+    pub fn register_waker(&mut self, waker: Waker) {
+        self.worker.register_waker(waker)
+    }
+
+    pub fn backend(&self) -> Backend {
+        match *self.source.lock().unwrap() {
+            Source::Dxgi { .. } => Backend::Dxgi,
+            Source::Dwm { .. } => Backend::Dwm,
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.worker.is_paused()
+    }
+
+    pub(crate) fn park(&self) -> Park {
+        self.worker.park()
+    }
+
+    pub fn is_stopped(&self) -> bool {
+        self.worker.is_stopped()
+    }
+
+    pub fn pause(&mut self) -> Result<(), PauseError> {
+        if self.is_paused() {
+            Err(PauseError::AlreadyPaused)
+        } else {
+            self.worker.set_paused(true);
+            Ok(())
+        }
+    }
+
+    pub fn resume(&mut self) -> Result<(), ResumeError> {
+        if !self.is_paused() {
+            Err(ResumeError::AlreadyRunning)
+        } else {
+            self.worker.set_paused(false);
+            Ok(())
+        }
+    }
+}
@@ -0,0 +1,187 @@
+//! Drives a `DisplayLink` callback from a dedicated thread, for platforms that only offer a
+//! blocking "wait for the next refresh" primitive.
+
+use crate::{
+    callback::Park,
+    frame::{Clock, DiscontinuityCallback, FrameObservers, RateCallback},
+    DisplayId, FrameTime, FrameTimestamp, PlatformTimestamp,
+};
+use std::{
+    sync::{Arc, Condvar, Mutex},
+    task::Waker,
+    thread::{self, JoinHandle},
+};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum State {
+    Paused,
+    Running,
+    Stopped,
+}
+
+#[derive(Debug)]
+struct Shared {
+    state:   Mutex<State>,
+    condvar: Condvar,
+}
+
+#[derive(Debug)]
+pub(crate) struct Worker {
+    shared:    Arc<Shared>,
+    thread:    Option<JoinHandle<()>>,
+    /// Only set for workers made with [`Worker::spawn_link`].
+    observers: Option<Arc<FrameObservers>>,
+    /// The display of the last frame delivered by a worker made with [`Worker::spawn_link`].
+    display:   Arc<Mutex<DisplayId>>,
+}
+
+impl Worker {
+    /// Spawns a paused worker thread.
+    ///
+    /// While running, the thread calls `wait` to block until the next refresh, then passes its
+    /// result to `deliver`. If `wait` returns `None`, the worker stops for good, dropping `wait`
+    /// and `deliver` on its way out.
+    ///
+    /// `deliver` is called without the state lock held, so it can query the worker, and pausing it
+    /// doesn't wait on a slow callback. A frame being delivered when `set_paused(true)` is called
+    /// still completes, but no later frames are delivered.
+    pub fn spawn<P, W, D>(name: &str, mut wait: W, mut deliver: D) -> Option<Self>
+    where
+        W: 'static + FnMut() -> Option<P> + Send,
+        D: 'static + FnMut(P) + Send,
+    {
+        let shared = Arc::new(Shared {
+            state:   Mutex::new(State::Paused),
+            condvar: Condvar::new(),
+        });
+        let thread = {
+            let shared = Arc::clone(&shared);
+            thread::Builder::new()
+                .name(name.to_owned())
+                .spawn(move || loop {
+                    {
+                        let mut state = shared.state.lock().unwrap();
+                        while *state == State::Paused {
+                            state = shared.condvar.wait(state).unwrap();
+                        }
+                        if *state == State::Stopped {
+                            break;
+                        }
+                    }
+                    let payload = match wait() {
+                        Some(payload) => payload,
+                        None => {
+                            *shared.state.lock().unwrap() = State::Stopped;
+                            break;
+                        }
+                    };
+                    let running = *shared.state.lock().unwrap() == State::Running;
+                    if running {
+                        deliver(payload);
+                    }
+                })
+                .ok()?
+        };
+        Some(Worker {
+            shared,
+            thread: Some(thread),
+            observers: None,
+            display: Arc::new(Mutex::new(DisplayId::UNKNOWN)),
+        })
+    }
+
+    /// Spawns a paused worker thread that converts the results of `wait` into `FrameTime`s for
+    /// `callback`.
+    pub fn spawn_link<T, F, W>(wait: W, mut callback: F) -> Option<Self>
+    where
+        T: FrameTimestamp,
+        F: 'static + FnMut(FrameTime<T>) + Send,
+        W: 'static + FnMut() -> Option<(PlatformTimestamp, DisplayId)> + Send,
+    {
+        let mut clock = Clock::default();
+        let observers = clock.observers();
+        let last_display = Arc::new(Mutex::new(DisplayId::UNKNOWN));
+        let mut worker = {
+            let last_display = Arc::clone(&last_display);
+            Self::spawn("display-link", wait, move |(timestamp, display)| {
+                *last_display.lock().unwrap() = display;
+                callback(clock.frame(timestamp, display))
+            })?
+        };
+        worker.observers = Some(observers);
+        worker.display = last_display;
+        Some(worker)
+    }
+
+    /// Returns the display of the last frame delivered, or `DisplayId::UNKNOWN` before the first
+    /// one.
+    // Windows links report the display of their source instead.
+    #[cfg_attr(target_os = "windows", allow(dead_code))]
+    pub fn display(&self) -> DisplayId {
+        *self.display.lock().unwrap()
+    }
+
+    /// Calls `callback` from the worker thread when the rate of the link's frames changes.
+    pub fn on_refresh_rate_changed(&self, callback: RateCallback) {
+        if let Some(observers) = &self.observers {
+            observers.rate.set_callback(callback)
+        }
+    }
+
+    /// Calls `callback` from the worker thread when the clocks behind the link's timestamps jump.
+    pub fn on_clock_discontinuity(&self, callback: DiscontinuityCallback) {
+        if let Some(observers) = &self.observers {
+            observers.set_discontinuity_callback(callback)
+        }
+    }
+
+    /// Wakes `waker` from the worker thread on each frame.
+    pub fn register_waker(&self, waker: Waker) {
+        if let Some(observers) = &self.observers {
+            observers.set_waker(waker)
+        }
+    }
+
+    pub fn is_paused(&self) -> bool {
+        *self.shared.state.lock().unwrap() != State::Running
+    }
+
+    /// Returns `true` once the worker has stopped for good, e.g. because `wait` returned `None`.
+    pub fn is_stopped(&self) -> bool {
+        *self.shared.state.lock().unwrap() == State::Stopped
+    }
+
+    /// Returns a `Park` pausing the worker once the link's callback pauses or stops it, so it
+    /// stops waiting for refreshes. It's called from the worker thread, between frames.
+    pub fn park(&self) -> Park {
+        let shared = Arc::clone(&self.shared);
+        Park::new(move |gate| {
+            let mut state = shared.state.lock().unwrap();
+            if *state == State::Running && !gate.is_open() {
+                *state = State::Paused;
+            }
+        })
+    }
+
+    pub fn set_paused(&self, paused: bool) {
+        let mut state = self.shared.state.lock().unwrap();
+        if *state != State::Stopped {
+            *state = if paused {
+                State::Paused
+            } else {
+                State::Running
+            };
+            self.shared.condvar.notify_all();
+        }
+    }
+}
+
+impl Drop for Worker {
+    fn drop(&mut self) {
+        *self.shared.state.lock().unwrap() = State::Stopped;
+        self.shared.condvar.notify_all();
+        if let Some(thread) = self.thread.take() {
+            let _ = thread.join();
+        }
+    }
+}